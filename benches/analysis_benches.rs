@@ -0,0 +1,51 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use peekaboo::analysis;
+use peekaboo::cipher_utils;
+use peekaboo::config::Config;
+use peekaboo::decoder::Decoder;
+use peekaboo::VigenereDecoder;
+
+
+// ~300 letters: long enough for key length estimation to settle, short enough for quick iterations.
+const PLAINTEXT: &str = "It is a truth universally acknowledged that a single man in possession of a good fortune must be in want of a wife. However little known the feelings or views of such a man may be on his first entering a neighbourhood, this truth is so well fixed in the minds of the surrounding families that he is considered the rightful property of some one or other of their daughters.";
+const KEYWORD: &[u8] = b"LEMON";
+
+fn vigenere_ciphertext() -> String {
+    let mut index = 0;
+    PLAINTEXT
+        .chars()
+        .map(|c| {
+            if !c.is_ascii_alphabetic() {
+                return c;
+            }
+            let shifted = cipher_utils::shift_char(c, (KEYWORD[index % KEYWORD.len()] - b'A') as i8);
+            index += 1;
+            shifted
+        })
+        .collect()
+}
+
+fn bench_trigram_scoring(c: &mut Criterion) {
+    c.bench_function("score_trigram_log_prob", |b| {
+        b.iter(|| analysis::score_trigram_log_prob(black_box(PLAINTEXT)))
+    });
+}
+
+fn bench_kasiski(c: &mut Criterion) {
+    let ciphertext = vigenere_ciphertext();
+    c.bench_function("estimate_key_lengths", |b| {
+        b.iter(|| analysis::estimate_key_lengths(black_box(&ciphertext), 3, 12))
+    });
+}
+
+fn bench_vigenere_decrypt(c: &mut Criterion) {
+    let ciphertext = vigenere_ciphertext();
+    let decoder = VigenereDecoder::new(&Config::default());
+    let mut group = c.benchmark_group("vigenere");
+    group.sample_size(10);
+    group.bench_function("decrypt", |b| b.iter(|| decoder.decrypt(black_box(&ciphertext))));
+    group.finish();
+}
+
+criterion_group!(benches, bench_trigram_scoring, bench_kasiski, bench_vigenere_decrypt);
+criterion_main!(benches);
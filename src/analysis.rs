@@ -1,6 +1,9 @@
 use std::collections::{HashMap, HashSet};
 use std::cmp::Ordering;
 use once_cell::sync::Lazy;
+use regex::Regex;
+use crate::config::Config;
+use crate::cipher_utils::Alphabet;
 
 const ENGLISH_FREQUENCIES: [f64; 26] = [
     0.08167, 0.01492, 0.02782, 0.04253, 0.12702, 0.02228, 0.02015,
@@ -10,20 +13,37 @@ const ENGLISH_FREQUENCIES: [f64; 26] = [
 ];
 pub const ENGLISH_IC: f64 = 0.0667;
 pub const RANDOM_IC: f64 = 1.0 / 26.0; // Approx 0.03846
-const MIN_CHARS_FOR_MIC: usize = 5;
+pub const DEFAULT_MIN_CHARS_FOR_MIC: usize = 5;
+pub const DEFAULT_KASISKI_MAX_POSITIONS: usize = 50;
+const PLAINTEXT_CHI2_MAX: f64 = 1.0;
+const PLAINTEXT_TRIGRAM_AVG_MIN: f64 = -4.2;
+const PLAINTEXT_IC_TOLERANCE: f64 = 0.015;
 const MIN_COUNT_FOR_LOG: f64 = 0.01;
-
-static ENGLISH_TRIGRAM_DATA: Lazy<(HashMap<String, f64>, f64)> = Lazy::new(|| {
-    const TRIGRAM_COUNTS_STR: &str = include_str!("english_trigrams.txt");
-
+const RANDOM_IC_TOLERANCE: f64 = 0.008;
+const RANDOM_ENTROPY_MIN: f64 = 4.5;
+const RANDOMNESS_MIN_LETTERS: usize = 50;
+const METRICS_TOP_KASISKI_LENGTHS: usize = 3;
+// One letter repeated throughout has an IC of 1.0 and no period worth measuring.
+const DEGENERATE_IC: f64 = 0.9;
+// Random text of n letters repeats roughly n^2 / (2 * 26^3) trigrams; allow a generous margin above that.
+const RANDOM_REPEATED_TRIGRAMS_PER_100: usize = 2;
+
+static ENGLISH_TRIGRAM_DATA: Lazy<(HashMap<String, f64>, f64)> =
+    Lazy::new(|| build_ngram_log_probs(include_str!("english_trigrams.txt"), 3, "english_trigrams.txt"));
+
+static ENGLISH_BIGRAM_DATA: Lazy<(HashMap<String, f64>, f64)> =
+    Lazy::new(|| build_ngram_log_probs(include_str!("english_bigrams.txt"), 2, "english_bigrams.txt"));
+
+// Parses "NGRAM COUNT" lines into log10 probabilities; n-grams absent from the table fall back to the floor.
+fn build_ngram_log_probs(counts_str: &str, n: usize, file_name: &str) -> (HashMap<String, f64>, f64) {
     let mut counts: HashMap<String, u64> = HashMap::new();
     let mut total_count: u64 = 0;
 
-    for line in TRIGRAM_COUNTS_STR.lines() {
+    for line in counts_str.lines() {
         let parts: Vec<&str> = line.split_whitespace().collect();
         if parts.len() == 2 {
             let ngram = parts[0].to_uppercase();
-            if ngram.len() == 3 && ngram.chars().all(|c| c.is_ascii_alphabetic()) {
+            if ngram.len() == n && ngram.chars().all(|c| c.is_ascii_alphabetic()) {
                 if let Ok(count) = parts[1].parse::<u64>() {
                     if count > 0 {
                         counts.insert(ngram, count);
@@ -35,125 +55,227 @@ static ENGLISH_TRIGRAM_DATA: Lazy<(HashMap<String, f64>, f64)> = Lazy::new(|| {
     }
 
     if total_count == 0 {
-        panic!("Failed to parse any valid trigram counts from embedded 'english_trigrams.txt'. Ensure file exists in src/ and has valid data.");
+        panic!("Failed to parse any valid n-gram counts from embedded '{}'. Ensure file exists in src/ and has valid data.", file_name);
     }
 
     let n_float = total_count as f64;
     let floor_log_prob = (MIN_COUNT_FOR_LOG / n_float).log10();
-    let mut log_prob_map = HashMap::with_capacity(17576);
-
-    for c1_val in b'A'..=b'Z' {
-        for c2_val in b'A'..=b'Z' {
-            for c3_val in b'A'..=b'Z' {
-                let c1 = c1_val as char;
-                let c2 = c2_val as char;
-                let c3 = c3_val as char;
-                let trigram = format!("{}{}{}", c1, c2, c3);
-                let count = counts.get(&trigram).cloned().unwrap_or(0);
-                let effective_count = (count as f64).max(MIN_COUNT_FOR_LOG);
-                let log_prob = (effective_count / n_float).log10();
-                log_prob_map.insert(trigram, log_prob);
-            }
-        }
-    }
+    let log_prob_map = counts
+        .into_iter()
+        .map(|(ngram, count)| (ngram, (count as f64 / n_float).log10()))
+        .collect();
 
     (log_prob_map, floor_log_prob)
-});
+}
+
+static ENGLISH_WORD_LIST: Lazy<WordList> = Lazy::new(|| WordList::from_text(include_str!("english_words.txt")));
+
+#[derive(Debug, Clone, Default)]
+pub struct WordList {
+    words: HashSet<String>,
+}
+
+impl WordList {
+    pub fn from_text(text: &str) -> Self {
+        let words = text
+            .split_whitespace()
+            .map(|w| w.to_ascii_uppercase())
+            .filter(|w| w.chars().all(|c| c.is_ascii_alphabetic()))
+            .collect();
+        WordList { words }
+    }
+
+    pub fn english() -> &'static WordList {
+        &ENGLISH_WORD_LIST
+    }
+
+    pub fn words(&self) -> impl Iterator<Item = &str> {
+        self.words.iter().map(String::as_str)
+    }
+
+    pub fn contains(&self, word: &str) -> bool {
+        self.words.contains(&word.to_ascii_uppercase())
+    }
+
+    pub fn len(&self) -> usize {
+        self.words.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+}
+
+pub fn dictionary_word_coverage(text: &str, word_list: &WordList) -> f64 {
+    let tokens: Vec<&str> = text
+        .split_whitespace()
+        .map(|token| token.trim_matches(|c: char| !c.is_ascii_alphabetic()))
+        .filter(|token| !token.is_empty())
+        .collect();
+
+    if tokens.is_empty() {
+        return 0.0;
+    }
+
+    let found = tokens.iter().filter(|token| word_list.contains(token)).count();
+    found as f64 / tokens.len() as f64
+}
 
 pub fn score_trigram_log_prob(text: &str) -> f64 {
     let alpha_text = get_alphabetic_chars(text).to_ascii_uppercase();
+    score_trigram_log_prob_prepared(&alpha_text)
+}
+
+// Trigram log-probability for text that is already uppercase A-Z only.
+// Skips the filtering and allocation done by `score_trigram_log_prob`, so callers scoring many
+// candidates can strip once; any other characters are scored as unseen trigrams.
+pub fn score_trigram_log_prob_prepared(alpha_text: &str) -> f64 {
+    score_ngram_log_prob_prepared(alpha_text, 3, &ENGLISH_TRIGRAM_DATA)
+}
+
+// Mean log-probability per trigram, so texts of different lengths can be compared.
+pub fn score_trigram_avg(text: &str) -> f64 {
+    let alpha_text = get_alphabetic_chars(text).to_ascii_uppercase();
+    score_trigram_avg_prepared(&alpha_text)
+}
+
+pub fn score_trigram_avg_prepared(alpha_text: &str) -> f64 {
     if alpha_text.len() < 3 {
         return -f64::INFINITY;
     }
+    score_trigram_log_prob_prepared(alpha_text) / (alpha_text.len() - 2) as f64
+}
+
+// Whether a text with this many letters is ranked by bigrams: trigrams are too sparse below
+// `bigram_scoring_max_len` letters. 0 disables bigram scoring.
+pub fn prefers_bigrams(letter_count: usize, bigram_scoring_max_len: usize) -> bool {
+    letter_count > 0 && letter_count < bigram_scoring_max_len
+}
 
-    let (log_prob_map, floor_log_prob) = &*ENGLISH_TRIGRAM_DATA;
+pub fn score_bigram_log_prob(text: &str) -> f64 {
+    let alpha_text = get_alphabetic_chars(text).to_ascii_uppercase();
+    score_bigram_log_prob_prepared(&alpha_text)
+}
+
+pub fn score_bigram_log_prob_prepared(alpha_text: &str) -> f64 {
+    score_ngram_log_prob_prepared(alpha_text, 2, &ENGLISH_BIGRAM_DATA)
+}
+
+fn score_ngram_log_prob_prepared(alpha_text: &str, n: usize, data: &(HashMap<String, f64>, f64)) -> f64 {
+    if alpha_text.len() < n {
+        return -f64::INFINITY;
+    }
+
+    let (log_prob_map, floor_log_prob) = data;
 
     let mut total_log_prob = 0.0;
-    let mut trigram_count = 0;
+    let mut ngram_count = 0;
 
-    for i in 0..(alpha_text.len() - 2) {
-        if let Some(trigram) = alpha_text.get(i..i + 3) {
+    for i in 0..=(alpha_text.len() - n) {
+        if let Some(ngram) = alpha_text.get(i..i + n) {
             let log_prob = log_prob_map
-                .get(trigram)
+                .get(ngram)
                 .cloned()
                 .unwrap_or(*floor_log_prob);
             total_log_prob += log_prob;
-            trigram_count += 1;
+            ngram_count += 1;
         }
     }
 
-    if trigram_count == 0 {
+    if ngram_count == 0 {
         return -f64::INFINITY;
     }
 
     total_log_prob
 }
 
-pub fn calculate_frequencies(text: &str) -> Option<([f64; 26], usize)> {
+// Raw A-Z counts (case-insensitive) and their total; None when the text has no letters.
+// Non-ASCII UTF-8 bytes are never ASCII letters, so a byte scan matches filtering chars.
+pub fn letter_counts(text: &str) -> Option<([usize; 26], usize)> {
     let mut counts = [0usize; 26];
-    let mut total_chars = 0usize;
-
-    for c in text.chars() {
-        if c.is_ascii_alphabetic() {
-            let index = (c.to_ascii_uppercase() as u8 - b'A') as usize;
-            if index < 26 {
-                counts[index] += 1;
-                total_chars += 1;
-            }
+    let mut total = 0usize;
+    for b in text.bytes() {
+        if b.is_ascii_alphabetic() {
+            counts[(b.to_ascii_uppercase() - b'A') as usize] += 1;
+            total += 1;
         }
     }
 
-    if total_chars == 0 {
-        return None;
-    }
+    (total > 0).then_some((counts, total))
+}
 
-    let mut frequencies = [0.0f64; 26];
-    for i in 0..26 {
-        frequencies[i] = counts[i] as f64 / total_chars as f64;
+// `letter_counts` over an arbitrary alphabet, indexed by symbol position.
+pub fn letter_counts_in(text: &str, alphabet: &Alphabet) -> Option<(Vec<usize>, usize)> {
+    let mut counts = vec![0usize; alphabet.size()];
+    let mut total = 0usize;
+    for index in text.chars().filter_map(|c| alphabet.index_of(c)) {
+        counts[index] += 1;
+        total += 1;
     }
 
-    Some((frequencies, total_chars))
+    (total > 0).then_some((counts, total))
 }
 
-pub fn find_top_n_caesar_shifts_mic(column_text: &str, n_top: usize) -> Option<Vec<(u8, f64)>> {
-    let mut counts = [0usize; 26];
-    let mut text_len = 0usize;
+pub fn calculate_frequencies_in(text: &str, alphabet: &Alphabet) -> Option<(Vec<f64>, usize)> {
+    let (counts, total) = letter_counts_in(text, alphabet)?;
+    Some((counts.iter().map(|&count| count as f64 / total as f64).collect(), total))
+}
 
-    for c in column_text.chars() {
-        if c.is_ascii_alphabetic() {
-            let index = (c.to_ascii_uppercase() as u8 - b'A') as usize;
-            if index < 26 {
-                counts[index] += 1;
-                text_len += 1;
-            }
-        }
-    }
+pub fn calculate_frequencies(text: &str) -> Option<([f64; 26], usize)> {
+    let (counts, total_chars) = letter_counts(text)?;
+    let frequencies: [f64; 26] = std::array::from_fn(|i| counts[i] as f64 / total_chars as f64);
+    Some((frequencies, total_chars))
+}
 
-    if text_len < MIN_CHARS_FOR_MIC || n_top == 0 {
+// Same contract as `find_top_n_caesar_shifts_mic`, but shifts are ranked by the chi-squared of the
+// unshifted column against English (lowest, i.e. best, first).
+pub fn find_top_n_caesar_shifts_chi2(column_text: &str, n_top: usize, min_chars: usize) -> Option<Vec<(u8, f64)>> {
+    let (observed_freq, text_len) = calculate_frequencies(column_text)?;
+    if text_len < min_chars || n_top == 0 {
         return None;
     }
 
-    let observed_freq: [f64; 26] = {
-        let mut freqs = [0.0; 26];
-        for i in 0..26 {
-            freqs[i] = counts[i] as f64 / text_len as f64;
-        }
-        freqs
-    };
+    let mut shift_scores: Vec<(u8, f64)> = (0..26)
+        .map(|g| {
+            let unshifted: [f64; 26] = std::array::from_fn(|i| observed_freq[(i + g) % 26]);
+            (g as u8, chi_squared_score(&unshifted, &ENGLISH_FREQUENCIES))
+        })
+        .collect();
+
+    shift_scores.sort_by(|a, b| {
+        a.1.partial_cmp(&b.1)
+            .unwrap_or(Ordering::Equal)
+            .then(a.0.cmp(&b.0))
+    });
+    shift_scores.truncate(n_top);
+
+    Some(shift_scores)
+}
+
+pub fn find_top_n_caesar_shifts_mic(column_text: &str, n_top: usize, min_chars: usize) -> Option<Vec<(u8, f64)>> {
+    let (observed_freq, text_len) = calculate_frequencies(column_text)?;
+    if text_len < min_chars || n_top == 0 {
+        return None;
+    }
 
     let mut shift_scores = Vec::with_capacity(26);
 
     for g in 0..26 {
         let mut current_mic_score = 0.0;
-        for i in 0..26 {
+        for (i, expected) in ENGLISH_FREQUENCIES.iter().enumerate() {
             let observed_index = (i + g) % 26;
-            current_mic_score += ENGLISH_FREQUENCIES[i] * observed_freq[observed_index];
+            current_mic_score += expected * observed_freq[observed_index];
         }
         shift_scores.push((g as u8, current_mic_score));
     }
 
 
-    shift_scores.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(Ordering::Equal));
+    // Break score ties on the shift itself so downstream key combinations come out in a stable order.
+    shift_scores.sort_by(|a, b| {
+        b.1.partial_cmp(&a.1)
+            .unwrap_or(Ordering::Equal)
+            .then(a.0.cmp(&b.0))
+    });
 
 
     shift_scores.truncate(n_top);
@@ -177,6 +299,60 @@ fn chi_squared_score(observed: &[f64; 26], expected: &[f64; 26]) -> f64 {
     score
 }
 
+// Expected letter distribution for chi-squared scoring. Entries may be zero for languages or
+// alphabets that never use a letter; observing such a letter then scores `f64::MAX`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LanguageProfile {
+    frequencies: [f64; 26],
+}
+
+impl LanguageProfile {
+    pub fn english() -> Self {
+        LanguageProfile { frequencies: ENGLISH_FREQUENCIES }
+    }
+
+    // Normalizes to proportions; rejects negative or non-finite entries and all-zero distributions.
+    pub fn from_frequencies(frequencies: [f64; 26]) -> Option<Self> {
+        if frequencies.iter().any(|f| !f.is_finite() || *f < 0.0) {
+            return None;
+        }
+        let total: f64 = frequencies.iter().sum();
+        if total <= 0.0 {
+            return None;
+        }
+        Some(LanguageProfile { frequencies: frequencies.map(|f| f / total) })
+    }
+
+    pub fn frequencies(&self) -> &[f64; 26] {
+        &self.frequencies
+    }
+}
+
+pub fn score_likelihood_with_profile(text: &str, profile: &LanguageProfile) -> Option<f64> {
+    calculate_frequencies(text)
+        .map(|(observed_freq, _)| chi_squared_score(&observed_freq, &profile.frequencies))
+}
+
+const ENGLISH_FREQUENCY_ORDER: &[u8; 26] = b"ETAOINSHRDLCUMWFGYPBVKJXQZ";
+
+// Quick substitution guess: `mapping[i]` is the plaintext letter for ciphertext letter 'A' + i, pairing
+// the ciphertext letters by descending frequency with English's frequency order. Ties keep alphabetical order.
+pub fn frequency_rank_mapping(text: &str) -> [char; 26] {
+    let mut counts = [0usize; 26];
+    for b in text.bytes().filter(|b| b.is_ascii_alphabetic()) {
+        counts[(b.to_ascii_uppercase() - b'A') as usize] += 1;
+    }
+
+    let mut ranked: Vec<usize> = (0..26).collect();
+    ranked.sort_by(|&a, &b| counts[b].cmp(&counts[a]).then(a.cmp(&b)));
+
+    let mut mapping = ['A'; 26];
+    for (rank, &cipher_index) in ranked.iter().enumerate() {
+        mapping[cipher_index] = ENGLISH_FREQUENCY_ORDER[rank] as char;
+    }
+    mapping
+}
+
 pub fn score_english_likelihood(text: &str) -> Option<f64> {
     calculate_frequencies(text)
         .map(|(observed_freq, _)| chi_squared_score(&observed_freq, &ENGLISH_FREQUENCIES))
@@ -186,22 +362,92 @@ pub fn get_alphabetic_chars(text: &str) -> String {
     text.chars().filter(|c| c.is_ascii_alphabetic()).collect()
 }
 
-pub fn calculate_ic(text: &str) -> Option<f64> {
-    let alpha_text = get_alphabetic_chars(text);
-    let n = alpha_text.len();
+// Every non-ASCII character with its count, most frequent first. Analysis only reads ASCII, so these
+// are silently ignored; accented letters are not folded to their base letter.
+pub fn non_ascii_report(text: &str) -> Vec<(char, usize)> {
+    let mut counts: HashMap<char, usize> = HashMap::new();
+    for c in text.chars().filter(|c| !c.is_ascii()) {
+        *counts.entry(c).or_default() += 1;
+    }
+    let mut report: Vec<(char, usize)> = counts.into_iter().collect();
+    report.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+    report
+}
 
-    if n < 2 {
+// "12 non-ASCII characters ignored (é x8, ü x4)", or None for pure ASCII input.
+pub fn non_ascii_warning(text: &str) -> Option<String> {
+    let report = non_ascii_report(text);
+    let total: usize = report.iter().map(|(_, count)| count).sum();
+    if total == 0 {
         return None;
     }
+    let listed: Vec<String> = report.iter().map(|(c, count)| format!("{} x{}", c, count)).collect();
+    let noun = if total == 1 { "character" } else { "characters" };
+    Some(format!("{} non-ASCII {} ignored ({})", total, noun, listed.join(", ")))
+}
 
-    let mut counts = [0usize; 26];
-    for c in alpha_text.chars() {
-        let index = (c.to_ascii_uppercase() as u8 - b'A') as usize;
-        if index < 26 {
-            counts[index] += 1;
+pub fn segment_preserving(original: &str, decrypted_alpha: &str) -> String {
+    let mut decrypted_letters = decrypted_alpha.chars().filter(|c| c.is_ascii_alphabetic());
+    let mut result = String::with_capacity(original.len());
+
+    for c in original.chars() {
+        if c.is_ascii_alphabetic() {
+            if let Some(d) = decrypted_letters.next() {
+                // Keep the case of the original position so mixed-case input reads naturally.
+                if c.is_ascii_lowercase() {
+                    result.push(d.to_ascii_lowercase());
+                } else {
+                    result.push(d.to_ascii_uppercase());
+                }
+            }
+        } else {
+            result.push(c);
         }
     }
 
+    result.extend(decrypted_letters);
+    result
+}
+
+pub fn calculate_ic(text: &str) -> Option<f64> {
+    let (counts, n) = letter_counts(text)?;
+    ic_from_counts(&counts, n)
+}
+
+pub fn calculate_ic_in(text: &str, alphabet: &Alphabet) -> Option<f64> {
+    let (counts, n) = letter_counts_in(text, alphabet)?;
+    ic_from_counts(&counts, n)
+}
+
+// Vigenere key letters for per-column shifts: 0 is 'A', 25 is 'Z'.
+pub fn shifts_to_keyword(shifts: &[u8]) -> String {
+    shifts.iter().map(|&shift| (b'A' + shift % 26) as char).collect()
+}
+
+// Inverse of `shifts_to_keyword`, case-insensitive. None for an empty keyword or one with non-letters.
+pub fn keyword_to_shifts(keyword: &str) -> Option<Vec<u8>> {
+    if keyword.is_empty() {
+        return None;
+    }
+    keyword
+        .bytes()
+        .map(|b| b.is_ascii_alphabetic().then(|| b.to_ascii_uppercase() - b'A'))
+        .collect()
+}
+
+// Probability that a letter drawn from each text matches: sum of freq_a[i] * freq_b[i]. Columns
+// enciphered with the same shift score near ENGLISH_IC, differently shifted ones near RANDOM_IC.
+pub fn mutual_ic(a: &str, b: &str) -> Option<f64> {
+    let (freq_a, _) = calculate_frequencies(a)?;
+    let (freq_b, _) = calculate_frequencies(b)?;
+    Some(freq_a.iter().zip(freq_b.iter()).map(|(x, y)| x * y).sum())
+}
+
+fn ic_from_counts(counts: &[usize], n: usize) -> Option<f64> {
+    if n < 2 {
+        return None;
+    }
+
     let mut sum = 0.0;
     for count in counts.iter() {
         sum += (*count as f64) * (*count as f64 - 1.0);
@@ -211,12 +457,108 @@ pub fn calculate_ic(text: &str) -> Option<f64> {
     Some(ic)
 }
 
+#[derive(Debug, Clone, PartialEq)]
+pub struct StreamingSummary {
+    pub letter_counts: [usize; 26],
+    pub char_count: usize,
+    pub alpha_count: usize,
+    pub word_count: usize,
+    pub ic: Option<f64>,
+    pub chi_squared: Option<f64>,
+}
+
+// Accumulates letter counts chunk by chunk so large inputs never need to be held in memory.
+// Words split across chunk boundaries are counted once.
+#[derive(Debug, Clone, Default)]
+pub struct StreamingStats {
+    letter_counts: [usize; 26],
+    char_count: usize,
+    alpha_count: usize,
+    word_count: usize,
+    in_word: bool,
+}
+
+impl StreamingStats {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn push(&mut self, chunk: &str) {
+        for c in chunk.chars() {
+            self.char_count += 1;
+            if c.is_ascii_alphabetic() {
+                self.letter_counts[(c.to_ascii_uppercase() as u8 - b'A') as usize] += 1;
+                self.alpha_count += 1;
+            }
+            if c.is_whitespace() {
+                self.in_word = false;
+            } else if !self.in_word {
+                self.in_word = true;
+                self.word_count += 1;
+            }
+        }
+    }
+
+    pub fn finish(&self) -> StreamingSummary {
+        let chi_squared = (self.alpha_count > 0).then(|| {
+            let total = self.alpha_count as f64;
+            let observed = self.letter_counts.map(|count| count as f64 / total);
+            chi_squared_score(&observed, &ENGLISH_FREQUENCIES)
+        });
+
+        StreamingSummary {
+            letter_counts: self.letter_counts,
+            char_count: self.char_count,
+            alpha_count: self.alpha_count,
+            word_count: self.word_count,
+            ic: ic_from_counts(&self.letter_counts, self.alpha_count),
+            chi_squared,
+        }
+    }
+}
+
+pub fn rolling_ic(text: &str, window: usize) -> Vec<f64> {
+    let indices: Vec<usize> = get_alphabetic_chars(text)
+        .bytes()
+        .map(|b| (b.to_ascii_uppercase() - b'A') as usize)
+        .collect();
+
+    if window < 2 || indices.len() < window {
+        return Vec::new();
+    }
+
+    let denominator = (window * (window - 1)) as f64;
+    let mut counts = [0usize; 26];
+    for &index in &indices[..window] {
+        counts[index] += 1;
+    }
+    // Sum of n*(n-1) over letters, updated incrementally as the window slides.
+    let mut pair_sum: usize = counts.iter().map(|&c| c * c.saturating_sub(1)).sum();
+
+    let mut results = Vec::with_capacity(indices.len() - window + 1);
+    results.push(pair_sum as f64 / denominator);
+
+    for start in 1..=(indices.len() - window) {
+        let outgoing = indices[start - 1];
+        pair_sum -= 2 * (counts[outgoing] - 1);
+        counts[outgoing] -= 1;
+
+        let incoming = indices[start + window - 1];
+        pair_sum += 2 * counts[incoming];
+        counts[incoming] += 1;
+
+        results.push(pair_sum as f64 / denominator);
+    }
+
+    results
+}
+
 fn find_factors(number: usize) -> HashSet<usize> {
     let mut factors = HashSet::new();
     if number == 0 { return factors; }
     let limit = (number as f64).sqrt() as usize;
     for i in 1..=limit {
-        if number % i == 0 {
+        if number.is_multiple_of(i) {
             factors.insert(i);
             factors.insert(number / i);
         }
@@ -224,40 +566,97 @@ fn find_factors(number: usize) -> HashSet<usize> {
     factors
 }
 
-pub fn estimate_key_lengths(text: &str, min_len: usize, max_len: usize) -> Vec<(usize, usize)> {
-    let alpha_text = get_alphabetic_chars(text);
-    if alpha_text.len() < min_len * 2 {
-        return Vec::new();
+fn find_repeated_sequences(alpha_text: &str, min_len: usize, max_len: usize) -> HashMap<String, Vec<usize>> {
+    let mut sequences: HashMap<String, Vec<usize>> = HashMap::new();
+    if min_len == 0 || alpha_text.len() < min_len * 2 {
+        return sequences;
     }
 
-    let mut sequences: HashMap<String, Vec<usize>> = HashMap::new();
     for len in (min_len..=std::cmp::min(max_len, alpha_text.len() / 2)).rev() {
         for i in 0..=(alpha_text.len() - len) {
             let seq = &alpha_text[i..(i + len)];
             if let Some(positions) = sequences.get_mut(seq) {
                 positions.push(i);
-            } else {
-                if alpha_text[(i+1)..].contains(seq) {
-                    sequences.insert(seq.to_string(), vec![i]);
-                }
+            } else if alpha_text[(i+1)..].contains(seq) {
+                sequences.insert(seq.to_string(), vec![i]);
             }
         }
 
     }
 
+    sequences
+}
+
+fn pairwise_distances(positions: &[usize]) -> Vec<usize> {
+    let mut distances = Vec::new();
+    for i in 0..positions.len().saturating_sub(1) {
+        for j in (i + 1)..positions.len() {
+            distances.push(positions[j] - positions[i]);
+        }
+    }
+    distances
+}
+
+pub fn kasiski_sequences(text: &str, min_len: usize, max_len: usize) -> Vec<(String, Vec<usize>, Vec<usize>)> {
+    let alpha_text = get_alphabetic_chars(text);
+
+    let mut results: Vec<(String, Vec<usize>, Vec<usize>)> = find_repeated_sequences(&alpha_text, min_len, max_len)
+        .into_iter()
+        .filter(|(_, positions)| positions.len() > 1)
+        .map(|(seq, positions)| {
+            let distances = pairwise_distances(&positions);
+            (seq, positions, distances)
+        })
+        .collect();
+
+    // Longest sequences first, then by where they first occur.
+    results.sort_by(|a, b| b.0.len().cmp(&a.0.len()).then(a.1[0].cmp(&b.1[0])));
+
+    results
+}
+
+// Kasiski examination limits. `max_positions` caps the occurrences of any one sequence that are
+// paired up, since distances grow quadratically with them.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct KasiskiSettings {
+    pub min_seq_len: usize,
+    pub max_key_len: usize,
+    pub max_positions: usize,
+}
+
+impl KasiskiSettings {
+    pub fn from_config(config: &Config) -> Self {
+        KasiskiSettings {
+            min_seq_len: config.kasiski_min_seq_len,
+            max_key_len: config.kasiski_max_key_len,
+            max_positions: config.kasiski_max_positions,
+        }
+    }
+}
+
+pub fn estimate_key_lengths(text: &str, min_len: usize, max_len: usize) -> Vec<(usize, usize)> {
+    let settings = KasiskiSettings { min_seq_len: min_len, max_key_len: max_len, max_positions: DEFAULT_KASISKI_MAX_POSITIONS };
+    estimate_key_lengths_with(text, &settings)
+}
+
+// Degenerate input (nearly one repeated letter) has repeats everywhere and returns no estimates.
+pub fn estimate_key_lengths_with(text: &str, settings: &KasiskiSettings) -> Vec<(usize, usize)> {
+    let KasiskiSettings { min_seq_len: min_len, max_key_len: max_len, max_positions } = *settings;
+    let alpha_text = get_alphabetic_chars(text);
+    if alpha_text.len() < min_len * 2 || calculate_ic(&alpha_text).is_some_and(|ic| ic >= DEGENERATE_IC) {
+        return Vec::new();
+    }
+
+    let sequences = find_repeated_sequences(&alpha_text, min_len, max_len);
+
 
     let mut factor_counts: HashMap<usize, usize> = HashMap::new();
     for positions in sequences.values() {
-        if positions.len() > 1 {
-            for i in 0..(positions.len() - 1) {
-                for j in (i + 1)..positions.len() {
-                    let distance = positions[j] - positions[i];
-                    let factors = find_factors(distance);
-                    for factor in factors {
-                        if factor > 1 && factor <= max_len {
-                            *factor_counts.entry(factor).or_insert(0) += 1;
-                        }
-                    }
+        for distance in pairwise_distances(&positions[..positions.len().min(max_positions)]) {
+            let factors = find_factors(distance);
+            for factor in factors {
+                if factor > 1 && factor <= max_len {
+                    *factor_counts.entry(factor).or_insert(0) += 1;
                 }
             }
         }
@@ -270,6 +669,29 @@ pub fn estimate_key_lengths(text: &str, min_len: usize, max_len: usize) -> Vec<(
     sorted_factors
 }
 
+// Average column IC for each period 1..=max_period, in period order for tables and plots.
+// Period 1 is the whole text's IC; periods with no measurable column are reported as 0.0.
+pub fn ic_by_period(text: &str, max_period: usize) -> Vec<(usize, f64)> {
+    let alpha_text = get_alphabetic_chars(text);
+
+    (1..=max_period)
+        .map(|period| {
+            let column_ics: Vec<f64> = (0..period)
+                .filter_map(|i| {
+                    let column: String = alpha_text.chars().skip(i).step_by(period).collect();
+                    calculate_ic(&column)
+                })
+                .collect();
+            let avg_ic = if column_ics.is_empty() {
+                0.0
+            } else {
+                column_ics.iter().sum::<f64>() / column_ics.len() as f64
+            };
+            (period, avg_ic)
+        })
+        .collect()
+}
+
 pub fn estimate_key_length_ic_periodicity(text: &str, min_len: usize, max_len: usize) -> Vec<(usize, f64)> {
     let alpha_text = get_alphabetic_chars(text);
     let n = alpha_text.len();
@@ -313,3 +735,262 @@ pub fn estimate_key_length_ic_periodicity(text: &str, min_len: usize, max_len: u
 
     results
 }
+
+// Alternative to `estimate_key_length_ic_periodicity`'s proximity-to-English ranking, which penalizes
+// the true length whenever its columns overshoot English IC. Each length is scored by how far its
+// average column IC rises above `expected_ic_for_key_length`, as a fraction of the rise English would
+// show at that length; higher is better, ties go to the shorter length.
+pub fn rank_key_lengths_by_expected_ic(text: &str, min_len: usize, max_len: usize) -> Vec<(usize, f64)> {
+    let mut results: Vec<(usize, f64)> = estimate_key_length_ic_periodicity(text, min_len, max_len)
+        .into_iter()
+        .filter_map(|(key_len, avg_ic)| {
+            let expected = expected_ic_for_key_length(key_len);
+            let gap = ENGLISH_IC - expected;
+            (gap > 0.0).then(|| (key_len, (avg_ic - expected) / gap))
+        })
+        .collect();
+
+    results.sort_by(|a, b| {
+        b.1.partial_cmp(&a.1)
+            .unwrap_or(Ordering::Equal)
+            .then(a.0.cmp(&b.0))
+    });
+    results
+}
+
+// Common CTF flag wrappers; a match is strong evidence a decryption is right.
+static FLAG_PATTERNS: Lazy<Vec<Regex>> = Lazy::new(|| {
+    [
+        r"(?i)\b(?:flag|ctf|picoctf|htb|thm|ductf)\{[^{}\s]{1,100}\}",
+        r"(?i)\b[a-z0-9]{1,15}ctf\{[^{}\s]{1,100}\}",
+    ]
+    .iter()
+    .map(|pattern| Regex::new(pattern).expect("flag patterns are valid"))
+    .collect()
+});
+
+// First flag-shaped match such as "FLAG{...}" or "CTF{...}", if any.
+pub fn contains_flag_pattern(text: &str) -> Option<String> {
+    FLAG_PATTERNS
+        .iter()
+        .find_map(|pattern| pattern.find(text))
+        .map(|found| found.as_str().to_string())
+}
+
+// Known-plaintext attack: slides the crib along the ciphertext letters and, at each offset, returns
+// the Vigenere key letters (cipher - plain mod 26) that would produce it. Offsets count letters only;
+// a fragment that repeats or spells a word hints at the real key.
+pub fn crib_drag(ciphertext: &str, crib: &str) -> Vec<(usize, String)> {
+    let cipher_letters = get_alphabetic_chars(ciphertext).to_ascii_uppercase().into_bytes();
+    let crib_letters = get_alphabetic_chars(crib).to_ascii_uppercase().into_bytes();
+    if crib_letters.is_empty() || crib_letters.len() > cipher_letters.len() {
+        return Vec::new();
+    }
+
+    cipher_letters
+        .windows(crib_letters.len())
+        .enumerate()
+        .map(|(offset, window)| {
+            let fragment = window
+                .iter()
+                .zip(&crib_letters)
+                .map(|(&c, &p)| (b'A' + (c + 26 - p) % 26) as char)
+                .collect();
+            (offset, fragment)
+        })
+        .collect()
+}
+
+pub fn looks_like_plaintext(text: &str) -> bool {
+    let alpha_len = get_alphabetic_chars(text).len();
+    if alpha_len < 3 {
+        return false;
+    }
+
+    let chi2 = match score_english_likelihood(text) {
+        Some(score) => score,
+        None => return false,
+    };
+    let ic = match calculate_ic(text) {
+        Some(val) => val,
+        None => return false,
+    };
+    let trigram_avg = score_trigram_avg(text);
+
+    chi2 < PLAINTEXT_CHI2_MAX
+        && trigram_avg > PLAINTEXT_TRIGRAM_AVG_MIN
+        && (ic - ENGLISH_IC).abs() < PLAINTEXT_IC_TOLERANCE
+}
+
+// Fraction of adjacent letter pairs that are the same letter (spaces and punctuation skipped).
+// English keeps LL/SS/EE at a few percent under any monoalphabetic cipher; Playfair never
+// enciphers a doubled pair, so its ratio is near zero.
+pub fn doubled_letter_ratio(text: &str) -> Option<f64> {
+    let letters: Vec<u8> = get_alphabetic_chars(text).to_ascii_uppercase().into_bytes();
+    if letters.len() < 2 {
+        return None;
+    }
+
+    let doubled = letters.windows(2).filter(|pair| pair[0] == pair[1]).count();
+    Some(doubled as f64 / (letters.len() - 1) as f64)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RandomnessLevel {
+    Likely,
+    Possible,
+    Unlikely,
+}
+
+// Shannon entropy of the letter distribution in bits; uniform letters give log2(26), about 4.70.
+fn letter_entropy(text: &str) -> Option<f64> {
+    let (frequencies, _) = calculate_frequencies(text)?;
+    Some(
+        frequencies
+            .iter()
+            .filter(|&&p| p > 0.0)
+            .map(|&p| -p * p.log2())
+            .sum(),
+    )
+}
+
+// One-time-pad or random letters have flat frequencies, random-level IC, and almost no repeated
+// trigrams. All three signals make randomness likely; two make it possible.
+pub fn randomness_indicator(text: &str) -> RandomnessLevel {
+    let alpha_text = get_alphabetic_chars(text).to_ascii_uppercase();
+    if alpha_text.len() < RANDOMNESS_MIN_LETTERS {
+        return RandomnessLevel::Unlikely;
+    }
+
+    let ic_near_random = calculate_ic(&alpha_text).is_some_and(|ic| (ic - RANDOM_IC).abs() < RANDOM_IC_TOLERANCE);
+    let high_entropy = letter_entropy(&alpha_text).is_some_and(|entropy| entropy > RANDOM_ENTROPY_MIN);
+    let repeated_trigrams = find_repeated_sequences(&alpha_text, 3, 3).len();
+    let few_repeats = repeated_trigrams <= alpha_text.len() * RANDOM_REPEATED_TRIGRAMS_PER_100 / 100;
+
+    match [ic_near_random, high_entropy, few_repeats].iter().filter(|&&signal| signal).count() {
+        3 => RandomnessLevel::Likely,
+        2 => RandomnessLevel::Possible,
+        _ => RandomnessLevel::Unlikely,
+    }
+}
+
+// Theoretical whole-text IC of English enciphered with a key of length k: one pair in k shares a
+// column and keeps English IC, the rest look random. k = 0 is treated as 1.
+pub fn expected_ic_for_key_length(k: usize) -> f64 {
+    let k = k.max(1) as f64;
+    ENGLISH_IC / k + RANDOM_IC * (k - 1.0) / k
+}
+
+// Friedman test: expected key length from how far the text's IC sits between random and English.
+fn friedman_key_length(ic: f64, n: usize) -> Option<f64> {
+    let n = n as f64;
+    let denominator = (n - 1.0) * ic - RANDOM_IC * n + ENGLISH_IC;
+    (denominator > 0.0).then(|| (ENGLISH_IC - RANDOM_IC) * n / denominator)
+}
+
+// Share of non-whitespace characters that must be dots, dashes or '/' word separators.
+const MIN_MORSE_SYMBOL_RATIO: f64 = 0.9;
+
+// True when the input is overwhelmingly Morse symbols, so it should be decoded as Morse rather than
+// analyzed as letters.
+pub fn looks_like_morse(text: &str) -> bool {
+    let symbols: Vec<char> = text.chars().filter(|c| !c.is_whitespace()).collect();
+    if !symbols.iter().any(|&c| c == '.' || c == '-') {
+        return false;
+    }
+
+    let morse_symbols = symbols.iter().filter(|&&c| matches!(c, '.' | '-' | '/')).count();
+    morse_symbols as f64 / symbols.len() as f64 >= MIN_MORSE_SYMBOL_RATIO
+}
+
+// Chi-squared against English picks the right Caesar shift reliably from about this many letters.
+pub const CAESAR_MIN_LETTERS: usize = 10;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SufficiencyReport {
+    pub alpha_count: usize,
+    pub caesar: bool,
+    pub vigenere_identification: bool,
+    pub vigenere_decode: bool,
+    // Letters still missing before every check passes and each column of the longest key length
+    // tried has enough letters for MIC; 0 when the text is long enough.
+    pub additional_letters_advised: usize,
+    // Morse, A1Z26 or Polybius input, written in more cipher symbols than letters; letter counts
+    // say nothing about whether it can be decoded, so no advice is given.
+    pub symbol_encoded: bool,
+}
+
+impl SufficiencyReport {
+    pub fn advice(&self) -> Option<String> {
+        (self.additional_letters_advised > 0 && !self.symbol_encoded).then(|| {
+            format!(
+                "Ciphertext has {} letters; about {} more would make the analysis reliable.",
+                self.alpha_count, self.additional_letters_advised
+            )
+        })
+    }
+}
+
+// English-like IC but un-English frequencies: letters were swapped one-for-one, not shuffled.
+pub fn is_monoalphabetic_signature(ic: f64, chi_squared: f64, config: &Config) -> bool {
+    ic > config.mono_ic_min && chi_squared > config.mono_chi2_min
+}
+
+// Whether the text has enough letters for each kind of analysis under `config`'s minimums.
+pub fn sufficiency(text: &str, config: &Config) -> SufficiencyReport {
+    let alpha_count = get_alphabetic_chars(text).len();
+    let symbol_count = text.chars().filter(|c| c.is_ascii_digit() || matches!(c, '.' | '-')).count();
+    let advised = [
+        CAESAR_MIN_LETTERS,
+        config.vigenere_min_id_len,
+        config.vigenere_min_dec_len,
+        config.kasiski_max_dec_key_len * config.min_chars_for_mic,
+    ]
+    .into_iter()
+    .max()
+    .unwrap_or(0);
+
+    SufficiencyReport {
+        alpha_count,
+        caesar: alpha_count >= CAESAR_MIN_LETTERS,
+        vigenere_identification: alpha_count >= config.vigenere_min_id_len,
+        vigenere_decode: alpha_count >= config.vigenere_min_dec_len,
+        additional_letters_advised: advised.saturating_sub(alpha_count),
+        symbol_encoded: looks_like_morse(text) || symbol_count > alpha_count,
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AnalysisMetrics {
+    pub alpha_count: usize,
+    pub ic: Option<f64>,
+    pub normalized_ic: Option<f64>,
+    pub chi_squared: f64,
+    pub entropy: f64,
+    pub friedman_key_length: Option<f64>,
+    pub top_kasiski_lengths: Vec<usize>,
+}
+
+// Every statistic in one place so callers can log or export it; None only without letters.
+pub fn compute_metrics(text: &str, config: &Config) -> Option<AnalysisMetrics> {
+    let alpha_text = get_alphabetic_chars(text).to_ascii_uppercase();
+    let chi_squared = score_english_likelihood(&alpha_text)?;
+    let entropy = letter_entropy(&alpha_text)?;
+    let ic = calculate_ic(&alpha_text);
+
+    let top_kasiski_lengths = estimate_key_lengths_with(&alpha_text, &KasiskiSettings::from_config(config))
+        .into_iter()
+        .take(METRICS_TOP_KASISKI_LENGTHS)
+        .map(|(len, _count)| len)
+        .collect();
+
+    Some(AnalysisMetrics {
+        alpha_count: alpha_text.len(),
+        ic,
+        normalized_ic: ic.map(|ic| ic * 26.0),
+        chi_squared,
+        entropy,
+        friedman_key_length: ic.and_then(|ic| friedman_key_length(ic, alpha_text.len())),
+        top_kasiski_lengths,
+    })
+}
@@ -1,3 +1,10 @@
+pub const STANDARD_ALPHABET: [char; 26] = [
+    'A', 'B', 'C', 'D', 'E', 'F', 'G', 'H', 'I', 'J', 'K', 'L', 'M',
+    'N', 'O', 'P', 'Q', 'R', 'S', 'T', 'U', 'V', 'W', 'X', 'Y', 'Z',
+];
+
+// A-Z specialization of `shift_char_in_alphabet`, kept as plain arithmetic because it sits in the hot
+// decryption loops.
 pub fn shift_char(c: char, shift: i8) -> char {
     if !c.is_ascii_alphabetic() {
         return c;
@@ -15,3 +22,187 @@ pub fn shift_char(c: char, shift: i8) -> char {
 pub fn shift_char_string(s: &str, shift: i8) -> String {
     s.chars().map(|c| shift_char(c, shift)).collect()
 }
+
+// Shifts each letter by the next keystream value; non-letters pass through without advancing the
+// keystream. Letters left over once the keystream runs out are copied unchanged.
+pub fn apply_keystream(text: &str, mut keystream: impl Iterator<Item = i8>) -> String {
+    text.chars()
+        .map(|c| match c.is_ascii_alphabetic().then(|| keystream.next()).flatten() {
+            Some(shift) => shift_char(c, shift),
+            None => c,
+        })
+        .collect()
+}
+
+// Puts `letters` back into the layout of `template`: each ASCII letter of the template is replaced by
+// the next letter, taking the template letter's case; everything else is copied as is.
+pub fn restore_layout(template: &str, letters: &str) -> String {
+    let mut letters = letters.chars();
+    template
+        .chars()
+        .map(|c| match c.is_ascii_alphabetic().then(|| letters.next()).flatten() {
+            Some(letter) if c.is_ascii_lowercase() => letter.to_ascii_lowercase(),
+            Some(letter) => letter.to_ascii_uppercase(),
+            None => c,
+        })
+        .collect()
+}
+
+// Mirrors the alphabet (A<->Z, B<->Y, ...), keeping case; other characters pass through.
+pub fn atbash(s: &str) -> String {
+    s.chars()
+        .map(|c| match c {
+            'A'..='Z' => (b'Z' - (c as u8 - b'A')) as char,
+            'a'..='z' => (b'z' - (c as u8 - b'a')) as char,
+            _ => c,
+        })
+        .collect()
+}
+
+const ALPHANUMERIC_ALPHABET: &[u8; 36] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789";
+
+// Shifts over the 36-symbol alphabet A-Z then 0-9, so 'Z' + 1 is '0' and '9' + 1 wraps to 'A'.
+// Lowercase letters keep their case when they land on a letter; other characters pass through.
+pub fn shift_char36(c: char, shift: i8) -> char {
+    let upper = c.to_ascii_uppercase();
+    let Some(index) = ALPHANUMERIC_ALPHABET.iter().position(|&s| s as char == upper) else {
+        return c;
+    };
+
+    let shifted = ALPHANUMERIC_ALPHABET[(index as i16 + shift as i16).rem_euclid(36) as usize] as char;
+    if c.is_ascii_lowercase() { shifted.to_ascii_lowercase() } else { shifted }
+}
+
+pub fn shift_char36_string(s: &str, shift: i8) -> String {
+    s.chars().map(|c| shift_char36(c, shift)).collect()
+}
+
+// Shifts by position within a custom ordering (e.g. a keyed or scrambled alphabet). Lowercase input
+// is matched against the alphabet case-insensitively and keeps its case; anything else passes through.
+pub fn shift_char_in_alphabet(c: char, shift: i8, alphabet: &[char; 26]) -> char {
+    let lookup = |target: char| alphabet.iter().position(|&a| a == target);
+    let shift_from = |index: usize| alphabet[(index as i16 + shift as i16).rem_euclid(26) as usize];
+
+    if let Some(index) = lookup(c) {
+        return shift_from(index);
+    }
+    match lookup(c.to_ascii_uppercase()) {
+        Some(index) if c.is_ascii_lowercase() => shift_from(index).to_ascii_lowercase(),
+        _ => c,
+    }
+}
+
+// An ordered symbol set for languages or symbol sets other than A-Z (e.g. the 33-letter Russian
+// alphabet). Symbols are matched case-insensitively through their uppercase form. The A-Z functions
+// elsewhere stay specialized for speed; the `_in` variants take an `Alphabet`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Alphabet {
+    symbols: Vec<char>,
+}
+
+impl Default for Alphabet {
+    fn default() -> Self {
+        Alphabet::latin()
+    }
+}
+
+impl Alphabet {
+    // None unless there are at least two symbols and none repeat (ignoring case).
+    pub fn new(symbols: &str) -> Option<Self> {
+        let symbols: Vec<char> = symbols.chars().map(to_upper_char).collect();
+        let mut sorted = symbols.clone();
+        sorted.sort_unstable();
+        sorted.dedup();
+        (symbols.len() >= 2 && sorted.len() == symbols.len()).then_some(Alphabet { symbols })
+    }
+
+    pub fn latin() -> Self {
+        Alphabet { symbols: STANDARD_ALPHABET.to_vec() }
+    }
+
+    pub fn size(&self) -> usize {
+        self.symbols.len()
+    }
+
+    pub fn index_of(&self, c: char) -> Option<usize> {
+        let upper = to_upper_char(c);
+        self.symbols.iter().position(|&s| s == upper)
+    }
+
+    pub fn symbol(&self, index: usize) -> char {
+        self.symbols[index % self.symbols.len()]
+    }
+}
+
+// Single-char uppercase; characters whose uppercase form is several chars are left alone.
+fn to_upper_char(c: char) -> char {
+    let mut upper = c.to_uppercase();
+    match (upper.next(), upper.next()) {
+        (Some(u), None) => u,
+        _ => c,
+    }
+}
+
+// `shift_char` over any alphabet: lowercase input keeps its case, symbols outside it pass through.
+pub fn shift_char_in(c: char, shift: i8, alphabet: &Alphabet) -> char {
+    let Some(index) = alphabet.index_of(c) else {
+        return c;
+    };
+    let shifted = alphabet.symbol((index as i64 + shift as i64).rem_euclid(alphabet.size() as i64) as usize);
+    if c.is_lowercase() {
+        shifted.to_lowercase().next().unwrap_or(shifted)
+    } else {
+        shifted
+    }
+}
+
+// Windows (\r\n) and old Mac (\r) line endings become \n so pasted text analyzes the same everywhere.
+pub fn normalize_input(text: &str) -> String {
+    text.replace("\r\n", "\n").replace('\r', "\n")
+}
+
+// Replaces each run of whitespace with a single space and trims the ends.
+pub fn collapse_whitespace(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<&str>>().join(" ")
+}
+
+// Keyword letters first (deduplicated), then the rest of A-Z in order.
+pub fn keyed_alphabet(keyword: &str) -> [char; 26] {
+    let mut alphabet = ['A'; 26];
+    let mut seen = [false; 26];
+    let mut len = 0;
+
+    let keyword_letters = keyword.chars().filter(|c| c.is_ascii_alphabetic()).map(|c| c.to_ascii_uppercase());
+    for c in keyword_letters.chain(STANDARD_ALPHABET) {
+        let index = (c as u8 - b'A') as usize;
+        if !seen[index] {
+            seen[index] = true;
+            alphabet[len] = c;
+            len += 1;
+        }
+    }
+    alphabet
+}
+
+// Drops the lowest-ranked candidates from the longest shortlists until the
+// cartesian product of all columns fits within `max_combinations`.
+pub fn limit_candidate_combinations(candidates_per_column: &mut [Vec<u8>], max_combinations: usize) {
+    let total = |columns: &[Vec<u8>]| -> usize {
+        columns.iter().map(|v| v.len()).fold(1usize, |acc, len| acc.saturating_mul(len))
+    };
+
+    while total(candidates_per_column) > max_combinations {
+        match candidates_per_column.iter_mut().filter(|v| v.len() > 1).max_by_key(|v| v.len()) {
+            Some(longest) => { longest.pop(); }
+            None => break,
+        }
+    }
+}
+
+// Number of differing bits between two equal-length byte strings; `None` if the lengths differ.
+pub fn hamming_distance(a: &[u8], b: &[u8]) -> Option<usize> {
+    if a.len() != b.len() {
+        return None;
+    }
+    Some(a.iter().zip(b).map(|(x, y)| (x ^ y).count_ones() as usize).sum())
+}
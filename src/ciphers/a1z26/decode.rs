@@ -0,0 +1,60 @@
+use crate::decoder::DecryptionAttempt;
+use crate::analysis;
+
+
+const LETTER_SEPARATORS: [char; 2] = ['-', ','];
+const WORD_SEPARATOR: char = '/';
+
+
+fn decode_number(token: &str) -> Option<char> {
+    match token.parse::<u8>() {
+        Ok(n @ 1..=26) => Some((b'A' + n - 1) as char),
+        _ => None,
+    }
+}
+
+// Letters are split by '-', ',' or whitespace. When dashes or commas separate the letters,
+// whitespace separates words ("8-9 20-8-5-18-5"); '/' always separates words.
+fn decode_a1z26(text: &str) -> Option<String> {
+    if !text.chars().all(|c| c.is_ascii_digit() || c.is_whitespace() || LETTER_SEPARATORS.contains(&c) || c == WORD_SEPARATOR) {
+        return None;
+    }
+
+    let normalized = text.trim().replace(", ", ",");
+    let whitespace_splits_words = normalized.contains(LETTER_SEPARATORS);
+    let words: Vec<&str> = normalized
+        .split(|c: char| c == WORD_SEPARATOR || (whitespace_splits_words && c.is_whitespace()))
+        .filter(|word| !word.trim().is_empty())
+        .collect();
+
+    let decoded_words = words
+        .iter()
+        .map(|word| {
+            word.split(|c: char| c.is_whitespace() || LETTER_SEPARATORS.contains(&c))
+                .filter(|token| !token.is_empty())
+                .map(decode_number)
+                .collect::<Option<String>>()
+        })
+        .collect::<Option<Vec<String>>>()?;
+
+    let plaintext = decoded_words.join(" ");
+    (!plaintext.is_empty()).then_some(plaintext)
+}
+
+
+pub(super) fn run_a1z26_decoding(ciphertext: &str) -> Vec<DecryptionAttempt> {
+    let Some(plaintext) = decode_a1z26(ciphertext) else {
+        return Vec::new();
+    };
+
+    let score = analysis::score_english_likelihood(&plaintext).unwrap_or(f64::MAX);
+
+    vec![DecryptionAttempt {
+        cipher_name: "A1Z26".to_string(),
+        key: "a1z26".to_string(),
+        plaintext,
+        score,
+        note: None,
+        solver_stats: None,
+    }]
+}
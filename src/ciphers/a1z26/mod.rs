@@ -0,0 +1,31 @@
+mod decode;
+
+use crate::decoder::{Decoder, DecryptionAttempt};
+use crate::config::Config;
+
+#[derive(Debug, Default)]
+pub struct A1Z26Decoder;
+
+impl A1Z26Decoder {
+    pub fn new(_config: &Config) -> Self {
+        Default::default()
+    }
+}
+
+impl Decoder for A1Z26Decoder {
+    fn decrypt(&self, ciphertext: &str) -> Vec<DecryptionAttempt> {
+        decode::run_a1z26_decoding(ciphertext)
+    }
+
+    fn name(&self) -> &'static str {
+        "A1Z26"
+    }
+
+    fn description(&self) -> &'static str {
+        "Letters written as their alphabet positions, A=1 to Z=26"
+    }
+
+    fn cache_key(&self) -> Option<String> {
+        Some(format!("{:?}", self))
+    }
+}
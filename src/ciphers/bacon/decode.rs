@@ -0,0 +1,75 @@
+use crate::decoder::{self, DecryptionAttempt, ScoringMethod};
+use crate::analysis;
+
+
+const GROUP_LEN: usize = 5;
+// The original table shares codes between I/J and U/V.
+const ALPHABET_24: &[u8] = b"ABCDEFGHIKLMNOPQRSTUWXYZ";
+const ALPHABET_26: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+const VARIANTS: [(&str, &[u8]); 2] = [("24-letter", ALPHABET_24), ("26-letter", ALPHABET_26)];
+// Ordinary text is almost all one case; a case-encoded message needs plenty of both.
+const MIN_MINORITY_CASE_RATIO: f64 = 0.2;
+
+
+// Each symbol is `true` for the second type ('B'). Dividers between groups are ignored.
+fn ab_symbols(text: &str) -> Option<Vec<bool>> {
+    if !text.chars().all(|c| matches!(c.to_ascii_uppercase(), 'A' | 'B') || c.is_whitespace() || matches!(c, '/' | '-' | '|')) {
+        return None;
+    }
+    let symbols: Vec<bool> = text.chars().filter(|c| c.is_ascii_alphabetic()).map(|c| c.eq_ignore_ascii_case(&'B')).collect();
+    (!symbols.is_empty() && symbols.len().is_multiple_of(GROUP_LEN)).then_some(symbols)
+}
+
+// Uppercase letters are `true`; trailing letters past the last full group are cover text.
+fn case_symbols(text: &str) -> Option<Vec<bool>> {
+    let symbols: Vec<bool> = text.chars().filter(|c| c.is_ascii_alphabetic()).map(|c| c.is_ascii_uppercase()).collect();
+    let upper = symbols.iter().filter(|&&b| b).count();
+    let minority = upper.min(symbols.len() - upper);
+    if symbols.len() < GROUP_LEN || (minority as f64) < symbols.len() as f64 * MIN_MINORITY_CASE_RATIO {
+        return None;
+    }
+    Some(symbols[..symbols.len() - symbols.len() % GROUP_LEN].to_vec())
+}
+
+// None when a group's code falls outside the table.
+fn decode_groups(symbols: &[bool], alphabet: &[u8], invert: bool) -> Option<String> {
+    symbols
+        .chunks(GROUP_LEN)
+        .map(|group| {
+            let code = group.iter().fold(0, |acc, &bit| (acc << 1) | usize::from(bit != invert));
+            alphabet.get(code).map(|&b| b as char)
+        })
+        .collect()
+}
+
+
+pub(super) fn run_bacon_decoding(ciphertext: &str) -> Vec<DecryptionAttempt> {
+    // A/B text is read only one way round; with case either type may stand for 'A'.
+    let (symbols, polarities): (Vec<bool>, &[(bool, &str)]) = if let Some(symbols) = ab_symbols(ciphertext) {
+        (symbols, &[(false, "A/B")])
+    } else if let Some(symbols) = case_symbols(ciphertext) {
+        (symbols, &[(false, "lowercase=A"), (true, "uppercase=A")])
+    } else {
+        return Vec::new();
+    };
+
+    let mut attempts = Vec::new();
+    for &(invert, polarity) in polarities {
+        for (variant, alphabet) in VARIANTS {
+            let Some(plaintext) = decode_groups(&symbols, alphabet, invert) else {
+                continue;
+            };
+            attempts.push(DecryptionAttempt {
+                cipher_name: "Bacon".to_string(),
+                key: format!("{}, {}", variant, polarity),
+                score: analysis::score_english_likelihood(&plaintext).unwrap_or(f64::MAX),
+                plaintext,
+                note: None,
+                solver_stats: None,
+            });
+        }
+    }
+
+    decoder::sort_best_first(&mut attempts, ScoringMethod::ChiSquared);
+    attempts
+}
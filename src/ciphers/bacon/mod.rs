@@ -0,0 +1,31 @@
+mod decode;
+
+use crate::decoder::{Decoder, DecryptionAttempt};
+use crate::config::Config;
+
+#[derive(Debug, Default)]
+pub struct BaconDecoder;
+
+impl BaconDecoder {
+    pub fn new(_config: &Config) -> Self {
+        Default::default()
+    }
+}
+
+impl Decoder for BaconDecoder {
+    fn decrypt(&self, ciphertext: &str) -> Vec<DecryptionAttempt> {
+        decode::run_bacon_decoding(ciphertext)
+    }
+
+    fn name(&self) -> &'static str {
+        "Bacon"
+    }
+
+    fn description(&self) -> &'static str {
+        "Letters hidden as five-symbol groups of two types, such as A/B or letter case"
+    }
+
+    fn cache_key(&self) -> Option<String> {
+        Some(format!("{:?}", self))
+    }
+}
@@ -1,39 +1,92 @@
-use crate::decoder::DecryptionAttempt;
+use crate::decoder::{self, DecryptionAttempt, ScoringMethod};
 use crate::analysis;
 use crate::cipher_utils;
-use std::cmp::Ordering;
 
 
-pub(super) fn run_caesar_decryption(ciphertext: &str) -> Vec<DecryptionAttempt> {
+// Shifting keeps the letter count, so the ciphertext and every candidate use the same method.
+pub(super) fn scoring_method(text: &str, bigram_scoring_max_len: usize) -> ScoringMethod {
+    if analysis::prefers_bigrams(analysis::get_alphabetic_chars(text).len(), bigram_scoring_max_len) {
+        ScoringMethod::Bigram
+    } else {
+        ScoringMethod::ChiSquared
+    }
+}
+
+fn score_candidate(plaintext: &str, bigram_scoring_max_len: usize) -> Option<f64> {
+    let alpha_text = analysis::get_alphabetic_chars(plaintext).to_ascii_uppercase();
+    if analysis::prefers_bigrams(alpha_text.len(), bigram_scoring_max_len) {
+        return Some(analysis::score_bigram_log_prob_prepared(&alpha_text));
+    }
+    analysis::score_english_likelihood(plaintext)
+}
+
+pub(super) fn run_caesar_decryption(ciphertext: &str, bigram_scoring_max_len: usize) -> Vec<DecryptionAttempt> {
+    let all_shifts: Vec<u8> = (0..26).collect();
+    run_caesar_decryption_for_shifts(ciphertext, &all_shifts, bigram_scoring_max_len)
+}
+
+
+pub(super) fn run_caesar_decryption_for_shifts(
+    ciphertext: &str,
+    shifts: &[u8],
+    bigram_scoring_max_len: usize,
+) -> Vec<DecryptionAttempt> {
     let mut attempts = Vec::new();
 
-    for shift in 0..26 {
-        let target_shift = shift as i8;
-        let potential_plaintext: String = ciphertext
-            .chars()
-            .map(|c| cipher_utils::shift_char(c, -target_shift))
-            .collect();
+    for shift in shifts.iter().map(|s| s % 26) {
+        let potential_plaintext = cipher_utils::apply_keystream(ciphertext, std::iter::repeat(-(shift as i8)));
 
-        if let Some(score) = analysis::score_english_likelihood(&potential_plaintext) {
+        if let Some(score) = score_candidate(&potential_plaintext, bigram_scoring_max_len) {
             attempts.push(DecryptionAttempt {
                 cipher_name: "Caesar".to_string(),
                 key: shift.to_string(),
                 plaintext: potential_plaintext,
                 score,
+                note: None,
+                solver_stats: None,
+            });
+        } else if !potential_plaintext.is_empty()
+            && attempts.is_empty()
+            && shift == 0
+            && ciphertext.chars().any(|c| !c.is_ascii_alphabetic())
+        {
+            attempts.push(DecryptionAttempt {
+                cipher_name: "Caesar".to_string(),
+                key: shift.to_string(),
+                plaintext: potential_plaintext,
+                score: f64::MAX,
+                note: None,
+                solver_stats: None,
             });
-        } else if !potential_plaintext.is_empty() && attempts.is_empty() {
-            if shift == 0 && ciphertext.chars().any(|c| !c.is_ascii_alphabetic()) {
-                attempts.push(DecryptionAttempt {
-                    cipher_name: "Caesar".to_string(),
-                    key: shift.to_string(),
-                    plaintext: potential_plaintext,
-                    score: f64::MAX,
-                });
-            }
         }
     }
 
-    attempts.sort_by(|a, b| a.score.partial_cmp(&b.score).unwrap_or(Ordering::Equal));
+    decoder::sort_best_first(&mut attempts, scoring_method(ciphertext, bigram_scoring_max_len));
 
     attempts
 }
+
+
+// Unlike the search, unscorable candidates are kept with the worst possible score.
+fn attempt_for_shift(ciphertext: &str, shift: u8, bigram_scoring_max_len: usize) -> DecryptionAttempt {
+    let plaintext = cipher_utils::shift_char_string(ciphertext, -(shift as i8));
+    let score = score_candidate(&plaintext, bigram_scoring_max_len).unwrap_or(f64::MAX);
+
+    DecryptionAttempt {
+        cipher_name: "Caesar".to_string(),
+        key: shift.to_string(),
+        plaintext,
+        score,
+        note: None,
+        solver_stats: None,
+    }
+}
+
+pub(super) fn run_caesar_decryption_with_key(ciphertext: &str, key: &str, bigram_scoring_max_len: usize) -> Option<DecryptionAttempt> {
+    let shift = key.trim().parse::<i32>().ok()?.rem_euclid(26);
+    Some(attempt_for_shift(ciphertext, shift as u8, bigram_scoring_max_len))
+}
+
+pub(super) fn run_caesar_decryption_table(ciphertext: &str, bigram_scoring_max_len: usize) -> Vec<DecryptionAttempt> {
+    (0..26).map(|shift| attempt_for_shift(ciphertext, shift, bigram_scoring_max_len)).collect()
+}
@@ -1,11 +1,14 @@
-use crate::identifier::IdentificationResult;
+use crate::identifier::{CipherParams, IdentificationResult};
 use crate::analysis; // Added use statement
 use crate::cipher_utils;
+use std::cmp::Ordering;
 
 
-pub(super) fn run_caesar_identification(ciphertext: &str) -> Option<IdentificationResult> {
-    let mut best_score = f64::MAX;
-    let mut best_shift: Option<u8> = None;
+const TOP_N_CAESAR_CANDIDATES: usize = 5;
+
+
+fn score_all_shifts(ciphertext: &str) -> Vec<(u8, f64)> {
+    let mut shift_scores = Vec::with_capacity(26);
 
     for shift in 0..26 {
         let potential_plaintext: String = ciphertext
@@ -14,16 +17,61 @@ pub(super) fn run_caesar_identification(ciphertext: &str) -> Option<Identificati
             .collect();
 
         if let Some(score) = analysis::score_english_likelihood(&potential_plaintext) {
-            if score < best_score {
-                best_score = score;
-                best_shift = Some(shift);
-            }
+            shift_scores.push((shift, score));
         }
     }
 
-    best_shift.map(|shift| IdentificationResult {
+    shift_scores.sort_by(|a, b| {
+        a.1.partial_cmp(&b.1)
+            .unwrap_or(Ordering::Equal)
+            .then(a.0.cmp(&b.0))
+    });
+    shift_scores
+}
+
+fn to_identification_result((shift, score): (u8, f64)) -> IdentificationResult {
+    IdentificationResult {
         cipher_name: "Caesar".to_string(),
-        confidence_score: best_score,
+        confidence_score: score,
         parameters: Some(format!("Potential Shift: {}", shift)),
-    })
+        params: CipherParams::Caesar { shift },
+    }
+}
+
+
+pub(super) fn run_caesar_identification(ciphertext: &str) -> Option<IdentificationResult> {
+    score_all_shifts(ciphertext)
+        .into_iter()
+        .next()
+        .map(to_identification_result)
+}
+
+// Scans shifts in order and stops at the first one whose chi-squared is below `strong_match_chi2`;
+// if none is that strong, this is the same as the full search.
+pub(super) fn run_caesar_identification_early_exit(ciphertext: &str, strong_match_chi2: f64) -> Option<IdentificationResult> {
+    let mut best: Option<(u8, f64)> = None;
+
+    for shift in 0..26u8 {
+        let potential_plaintext = cipher_utils::shift_char_string(ciphertext, -(shift as i8));
+        let Some(score) = analysis::score_english_likelihood(&potential_plaintext) else {
+            continue;
+        };
+
+        if score < strong_match_chi2 {
+            return Some(to_identification_result((shift, score)));
+        }
+        if best.is_none_or(|(_, best_score)| score < best_score) {
+            best = Some((shift, score));
+        }
+    }
+
+    best.map(to_identification_result)
+}
+
+pub(super) fn run_caesar_identification_all(ciphertext: &str) -> Vec<IdentificationResult> {
+    score_all_shifts(ciphertext)
+        .into_iter()
+        .take(TOP_N_CAESAR_CANDIDATES)
+        .map(to_identification_result)
+        .collect()
 }
@@ -2,39 +2,93 @@ mod identify;
 mod decode;
 
 use crate::identifier::{Identifier, IdentificationResult};
-use crate::decoder::{Decoder, DecryptionAttempt};
+use crate::decoder::{Decoder, DecryptionAttempt, ScoringMethod};
 use crate::config::Config;
 
 #[derive(Default)]
-pub struct CaesarIdentifier;
+pub struct CaesarIdentifier {
+    strong_match_chi2: Option<f64>,
+}
 
-#[derive(Default)]
-pub struct CaesarDecoder;
+#[derive(Debug, Default)]
+pub struct CaesarDecoder {
+    bigram_scoring_max_len: usize,
+}
 
 impl CaesarIdentifier {
-    pub fn new(_config: &Config) -> Self {
-        Default::default()
+    pub fn new(config: &Config) -> Self {
+        CaesarIdentifier {
+            strong_match_chi2: config.caesar_strong_match_chi2,
+        }
+    }
+
+    pub fn identify_full_search(&self, ciphertext: &str) -> Option<IdentificationResult> {
+        identify::run_caesar_identification(ciphertext)
     }
 }
 
 impl CaesarDecoder {
-    pub fn new(_config: &Config) -> Self {
-        Default::default()
+    pub fn new(config: &Config) -> Self {
+        CaesarDecoder {
+            bigram_scoring_max_len: config.bigram_scoring_max_len,
+        }
+    }
+
+    pub fn decrypt_shifts(&self, ciphertext: &str, shifts: &[u8]) -> Vec<DecryptionAttempt> {
+        decode::run_caesar_decryption_for_shifts(ciphertext, shifts, self.bigram_scoring_max_len)
+    }
+
+    // All 26 shifts in shift order rather than score order, for showing the full table.
+    pub fn decrypt_table(&self, ciphertext: &str) -> Vec<DecryptionAttempt> {
+        decode::run_caesar_decryption_table(ciphertext, self.bigram_scoring_max_len)
     }
 }
 
 impl Identifier for CaesarIdentifier {
+    fn name(&self) -> &'static str {
+        "Caesar"
+    }
+
+    fn description(&self) -> &'static str {
+        "Looks for a single shift that turns the text into English"
+    }
+
     fn identify(&self, ciphertext: &str) -> Option<IdentificationResult> {
-        identify::run_caesar_identification(ciphertext)
+        match self.strong_match_chi2 {
+            Some(threshold) => identify::run_caesar_identification_early_exit(ciphertext, threshold),
+            None => identify::run_caesar_identification(ciphertext),
+        }
+    }
+
+    fn identify_all(&self, ciphertext: &str) -> Vec<IdentificationResult> {
+        identify::run_caesar_identification_all(ciphertext)
     }
 }
 
 impl Decoder for CaesarDecoder {
     fn decrypt(&self, ciphertext: &str) -> Vec<DecryptionAttempt> {
-        decode::run_caesar_decryption(ciphertext)
+        decode::run_caesar_decryption(ciphertext, self.bigram_scoring_max_len)
     }
 
     fn name(&self) -> &'static str {
         "Caesar"
     }
+
+    fn description(&self) -> &'static str {
+        "Every letter shifted by the same fixed amount"
+    }
+
+    fn cache_key(&self) -> Option<String> {
+        Some(format!("{:?}", self))
+    }
+
+    fn scoring_method_for(&self, attempts: &[DecryptionAttempt]) -> ScoringMethod {
+        attempts
+            .first()
+            .map_or(self.scoring_method(), |attempt| decode::scoring_method(&attempt.plaintext, self.bigram_scoring_max_len))
+    }
+
+    fn decrypt_with_key(&self, ciphertext: &str, key: &str) -> Option<DecryptionAttempt> {
+        decode::run_caesar_decryption_with_key(ciphertext, key, self.bigram_scoring_max_len)
+    }
 }
@@ -0,0 +1,104 @@
+const BASE64_ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+const MIN_ENCODED_LEN: usize = 4;
+const MIN_PRINTABLE_RATIO: f64 = 0.9;
+
+
+pub fn decode_hex(text: &str) -> Option<Vec<u8>> {
+    if text.len() < MIN_ENCODED_LEN || !text.len().is_multiple_of(2) || !text.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+
+    (0..text.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&text[i..i + 2], 16).ok())
+        .collect()
+}
+
+pub fn encode_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+pub fn encode_base64(bytes: &[u8]) -> String {
+    let mut encoded = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let mut buffer: u32 = 0;
+        for (i, &b) in chunk.iter().enumerate() {
+            buffer |= (b as u32) << (16 - 8 * i);
+        }
+        for i in 0..4 {
+            if i <= chunk.len() {
+                encoded.push(BASE64_ALPHABET[((buffer >> (18 - 6 * i)) & 0x3f) as usize] as char);
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+    encoded
+}
+
+fn decode_base64(text: &str) -> Option<Vec<u8>> {
+    let bytes = text.as_bytes();
+    if bytes.len() < MIN_ENCODED_LEN || !bytes.len().is_multiple_of(4) {
+        return None;
+    }
+
+    let padding = bytes.iter().rev().take_while(|&&b| b == b'=').count();
+    if padding > 2 {
+        return None;
+    }
+
+    let mut sextets = Vec::with_capacity(bytes.len() - padding);
+    for &b in &bytes[..bytes.len() - padding] {
+        let value = BASE64_ALPHABET.iter().position(|&a| a == b)?;
+        sextets.push(value as u8);
+    }
+
+    let mut decoded = Vec::with_capacity(sextets.len() * 3 / 4);
+    for chunk in sextets.chunks(4) {
+        let mut buffer: u32 = 0;
+        for (i, &sextet) in chunk.iter().enumerate() {
+            buffer |= (sextet as u32) << (18 - 6 * i);
+        }
+        let byte_count = chunk.len() * 6 / 8;
+        for i in 0..byte_count {
+            decoded.push((buffer >> (16 - 8 * i)) as u8);
+        }
+    }
+
+    Some(decoded)
+}
+
+// Plain ciphertext like "LXFOPVEFRNHR" is also valid Base64, so only accept decodings that read as text.
+fn is_mostly_printable(bytes: &[u8]) -> bool {
+    if bytes.is_empty() {
+        return false;
+    }
+    let printable = bytes
+        .iter()
+        .filter(|&&b| b.is_ascii_graphic() || b.is_ascii_whitespace())
+        .count();
+    printable as f64 / bytes.len() as f64 >= MIN_PRINTABLE_RATIO
+}
+
+
+fn into_text(decoded: Option<Vec<u8>>) -> Option<String> {
+    decoded
+        .filter(|bytes| is_mostly_printable(bytes))
+        .map(|bytes| String::from_utf8_lossy(&bytes).into_owned())
+}
+
+// Hex input that decodes to readable text.
+pub fn decode_hex_text(text: &str) -> Option<String> {
+    into_text(decode_hex(text.trim()))
+}
+
+// Base64 input that decodes to readable text.
+pub fn decode_base64_text(text: &str) -> Option<String> {
+    into_text(decode_base64(text.trim()))
+}
+
+pub fn detect_and_decode(text: &str) -> Option<(String, &'static str)> {
+    decode_hex_text(text)
+        .map(|decoded| (decoded, "Hex"))
+        .or_else(|| decode_base64_text(text).map(|decoded| (decoded, "Base64")))
+}
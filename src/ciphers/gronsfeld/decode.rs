@@ -0,0 +1,129 @@
+use crate::decoder::{DecryptionAttempt, ScoringMethod};
+use crate::analysis;
+use crate::cipher_utils;
+use itertools::Itertools;
+
+
+const MAX_KEY_LENGTHS_TO_TRY: usize = 4;
+const TOP_N_SHIFTS_PER_COLUMN: usize = 3;
+const MAX_GRONSFELD_SHIFT: u8 = 10;
+
+
+fn gronsfeld_decrypt(ciphertext: &str, key_digits: &[u8]) -> String {
+    if key_digits.is_empty() {
+        return ciphertext.to_string();
+    }
+    cipher_utils::apply_keystream(ciphertext, key_digits.iter().map(|&d| -(d as i8)).cycle())
+}
+
+
+pub(super) fn run_gronsfeld_decryption(
+    ciphertext: &str,
+    min_text_len: usize,
+    min_chars_for_mic: usize,
+    max_key_len: usize,
+    max_combinations: Option<usize>,
+) -> Vec<DecryptionAttempt> {
+    let alpha_text = analysis::get_alphabetic_chars(ciphertext);
+    if alpha_text.len() < min_text_len {
+        return Vec::new();
+    }
+
+    let key_lengths_to_try: Vec<usize> = analysis::estimate_key_length_ic_periodicity(
+        &alpha_text,
+        1,
+        max_key_len
+    )
+        .iter()
+        .take(MAX_KEY_LENGTHS_TO_TRY)
+        .map(|(len, _score)| *len)
+        .collect();
+
+    let mut candidates = Vec::new();
+    let alpha_upper = alpha_text.to_ascii_uppercase();
+
+    for key_len in key_lengths_to_try {
+        let mut top_shifts_per_column: Vec<Vec<u8>> = Vec::with_capacity(key_len);
+
+        for i in 0..key_len {
+            let column: String = alpha_text
+                .chars()
+                .skip(i)
+                .step_by(key_len)
+                .collect();
+
+            // Rank all 26 shifts, then keep only those a single key digit can produce.
+            let ranked_shifts = match analysis::find_top_n_caesar_shifts_mic(&column, 26, min_chars_for_mic) {
+                Some(shifts) => shifts,
+                None => {
+                    log::info!(
+                        "Gronsfeld analysis for key length {} skipped: Column {} has {} letters, below the MIC minimum of {}.",
+                        key_len, i, column.len(), min_chars_for_mic
+                    );
+                    break;
+                }
+            };
+            let digit_shifts: Vec<u8> = ranked_shifts
+                .into_iter()
+                .map(|(shift, _score)| shift)
+                .filter(|&shift| shift < MAX_GRONSFELD_SHIFT)
+                .take(TOP_N_SHIFTS_PER_COLUMN)
+                .collect();
+
+            if digit_shifts.is_empty() {
+                break;
+            }
+            top_shifts_per_column.push(digit_shifts);
+        }
+
+        if top_shifts_per_column.len() != key_len {
+            continue;
+        }
+        if let Some(max_combinations) = max_combinations {
+            cipher_utils::limit_candidate_combinations(&mut top_shifts_per_column, max_combinations);
+        }
+
+        for key_digits in top_shifts_per_column.into_iter().multi_cartesian_product() {
+            let letters = gronsfeld_decrypt(&alpha_upper, &key_digits);
+            let score = analysis::score_trigram_log_prob_prepared(&letters);
+            candidates.push((key_digits, letters, score));
+        }
+    }
+
+    // Repeating the key (e.g. "3141531415") scores identically, so prefer the shortest form.
+    candidates.sort_by_key(|(key_digits, _, _)| key_digits.len());
+    candidates.sort_by(|a, b| ScoringMethod::Trigram.compare(a.2, b.2));
+
+    // Only the letters were decrypted while searching; lay them back into the ciphertext here.
+    candidates
+        .into_iter()
+        .map(|(key_digits, letters, score)| DecryptionAttempt {
+            cipher_name: "Gronsfeld".to_string(),
+            key: key_digits.iter().map(|d| (b'0' + d) as char).collect(),
+            plaintext: cipher_utils::restore_layout(ciphertext, &letters),
+            score,
+            note: None,
+            solver_stats: None,
+        })
+        .collect()
+}
+
+
+pub(super) fn run_gronsfeld_decryption_with_key(ciphertext: &str, key: &str) -> Option<DecryptionAttempt> {
+    if key.is_empty() || !key.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+
+    let key_digits: Vec<u8> = key.bytes().map(|b| b - b'0').collect();
+    let plaintext = gronsfeld_decrypt(ciphertext, &key_digits);
+    let score = analysis::score_trigram_log_prob(&plaintext);
+
+    Some(DecryptionAttempt {
+        cipher_name: "Gronsfeld".to_string(),
+        key: key.to_string(),
+        plaintext,
+        score,
+        note: None,
+        solver_stats: None,
+    })
+}
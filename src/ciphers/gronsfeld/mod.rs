@@ -0,0 +1,60 @@
+mod decode;
+
+use crate::decoder::{Decoder, DecryptionAttempt, ScoringMethod};
+use crate::config::Config;
+
+
+#[derive(Debug, Default)]
+pub struct GronsfeldDecoder {
+    min_text_len: usize,
+    min_chars_for_mic: usize,
+    max_key_len: usize,
+    max_combinations: Option<usize>,
+}
+
+impl GronsfeldDecoder {
+    pub fn new(config: &Config) -> Self {
+        GronsfeldDecoder {
+            min_text_len: config.vigenere_min_dec_len,
+            min_chars_for_mic: config.min_chars_for_mic,
+            max_key_len: config.kasiski_max_dec_key_len,
+            max_combinations: config.max_key_combinations,
+        }
+    }
+}
+
+impl Decoder for GronsfeldDecoder {
+    fn decrypt(&self, ciphertext: &str) -> Vec<DecryptionAttempt> {
+        decode::run_gronsfeld_decryption(
+            ciphertext,
+            self.min_text_len,
+            self.min_chars_for_mic,
+            self.max_key_len,
+            self.max_combinations,
+        )
+    }
+
+    fn name(&self) -> &'static str {
+        "Gronsfeld"
+    }
+
+    fn description(&self) -> &'static str {
+        "Vigenere variant whose key is a sequence of digit shifts"
+    }
+
+    fn cache_key(&self) -> Option<String> {
+        Some(format!("{:?}", self))
+    }
+
+    fn min_ciphertext_len(&self) -> usize {
+        self.min_text_len
+    }
+
+    fn scoring_method(&self) -> ScoringMethod {
+        ScoringMethod::Trigram
+    }
+
+    fn decrypt_with_key(&self, ciphertext: &str, key: &str) -> Option<DecryptionAttempt> {
+        decode::run_gronsfeld_decryption_with_key(ciphertext, key)
+    }
+}
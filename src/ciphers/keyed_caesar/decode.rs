@@ -0,0 +1,75 @@
+use crate::decoder::DecryptionAttempt;
+use crate::analysis;
+use crate::cipher_utils;
+use std::cmp::Ordering;
+
+
+const MAX_RESULTS: usize = 10;
+
+
+// Keys are written "KEYWORD+SHIFT", e.g. "ZEBRA+3".
+fn format_key(keyword: &str, shift: u8) -> String {
+    format!("{}+{}", keyword, shift)
+}
+
+fn parse_key(key: &str) -> Option<(&str, u8)> {
+    let (keyword, shift) = key.rsplit_once('+')?;
+    let shift = shift.trim().parse::<u8>().ok().filter(|&s| s < 26)?;
+    let keyword = keyword.trim();
+    (!keyword.is_empty() && keyword.chars().all(|c| c.is_ascii_alphabetic())).then_some((keyword, shift))
+}
+
+fn keyed_caesar_decrypt(ciphertext: &str, alphabet: &[char; 26], shift: u8) -> String {
+    ciphertext
+        .chars()
+        .map(|c| cipher_utils::shift_char_in_alphabet(c, -(shift as i8), alphabet))
+        .collect()
+}
+
+fn make_attempt(ciphertext: &str, keyword: &str, shift: u8, score: f64) -> DecryptionAttempt {
+    let alphabet = cipher_utils::keyed_alphabet(keyword);
+    DecryptionAttempt {
+        cipher_name: "Keyed Caesar".to_string(),
+        key: format_key(keyword, shift),
+        plaintext: keyed_caesar_decrypt(ciphertext, &alphabet, shift),
+        score,
+        note: None,
+        solver_stats: None,
+    }
+}
+
+
+pub(super) fn run_keyed_caesar_decryption(ciphertext: &str, keywords: &[String]) -> Vec<DecryptionAttempt> {
+    let alpha_upper = analysis::get_alphabetic_chars(ciphertext).to_ascii_uppercase();
+    if alpha_upper.is_empty() {
+        return Vec::new();
+    }
+
+    // Only the letters are scored here; full plaintexts are built for the survivors.
+    let mut scored: Vec<(&str, u8, f64)> = Vec::with_capacity(keywords.len() * 26);
+    for keyword in keywords {
+        let alphabet = cipher_utils::keyed_alphabet(keyword);
+        for shift in 0..26u8 {
+            let candidate = keyed_caesar_decrypt(&alpha_upper, &alphabet, shift);
+            scored.push((keyword, shift, analysis::score_trigram_log_prob_prepared(&candidate)));
+        }
+    }
+
+    scored.sort_by(|a, b| b.2.partial_cmp(&a.2).unwrap_or(Ordering::Equal));
+    scored
+        .into_iter()
+        .take(MAX_RESULTS)
+        .map(|(keyword, shift, score)| make_attempt(ciphertext, keyword, shift, score))
+        .collect()
+}
+
+
+pub(super) fn run_keyed_caesar_decryption_with_key(ciphertext: &str, key: &str) -> Option<DecryptionAttempt> {
+    let (keyword, shift) = parse_key(key)?;
+    let keyword = keyword.to_ascii_uppercase();
+    let alphabet = cipher_utils::keyed_alphabet(&keyword);
+    let alpha_upper = analysis::get_alphabetic_chars(ciphertext).to_ascii_uppercase();
+    let score = analysis::score_trigram_log_prob_prepared(&keyed_caesar_decrypt(&alpha_upper, &alphabet, shift));
+
+    Some(make_attempt(ciphertext, &keyword, shift, score))
+}
@@ -0,0 +1,59 @@
+mod decode;
+
+use crate::analysis::WordList;
+use crate::decoder::{Decoder, DecryptionAttempt, ScoringMethod};
+use crate::config::Config;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+#[derive(Debug, Default)]
+pub struct KeyedCaesarDecoder {
+    keywords: Vec<String>,
+}
+
+impl KeyedCaesarDecoder {
+    pub fn new(_config: &Config) -> Self {
+        KeyedCaesarDecoder::with_keywords(WordList::english().words())
+    }
+
+    // Candidate keywords are uppercased and sorted so equal scores always rank the same way.
+    pub fn with_keywords<'a>(keywords: impl IntoIterator<Item = &'a str>) -> Self {
+        let mut keywords: Vec<String> = keywords
+            .into_iter()
+            .filter(|w| !w.is_empty() && w.chars().all(|c| c.is_ascii_alphabetic()))
+            .map(|w| w.to_ascii_uppercase())
+            .collect();
+        keywords.sort();
+        keywords.dedup();
+        KeyedCaesarDecoder { keywords }
+    }
+}
+
+impl Decoder for KeyedCaesarDecoder {
+    fn decrypt(&self, ciphertext: &str) -> Vec<DecryptionAttempt> {
+        decode::run_keyed_caesar_decryption(ciphertext, &self.keywords)
+    }
+
+    fn name(&self) -> &'static str {
+        "Keyed Caesar"
+    }
+
+    fn description(&self) -> &'static str {
+        "Caesar shift over an alphabet reordered by a keyword"
+    }
+
+    // The word list is too long to spell out in the key, so it is hashed.
+    fn cache_key(&self) -> Option<String> {
+        let mut hasher = DefaultHasher::new();
+        self.keywords.hash(&mut hasher);
+        Some(format!("{:x}", hasher.finish()))
+    }
+
+    fn scoring_method(&self) -> ScoringMethod {
+        ScoringMethod::Trigram
+    }
+
+    fn decrypt_with_key(&self, ciphertext: &str, key: &str) -> Option<DecryptionAttempt> {
+        decode::run_keyed_caesar_decryption_with_key(ciphertext, key)
+    }
+}
@@ -1,2 +1,11 @@
+pub mod a1z26;
+pub mod bacon;
 pub mod caesar;
-pub mod vigenere;
\ No newline at end of file
+pub mod encoding;
+pub mod gronsfeld;
+pub mod keyed_caesar;
+pub mod morse;
+pub mod polybius;
+pub mod substitution;
+pub mod vigenere;
+pub mod xor;
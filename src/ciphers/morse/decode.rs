@@ -0,0 +1,60 @@
+use crate::decoder::DecryptionAttempt;
+use crate::analysis;
+
+
+const MORSE_TABLE: [(&str, char); 36] = [
+    (".-", 'A'), ("-...", 'B'), ("-.-.", 'C'), ("-..", 'D'), (".", 'E'), ("..-.", 'F'),
+    ("--.", 'G'), ("....", 'H'), ("..", 'I'), (".---", 'J'), ("-.-", 'K'), (".-..", 'L'),
+    ("--", 'M'), ("-.", 'N'), ("---", 'O'), (".--.", 'P'), ("--.-", 'Q'), (".-.", 'R'),
+    ("...", 'S'), ("-", 'T'), ("..-", 'U'), ("...-", 'V'), (".--", 'W'), ("-..-", 'X'),
+    ("-.--", 'Y'), ("--..", 'Z'), ("-----", '0'), (".----", '1'), ("..---", '2'), ("...--", '3'),
+    ("....-", '4'), (".....", '5'), ("-....", '6'), ("--...", '7'), ("---..", '8'), ("----.", '9'),
+];
+const INVALID_SEQUENCE_MARKER: char = '?';
+
+
+fn decode_morse_letter(sequence: &str) -> char {
+    MORSE_TABLE
+        .iter()
+        .find(|(code, _)| *code == sequence)
+        .map(|(_, letter)| *letter)
+        .unwrap_or(INVALID_SEQUENCE_MARKER)
+}
+
+
+// Letters are separated by spaces and words by '/'; None unless the text looks like Morse.
+pub fn decode_morse_text(ciphertext: &str) -> Option<String> {
+    if !analysis::looks_like_morse(ciphertext) {
+        return None;
+    }
+
+    let plaintext = ciphertext
+        .split('/')
+        .map(|word| {
+            word.split_whitespace()
+                .map(decode_morse_letter)
+                .collect::<String>()
+        })
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<String>>()
+        .join(" ");
+    (!plaintext.is_empty()).then_some(plaintext)
+}
+
+
+pub(super) fn run_morse_decoding(ciphertext: &str) -> Vec<DecryptionAttempt> {
+    let Some(plaintext) = decode_morse_text(ciphertext) else {
+        return Vec::new();
+    };
+
+    let score = analysis::score_english_likelihood(&plaintext).unwrap_or(f64::MAX);
+
+    vec![DecryptionAttempt {
+        cipher_name: "Morse".to_string(),
+        key: "morse".to_string(),
+        plaintext,
+        score,
+        note: None,
+        solver_stats: None,
+    }]
+}
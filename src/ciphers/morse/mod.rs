@@ -0,0 +1,38 @@
+mod decode;
+
+use crate::decoder::{Decoder, DecryptionAttempt};
+use crate::config::Config;
+use crate::analysis;
+
+pub use decode::decode_morse_text;
+
+#[derive(Debug, Default)]
+pub struct MorseDecoder;
+
+impl MorseDecoder {
+    pub fn new(_config: &Config) -> Self {
+        Default::default()
+    }
+}
+
+impl Decoder for MorseDecoder {
+    fn decrypt(&self, ciphertext: &str) -> Vec<DecryptionAttempt> {
+        decode::run_morse_decoding(ciphertext)
+    }
+
+    fn name(&self) -> &'static str {
+        "Morse"
+    }
+
+    fn description(&self) -> &'static str {
+        "Letters written as dots and dashes"
+    }
+
+    fn cache_key(&self) -> Option<String> {
+        Some(format!("{:?}", self))
+    }
+
+    fn skip_reason(&self, text: &str) -> Option<String> {
+        (!analysis::looks_like_morse(text)).then(|| "input is not Morse".to_string())
+    }
+}
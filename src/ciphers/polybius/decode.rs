@@ -0,0 +1,101 @@
+use crate::decoder::{self, DecryptionAttempt, ScoringMethod};
+use crate::analysis;
+
+
+const STANDARD_SQUARE_5X5: &str = "ABCDEFGHIKLMNOPQRSTUVWXYZ";
+const STANDARD_SQUARE_6X6: &str = "ABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789";
+
+
+// Each whitespace-separated token must be an even-length run of coordinate digits.
+fn parse_tokens(ciphertext: &str, size: u8) -> Option<Vec<&str>> {
+    let max_digit = (b'0' + size) as char;
+    let tokens: Vec<&str> = ciphertext.split_whitespace().collect();
+    if tokens.is_empty() {
+        return None;
+    }
+
+    let valid = tokens.iter().all(|token| {
+        token.len().is_multiple_of(2) && token.chars().all(|c| ('1'..=max_digit).contains(&c))
+    });
+    valid.then_some(tokens)
+}
+
+// Keyword letters first (deduplicated), then the rest of the standard square in order.
+fn build_square(keyword: &str, size: u8) -> Vec<char> {
+    let standard = if size == 5 { STANDARD_SQUARE_5X5 } else { STANDARD_SQUARE_6X6 };
+    let mut square: Vec<char> = Vec::with_capacity(standard.len());
+
+    let keyword_chars = keyword
+        .chars()
+        .map(|c| c.to_ascii_uppercase())
+        .map(|c| if size == 5 && c == 'J' { 'I' } else { c });
+
+    for c in keyword_chars.chain(standard.chars()) {
+        if standard.contains(c) && !square.contains(&c) {
+            square.push(c);
+        }
+    }
+    square
+}
+
+fn decode_with_square(tokens: &[&str], square: &[char], size: u8) -> String {
+    let decode_token = |token: &str| -> String {
+        token
+            .as_bytes()
+            .chunks(2)
+            .map(|pair| {
+                let row = (pair[0] - b'1') as usize;
+                let col = (pair[1] - b'1') as usize;
+                square[row * size as usize + col]
+            })
+            .collect()
+    };
+
+    // "23 15 31" style input is one letter per token; longer tokens are whole words.
+    if tokens.iter().all(|token| token.len() == 2) {
+        tokens.iter().map(|token| decode_token(token)).collect()
+    } else {
+        tokens.iter().map(|token| decode_token(token)).collect::<Vec<String>>().join(" ")
+    }
+}
+
+fn make_attempt(tokens: &[&str], square: &[char], size: u8) -> DecryptionAttempt {
+    let plaintext = decode_with_square(tokens, square, size);
+    let score = analysis::score_english_likelihood(&plaintext).unwrap_or(f64::MAX);
+
+    DecryptionAttempt {
+        cipher_name: "Polybius".to_string(),
+        key: square.iter().collect(),
+        plaintext,
+        score,
+        note: None,
+        solver_stats: None,
+    }
+}
+
+
+pub(super) fn run_polybius_decoding(ciphertext: &str) -> Vec<DecryptionAttempt> {
+    let mut attempts = Vec::new();
+
+    // The plain 5x5 square (I/J merged) is tried first; digits up to 6 also allow the 6x6 variant.
+    for size in [5u8, 6] {
+        if let Some(tokens) = parse_tokens(ciphertext, size) {
+            attempts.push(make_attempt(&tokens, &build_square("", size), size));
+        }
+    }
+
+    decoder::sort_best_first(&mut attempts, ScoringMethod::ChiSquared);
+
+    attempts
+}
+
+
+pub(super) fn run_polybius_decoding_with_key(ciphertext: &str, keyword: &str) -> Option<DecryptionAttempt> {
+    if !keyword.chars().all(|c| c.is_ascii_alphanumeric()) {
+        return None;
+    }
+
+    let size = if parse_tokens(ciphertext, 5).is_some() { 5 } else { 6 };
+    let tokens = parse_tokens(ciphertext, size)?;
+    Some(make_attempt(&tokens, &build_square(keyword, size), size))
+}
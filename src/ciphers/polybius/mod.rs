@@ -0,0 +1,35 @@
+mod decode;
+
+use crate::decoder::{Decoder, DecryptionAttempt};
+use crate::config::Config;
+
+#[derive(Debug, Default)]
+pub struct PolybiusDecoder;
+
+impl PolybiusDecoder {
+    pub fn new(_config: &Config) -> Self {
+        Default::default()
+    }
+}
+
+impl Decoder for PolybiusDecoder {
+    fn decrypt(&self, ciphertext: &str) -> Vec<DecryptionAttempt> {
+        decode::run_polybius_decoding(ciphertext)
+    }
+
+    fn name(&self) -> &'static str {
+        "Polybius"
+    }
+
+    fn description(&self) -> &'static str {
+        "Letters written as row/column coordinates in a 5x5 or 6x6 square"
+    }
+
+    fn cache_key(&self) -> Option<String> {
+        Some(format!("{:?}", self))
+    }
+
+    fn decrypt_with_key(&self, ciphertext: &str, key: &str) -> Option<DecryptionAttempt> {
+        decode::run_polybius_decoding_with_key(ciphertext, key)
+    }
+}
@@ -0,0 +1,148 @@
+use crate::decoder::{self, DecryptionAttempt, ScoringMethod, SolverStats};
+use crate::analysis;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+
+
+pub(super) const MIN_SUBSTITUTION_TEXT_LEN: usize = 40;
+const MAX_STALE_SWAPS: usize = 1500;
+
+
+// `key[i]` is the plaintext letter for ciphertext letter `'A' + i`.
+fn substitution_decrypt(ciphertext: &str, key: &[u8; 26]) -> String {
+    ciphertext
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphabetic() {
+                let plain = key[(c.to_ascii_uppercase() as u8 - b'A') as usize] as char;
+                if c.is_ascii_lowercase() { plain.to_ascii_lowercase() } else { plain }
+            } else {
+                c
+            }
+        })
+        .collect()
+}
+
+fn score_key(alpha_upper: &str, key: &[u8; 26]) -> f64 {
+    analysis::score_trigram_log_prob_prepared(&substitution_decrypt(alpha_upper, key))
+}
+
+fn parse_key(key: &str) -> Option<[u8; 26]> {
+    let upper = key.trim().to_ascii_uppercase();
+    if upper.len() != 26 || !upper.bytes().all(|b| b.is_ascii_uppercase()) {
+        return None;
+    }
+
+    let mut seen = [false; 26];
+    let mut parsed = [0u8; 26];
+    for (i, b) in upper.bytes().enumerate() {
+        let index = (b - b'A') as usize;
+        if seen[index] {
+            return None;
+        }
+        seen[index] = true;
+        parsed[i] = b;
+    }
+    Some(parsed)
+}
+
+// Stops after MAX_STALE_SWAPS swaps without improvement or `max_iterations` swaps in total.
+fn hill_climb<R: Rng>(alpha_upper: &str, mut key: [u8; 26], max_iterations: usize, rng: &mut R, stats: &mut SolverStats) -> ([u8; 26], f64) {
+    let mut best_score = score_key(alpha_upper, &key);
+    let mut stale_swaps = 0;
+    let mut iterations = 0;
+
+    while stale_swaps < MAX_STALE_SWAPS && iterations < max_iterations {
+        let a = rng.gen_range(0..26);
+        let b = rng.gen_range(0..26);
+        if a == b {
+            continue;
+        }
+        iterations += 1;
+        stats.iterations += 1;
+
+        key.swap(a, b);
+        let score = score_key(alpha_upper, &key);
+        if score > best_score {
+            best_score = score;
+            stale_swaps = 0;
+        } else {
+            key.swap(a, b);
+            stale_swaps += 1;
+        }
+    }
+
+    (key, best_score)
+}
+
+
+pub(super) fn run_substitution_decryption(
+    ciphertext: &str,
+    random_seed: Option<u64>,
+    restarts: usize,
+    max_iterations: usize,
+) -> Vec<DecryptionAttempt> {
+    let mut stats = SolverStats::default();
+    let alpha_upper = analysis::get_alphabetic_chars(ciphertext).to_ascii_uppercase();
+    if alpha_upper.len() < MIN_SUBSTITUTION_TEXT_LEN {
+        return Vec::new();
+    }
+
+    // A fixed seed makes the whole search reproducible for tests and bug reports.
+    let mut rng = match random_seed {
+        Some(seed) => StdRng::seed_from_u64(seed),
+        None => StdRng::from_entropy(),
+    };
+
+    let mut attempts: Vec<DecryptionAttempt> = Vec::with_capacity(restarts);
+    // The first climb starts from the frequency-rank guess; the rest restart from random keys.
+    let frequency_key = analysis::frequency_rank_mapping(&alpha_upper).map(|c| c as u8);
+    for restart in 0..restarts {
+        let start_key = if restart == 0 {
+            frequency_key
+        } else {
+            let mut key: [u8; 26] = std::array::from_fn(|i| b'A' + i as u8);
+            key.shuffle(&mut rng);
+            key
+        };
+        let (key, score) = hill_climb(&alpha_upper, start_key, max_iterations, &mut rng, &mut stats);
+        stats.restarts += 1;
+        let key_string: String = key.iter().map(|&b| b as char).collect();
+        if attempts.iter().any(|a| a.key == key_string) {
+            continue;
+        }
+
+        attempts.push(DecryptionAttempt {
+            cipher_name: "Substitution".to_string(),
+            key: key_string,
+            plaintext: substitution_decrypt(ciphertext, &key),
+            score,
+            note: None,
+            solver_stats: None,
+        });
+    }
+
+    decoder::sort_best_first(&mut attempts, ScoringMethod::Trigram);
+    for attempt in &mut attempts {
+        attempt.solver_stats = Some(stats);
+    }
+
+    attempts
+}
+
+
+pub(super) fn run_substitution_decryption_with_key(ciphertext: &str, key: &str) -> Option<DecryptionAttempt> {
+    let parsed = parse_key(key)?;
+    let plaintext = substitution_decrypt(ciphertext, &parsed);
+    let score = analysis::score_trigram_log_prob(&plaintext);
+
+    Some(DecryptionAttempt {
+        cipher_name: "Substitution".to_string(),
+        key: parsed.iter().map(|&b| b as char).collect(),
+        plaintext,
+        score,
+        note: None,
+        solver_stats: None,
+    })
+}
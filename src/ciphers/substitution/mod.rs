@@ -0,0 +1,76 @@
+mod decode;
+
+use crate::decoder::{Decoder, DecryptionAttempt, ScoringMethod};
+use crate::config::Config;
+use crate::analysis;
+
+
+pub use crate::decoder::SolverStats;
+
+#[derive(Debug, Default)]
+pub struct SubstitutionDecoder {
+    random_seed: Option<u64>,
+    restarts: usize,
+    max_iterations: usize,
+    // Thresholds of `analysis::is_monoalphabetic_signature`; the hill climb only runs on text that shows it.
+    mono_ic_min: f64,
+    mono_chi2_min: f64,
+}
+
+impl SubstitutionDecoder {
+    pub fn new(config: &Config) -> Self {
+        SubstitutionDecoder {
+            random_seed: config.random_seed,
+            restarts: config.solver_restarts,
+            max_iterations: config.solver_max_iterations,
+            mono_ic_min: config.mono_ic_min,
+            mono_chi2_min: config.mono_chi2_min,
+        }
+    }
+}
+
+impl Decoder for SubstitutionDecoder {
+    fn decrypt(&self, ciphertext: &str) -> Vec<DecryptionAttempt> {
+        decode::run_substitution_decryption(ciphertext, self.random_seed, self.restarts, self.max_iterations)
+    }
+
+    fn name(&self) -> &'static str {
+        "Substitution"
+    }
+
+    fn description(&self) -> &'static str {
+        "Monoalphabetic substitution with an arbitrary 26-letter key"
+    }
+
+    fn cache_key(&self) -> Option<String> {
+        Some(format!("{:?}", self))
+    }
+
+    fn scoring_method(&self) -> ScoringMethod {
+        ScoringMethod::Trigram
+    }
+
+    fn min_ciphertext_len(&self) -> usize {
+        decode::MIN_SUBSTITUTION_TEXT_LEN
+    }
+
+    // The restarts are expensive, so skip input whose statistics don't look monoalphabetic.
+    fn skip_reason(&self, text: &str) -> Option<String> {
+        if analysis::looks_like_morse(text) {
+            return Some("input looks like Morse".to_string());
+        }
+        let ic = analysis::calculate_ic(text)?;
+        let chi_squared = analysis::score_english_likelihood(text)?;
+        if ic <= self.mono_ic_min {
+            Some(format!("IC {:.4} is not above the monoalphabetic minimum {}", ic, self.mono_ic_min))
+        } else if chi_squared <= self.mono_chi2_min {
+            Some(format!("chi-squared {:.2} already matches English", chi_squared))
+        } else {
+            None
+        }
+    }
+
+    fn decrypt_with_key(&self, ciphertext: &str, key: &str) -> Option<DecryptionAttempt> {
+        decode::run_substitution_decryption_with_key(ciphertext, key)
+    }
+}
@@ -1,174 +1,526 @@
-use crate::decoder::DecryptionAttempt;
-use crate::analysis;
+use crate::decoder::{DecodeOutcome, DecryptionAttempt, ScoringMethod};
+use crate::config::ColumnShiftMethod;
+use super::{ColumnDiagnostics, KeyLengthDiagnostics};
+use crate::analysis::{self, KasiskiSettings};
 use crate::cipher_utils;
 use std::cmp::Ordering;
+use std::collections::HashSet;
 use itertools::Itertools;
 
 
-const MIN_KASISKI_SEQ_LEN_DEC: usize = 3;
-const MAX_KASISKI_KEY_LEN_DEC: usize = 12; // Reduced from 20 to limit estimator search space
 const MAX_KEY_LENGTHS_TO_TRY: usize = 4;
 const DEFAULT_KEY_LENGTHS_TO_TRY: &[usize] = &[2, 3, 4, 5, 6, 7];
-const TOP_N_SHIFTS_PER_COLUMN: usize = 3;
-const MAX_VIGENERE_KEY_LEN_TO_ATTEMPT: usize = 15; // Keep this filter too, though redundant if above is lower
+pub const TOP_N_SHIFTS_PER_COLUMN: usize = 3;
+const MAX_VIGENERE_KEY_LEN_TO_ATTEMPT: usize = 15; // Keep this filter too, though redundant if the search limit is lower
 const PROGRESS_UPDATE_INTERVAL: usize = 10000;
+// Ciphertext IC within this of English means one alphabet was used, i.e. key length 1.
+const MONOALPHABETIC_IC_MARGIN: f64 = 0.01;
+
+
+// A key of one repeated letter is just a Caesar shift, so say so on the attempt.
+pub(super) fn annotate_caesar_reduction(attempt: &mut DecryptionAttempt) {
+    let mut letters = attempt.key.chars();
+    if attempt.key.len() == 1 {
+        attempt.note = Some(format!(
+            "Key length 1: the polyalphabetic analysis reduces to a Caesar cipher with shift {}.",
+            attempt.key.as_bytes()[0] - b'A'
+        ));
+    } else if let Some(first) = letters.next() {
+        if letters.all(|c| c == first) {
+            attempt.note = Some(format!(
+                "Key '{}' repeats the single letter {}; this reduces to a Caesar cipher with shift {}.",
+                attempt.key, first, first as u8 - b'A'
+            ));
+        }
+    }
+}
+
+
+// How candidate keys are found and ranked. `column_method` picks each column's candidate shifts.
+// Trigrams are too sparse on very short texts, so below `bigram_scoring_max_len` letters bigrams are
+// used instead; `rank_by_trigram_avg` divides the trigram total by the trigram count so scores
+// compare across lengths. `rank_lengths_by_expected_ic` orders key lengths with
+// `analysis::rank_key_lengths_by_expected_ic` instead of proximity to English IC.
+// `max_combinations` optionally caps the keywords tried per key length.
+#[derive(Debug, Clone, Copy, Default)]
+pub(super) struct CandidateScoring {
+    pub column_method: ColumnShiftMethod,
+    pub bigram_scoring_max_len: usize,
+    pub rank_by_trigram_avg: bool,
+    pub rank_lengths_by_expected_ic: bool,
+    pub max_combinations: Option<usize>,
+}
+
+fn top_column_shifts(column: &str, min_chars_for_mic: usize, method: ColumnShiftMethod) -> Option<Vec<(u8, f64)>> {
+    match method {
+        ColumnShiftMethod::Mic => analysis::find_top_n_caesar_shifts_mic(column, TOP_N_SHIFTS_PER_COLUMN, min_chars_for_mic),
+        ColumnShiftMethod::ChiSquared => analysis::find_top_n_caesar_shifts_chi2(column, TOP_N_SHIFTS_PER_COLUMN, min_chars_for_mic),
+    }
+}
 
+impl CandidateScoring {
+    // Decryption keeps the letter count, so this is the method for every candidate of the text.
+    pub fn method(&self, letter_count: usize) -> ScoringMethod {
+        if analysis::prefers_bigrams(letter_count, self.bigram_scoring_max_len) {
+            ScoringMethod::Bigram
+        } else {
+            ScoringMethod::Trigram
+        }
+    }
+}
+
+fn score_prepared(alpha_upper: &str, scoring: CandidateScoring) -> f64 {
+    if analysis::prefers_bigrams(alpha_upper.len(), scoring.bigram_scoring_max_len) {
+        analysis::score_bigram_log_prob_prepared(alpha_upper)
+    } else if scoring.rank_by_trigram_avg {
+        analysis::score_trigram_avg_prepared(alpha_upper)
+    } else {
+        analysis::score_trigram_log_prob_prepared(alpha_upper)
+    }
+}
 
 fn vigenere_decrypt(ciphertext: &str, keyword: &str) -> String {
-    if keyword.is_empty() || !keyword.chars().all(|c| c.is_ascii_alphabetic()) {
+    let Some(shifts) = analysis::keyword_to_shifts(keyword) else {
         return ciphertext.to_string();
+    };
+    cipher_utils::apply_keystream(ciphertext, shifts.iter().map(|&shift| -(shift as i8)).cycle())
+}
+
+
+// Where the key lengths tried came from; the first source with any estimates is used.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum KeyLengthSource {
+    ExpectedIc,
+    IcPeriodicity,
+    Kasiski,
+    Defaults,
+}
+
+impl KeyLengthSource {
+    fn label(self) -> &'static str {
+        match self {
+            KeyLengthSource::ExpectedIc => "ranking by expected IC",
+            KeyLengthSource::IcPeriodicity => "IC periodicity",
+            KeyLengthSource::Kasiski => "Kasiski examination",
+            KeyLengthSource::Defaults => "the default lengths, as estimation was inconclusive",
+        }
     }
-    let keyword_bytes = keyword.to_ascii_uppercase().into_bytes();
-    let key_len = keyword_bytes.len();
-    let mut key_index = 0;
-    let mut plaintext = String::with_capacity(ciphertext.len());
-
-    for c in ciphertext.chars() {
-        if c.is_ascii_alphabetic() {
-            let key_byte = keyword_bytes[key_index % key_len];
-            let key_shift = (key_byte - b'A') as i8;
-            let decrypted_char = cipher_utils::shift_char(c, -key_shift);
-            plaintext.push(decrypted_char);
-            key_index += 1;
+}
+
+// Key lengths to try, best first. Near-English IC means one alphabet was used, so length 1 goes first.
+fn key_lengths_to_try(alpha_text: &str, kasiski: &KasiskiSettings, scoring: CandidateScoring) -> (Vec<usize>, KeyLengthSource) {
+    let (icp_estimates, icp_source) = if scoring.rank_lengths_by_expected_ic {
+        (analysis::rank_key_lengths_by_expected_ic(alpha_text, 2, kasiski.max_key_len), KeyLengthSource::ExpectedIc)
+    } else {
+        (analysis::estimate_key_length_ic_periodicity(alpha_text, 2, kasiski.max_key_len), KeyLengthSource::IcPeriodicity)
+    };
+
+    let (lengths, source): (Vec<usize>, KeyLengthSource) = if !icp_estimates.is_empty() {
+        log::info!("Using key lengths from {}.", icp_source.label());
+        (icp_estimates.iter().take(MAX_KEY_LENGTHS_TO_TRY).map(|(len, _score)| *len).collect(), icp_source)
+    } else {
+        let kasiski_estimates = analysis::estimate_key_lengths_with(alpha_text, kasiski);
+        if !kasiski_estimates.is_empty() {
+            log::info!("Using key lengths from Kasiski Examination.");
+            (kasiski_estimates.iter().take(MAX_KEY_LENGTHS_TO_TRY).map(|(len, _count)| *len).collect(), KeyLengthSource::Kasiski)
         } else {
-            plaintext.push(c);
+            log::info!("Key length estimation inconclusive, using defaults.");
+            (DEFAULT_KEY_LENGTHS_TO_TRY.to_vec(), KeyLengthSource::Defaults)
         }
+    };
+    let mut lengths: Vec<usize> = lengths.into_iter().filter(|&len| len <= MAX_VIGENERE_KEY_LEN_TO_ATTEMPT).collect();
+
+    let ic = analysis::calculate_ic(alpha_text).unwrap_or(0.0);
+    if ic >= analysis::ENGLISH_IC - MONOALPHABETIC_IC_MARGIN && !lengths.contains(&1) {
+        log::info!("IC {:.4} is close to English; trying key length 1 (Caesar) first.", ic);
+        lengths.insert(0, 1);
     }
-    plaintext
-}
 
+    (lengths, source)
+}
 
-pub(super) fn run_vigenere_decryption(ciphertext: &str, min_text_len: usize) -> Vec<DecryptionAttempt> {
 
+pub(super) fn run_vigenere_decryption(
+    ciphertext: &str,
+    min_text_len: usize,
+    min_chars_for_mic: usize,
+    kasiski: KasiskiSettings,
+    scoring: CandidateScoring,
+) -> DecodeOutcome {
     let alpha_text = analysis::get_alphabetic_chars(ciphertext);
     if alpha_text.len() < min_text_len {
 
-        return Vec::new();
+        return DecodeOutcome::TooShort { needed: min_text_len, got: alpha_text.len() };
     }
 
 
-    let icp_estimates = analysis::estimate_key_length_ic_periodicity(
-        &alpha_text,
-        2,
-        MAX_KASISKI_KEY_LEN_DEC
-    );
+    let (key_lengths_to_try, _source) = key_lengths_to_try(&alpha_text, &kasiski, scoring);
+    log::info!("Final key lengths to attempt: {:?}", key_lengths_to_try);
 
-    let key_lengths_to_try: Vec<usize> = if !icp_estimates.is_empty() {
-        println!("INFO: Using key lengths from IC Periodicity Test.");
-        icp_estimates
-            .iter()
-            .take(MAX_KEY_LENGTHS_TO_TRY)
-            .map(|(len, _score)| *len)
-            .collect()
-    } else {
 
-        let kasiski_estimates = analysis::estimate_key_lengths(
-            &alpha_text,
-            MIN_KASISKI_SEQ_LEN_DEC,
-            MAX_KASISKI_KEY_LEN_DEC
-        );
-        if !kasiski_estimates.is_empty() {
-            println!("INFO: Using key lengths from Kasiski Examination.");
-            kasiski_estimates
-                .iter()
-                .take(MAX_KEY_LENGTHS_TO_TRY)
-                .map(|(len, _count)| *len)
-                .collect()
-        } else {
+    let mut candidates = Vec::new();
+    let mut short_column_key_len: Option<usize> = None;
 
-            println!("INFO: Key length estimation inconclusive, using defaults.");
-            DEFAULT_KEY_LENGTHS_TO_TRY.to_vec()
+    for key_len in &key_lengths_to_try {
+        match attempts_for_key_length(&alpha_text, *key_len, min_chars_for_mic, scoring) {
+            Some(length_candidates) => candidates.extend(length_candidates),
+            None if *key_len > 0 => {
+                short_column_key_len.get_or_insert(*key_len);
+            }
+            None => {}
         }
     }
-        .into_iter()
-        .filter(|&len| len <= MAX_VIGENERE_KEY_LEN_TO_ATTEMPT)
-        .collect();
 
-    println!("INFO: Final key lengths to attempt: {:?}", key_lengths_to_try);
+    if candidates.is_empty() {
+        return match short_column_key_len {
+            Some(key_len) => DecodeOutcome::ColumnsTooShort { key_len },
+            None => DecodeOutcome::NoCandidates,
+        };
+    }
 
+    // Key lengths that are multiples of each other (3 and 6) can find the same plaintext; keep only
+    // its best-scoring, shortest-key representative.
+    let mut attempts = rank_candidates(candidates, scoring.method(alpha_text.len()), ciphertext);
+    if let Some(rotated) = attempts.first().and_then(|top| best_key_rotation(ciphertext, top, scoring)) {
+        attempts.insert(0, rotated);
+    }
+    let mut seen_plaintexts = HashSet::new();
+    attempts.retain(|attempt| seen_plaintexts.insert(attempt.plaintext.clone()));
+    attempts.iter_mut().for_each(annotate_caesar_reduction);
 
-    let mut attempts = Vec::new();
+    DecodeOutcome::Results(attempts)
+}
 
-    for key_len in &key_lengths_to_try {
-        let key_len = *key_len;
-        if key_len == 0 { continue; }
 
+// A scored key whose plaintext is kept as letters only; the full plaintext is laid out once the
+// candidate survives ranking and deduplication.
+struct Candidate {
+    key: String,
+    letters: String,
+    score: f64,
+}
 
-        let mut top_shifts_per_column: Vec<Vec<u8>> = Vec::with_capacity(key_len);
-        let mut possible_key = true;
+impl Candidate {
+    fn into_attempt(self, ciphertext: &str) -> DecryptionAttempt {
+        DecryptionAttempt {
+            cipher_name: "Vigenere".to_string(),
+            plaintext: cipher_utils::restore_layout(ciphertext, &self.letters),
+            key: self.key,
+            score: self.score,
+            note: None,
+            solver_stats: None,
+        }
+    }
+}
 
-        for i in 0..key_len {
-            let column: String = alpha_text
-                .chars()
-                .skip(i)
-                .step_by(key_len)
-                .collect();
+// Best first under `method`; among equal scores the shortest key wins, and repeated plaintexts are dropped.
+fn rank_candidates(mut candidates: Vec<Candidate>, method: ScoringMethod, ciphertext: &str) -> Vec<DecryptionAttempt> {
+    candidates.sort_by_key(|candidate| candidate.key.len());
+    candidates.sort_by(|a, b| method.compare(a.score, b.score));
+    let mut seen_letters = HashSet::new();
+    candidates
+        .into_iter()
+        .filter(|candidate| seen_letters.insert(candidate.letters.clone()))
+        .map(|candidate| candidate.into_attempt(ciphertext))
+        .collect()
+}
 
+// Runs the column/MIC pipeline for one key length. Returns None when a column is too short for MIC.
+fn attempts_for_key_length(
+    alpha_text: &str,
+    key_len: usize,
+    min_chars_for_mic: usize,
+    scoring: CandidateScoring,
+) -> Option<Vec<Candidate>> {
+    if key_len == 0 {
+        return None;
+    }
 
+    let alpha_upper = alpha_text.to_ascii_uppercase();
+    let mut top_shifts_per_column: Vec<Vec<u8>> = Vec::with_capacity(key_len);
 
-            if let Some(top_shifts) = analysis::find_top_n_caesar_shifts_mic(&column, TOP_N_SHIFTS_PER_COLUMN) {
-                top_shifts_per_column.push(top_shifts.into_iter().map(|(shift, _score)| shift).collect());
-            } else {
+    for i in 0..key_len {
+        let column: String = alpha_text
+            .chars()
+            .skip(i)
+            .step_by(key_len)
+            .collect();
 
-                possible_key = false;
-                println!("INFO: Vigenere analysis for key length {} skipped: Column {} too short for MIC analysis.", key_len, i);
-                break;
-            }
-        }
 
-        if !possible_key {
 
-            continue;
+        if let Some(top_shifts) = top_column_shifts(&column, min_chars_for_mic, scoring.column_method) {
+            top_shifts_per_column.push(top_shifts.into_iter().map(|(shift, _score)| shift).collect());
+        } else {
+            log::info!(
+                "Vigenere analysis for key length {} skipped: Column {} has {} letters, below the MIC minimum of {}.",
+                key_len, i, column.len(), min_chars_for_mic
+            );
+            return None;
         }
+    }
 
 
-        let total_combinations: usize = top_shifts_per_column.iter().map(|v| v.len()).product();
+    if let Some(max_combinations) = scoring.max_combinations {
+        cipher_utils::limit_candidate_combinations(&mut top_shifts_per_column, max_combinations);
+    }
+    let total_combinations: usize = top_shifts_per_column.iter().map(|v| v.len()).product();
 
 
-        if total_combinations > PROGRESS_UPDATE_INTERVAL * 2 {
-            println!("INFO: Vigenere trying key length {}: Testing {} possible keywords...", key_len, total_combinations);
-        } else {
-            println!("INFO: Vigenere trying key length {}: Testing {} possible keywords...", key_len, total_combinations);
+    log::info!("Vigenere trying key length {}: Testing {} possible keywords...", key_len, total_combinations);
+
+
+    let mut candidates = Vec::with_capacity(total_combinations);
+    let combinations_iter = top_shifts_per_column.into_iter().multi_cartesian_product();
+    let mut _combinations_processed: usize = 0;
+
+
+    for key_combination in combinations_iter {
+        _combinations_processed += 1;
+
+
+        if total_combinations > PROGRESS_UPDATE_INTERVAL && _combinations_processed.is_multiple_of(PROGRESS_UPDATE_INTERVAL) {
+            log::debug!("... checked {} / {} combinations for length {}", _combinations_processed, total_combinations, key_len);
         }
 
 
-        let combinations_iter = top_shifts_per_column.clone().into_iter().multi_cartesian_product();
-        let mut _combinations_processed: usize = 0;
+        let keyword = analysis::shifts_to_keyword(&key_combination);
 
+        if keyword.is_empty() { continue; }
 
-        for key_combination in combinations_iter {
-            _combinations_processed += 1;
+        let letters = vigenere_decrypt(&alpha_upper, &keyword);
+        let score = score_prepared(&letters, scoring);
 
+        candidates.push(Candidate { key: keyword, letters, score });
+    }
 
-            if total_combinations > PROGRESS_UPDATE_INTERVAL && _combinations_processed % PROGRESS_UPDATE_INTERVAL == 0 {
-                println!("INFO: ... checked {} / {} combinations for length {}", _combinations_processed, total_combinations, key_len);
-            }
+    log::info!("Finished testing key length {}.", key_len);
 
+    Some(candidates)
+}
 
-            let keyword: String = key_combination.into_iter().map(|shift| (b'A' + shift) as char).collect();
 
-            if keyword.is_empty() { continue; }
+// MIC can lock onto a key whose every letter is off by the same amount, which leaves the plaintext
+// uniformly Caesar-shifted. Rotating the whole key by each of the 26 shifts is cheap, so return the
+// best rotation when it beats the attempt as given.
+pub(super) fn best_key_rotation(ciphertext: &str, attempt: &DecryptionAttempt, scoring: CandidateScoring) -> Option<DecryptionAttempt> {
+    if attempt.key.is_empty() || !attempt.key.chars().all(|c| c.is_ascii_uppercase()) {
+        return None;
+    }
 
-            let plaintext = vigenere_decrypt(ciphertext, &keyword);
+    let alpha_upper = analysis::get_alphabetic_chars(ciphertext).to_ascii_uppercase();
+    let (best_key, best_score) = (1..26u8)
+        .map(|rotation| {
+            let key: String = attempt.key.bytes().map(|b| (b'A' + (b - b'A' + rotation) % 26) as char).collect();
+            let score = score_prepared(&vigenere_decrypt(&alpha_upper, &key), scoring);
+            (key, score)
+        })
+        .max_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(Ordering::Equal))?;
+
+    (best_score > attempt.score).then(|| DecryptionAttempt {
+        cipher_name: "Vigenere".to_string(),
+        plaintext: vigenere_decrypt(ciphertext, &best_key),
+        key: best_key,
+        score: best_score,
+        note: None,
+        solver_stats: None,
+    })
+}
 
-            let score = analysis::score_trigram_log_prob(&plaintext);
 
+pub(super) fn run_vigenere_decryption_for_key_length(
+    ciphertext: &str,
+    key_len: usize,
+    min_chars_for_mic: usize,
+    scoring: CandidateScoring,
+) -> Vec<DecryptionAttempt> {
+    let alpha_text = analysis::get_alphabetic_chars(ciphertext);
+    let candidates = attempts_for_key_length(&alpha_text, key_len, min_chars_for_mic, scoring).unwrap_or_default();
+    let mut attempts = rank_candidates(candidates, scoring.method(alpha_text.len()), ciphertext);
+    attempts.iter_mut().for_each(annotate_caesar_reduction);
+
+    attempts
+}
+
+
+pub(super) fn run_vigenere_decryption_with_key(ciphertext: &str, keyword: &str, scoring: CandidateScoring) -> Option<DecryptionAttempt> {
+    if keyword.is_empty() || !keyword.chars().all(|c| c.is_ascii_alphabetic()) {
+        return None;
+    }
+
+    let plaintext = vigenere_decrypt(ciphertext, keyword);
+    let alpha_upper = analysis::get_alphabetic_chars(&plaintext).to_ascii_uppercase();
+    let score = score_prepared(&alpha_upper, scoring);
+
+    let mut attempt = DecryptionAttempt {
+        cipher_name: "Vigenere".to_string(),
+        key: keyword.to_ascii_uppercase(),
+        plaintext,
+        score,
+        note: None,
+        solver_stats: None,
+    };
+    annotate_caesar_reduction(&mut attempt);
+
+    Some(attempt)
+}
 
 
-            attempts.push(DecryptionAttempt {
-                cipher_name: "Vigenere".to_string(),
-                key: keyword,
-                plaintext,
-                score,
-            });
+// Column `index` of every message under `key_len`, joined. Each message restarts the key.
+fn combined_column(alpha_uppers: &[String], key_len: usize, index: usize) -> String {
+    alpha_uppers
+        .iter()
+        .flat_map(|alpha| alpha.chars().skip(index).step_by(key_len))
+        .collect()
+}
+
+// "KEYKEY" decrypts exactly like "KEY", so report the shortest repeating unit.
+fn shortest_key_period(key: &str) -> &str {
+    (1..key.len())
+        .filter(|period| key.len().is_multiple_of(*period))
+        .find(|&period| key.as_bytes().chunks(period).all(|chunk| chunk == &key.as_bytes()[..period]))
+        .map_or(key, |period| &key[..period])
+}
+
+// Best key for messages sharing one Vigenere key: pooling their columns gives MIC more letters than
+// any single message has. Returns the key and its score over all messages' letters.
+fn best_shared_key(
+    alpha_uppers: &[String],
+    min_chars_for_mic: usize,
+    max_key_len: usize,
+    scoring: CandidateScoring,
+) -> Option<(String, f64)> {
+    let mut key_lengths: Vec<(usize, f64)> = (2..=max_key_len)
+        .filter_map(|key_len| {
+            let columns: Vec<String> = (0..key_len).map(|i| combined_column(alpha_uppers, key_len, i)).collect();
+            let ics: Vec<f64> = columns.iter().filter_map(|column| analysis::calculate_ic(column)).collect();
+            (ics.len() == key_len).then(|| (key_len, ics.iter().sum::<f64>() / key_len as f64))
+        })
+        .collect();
+    key_lengths.sort_by(|a, b| {
+        let diff_a = (a.1 - analysis::ENGLISH_IC).abs();
+        let diff_b = (b.1 - analysis::ENGLISH_IC).abs();
+        diff_a.partial_cmp(&diff_b).unwrap_or(Ordering::Equal)
+    });
+
+    let score_key = |key: &str| {
+        let plaintext: String = alpha_uppers.iter().map(|alpha| vigenere_decrypt(alpha, key)).collect();
+        score_prepared(&plaintext, scoring)
+    };
+
+    let mut best: Option<(String, f64)> = None;
+    for (key_len, _) in key_lengths.into_iter().take(MAX_KEY_LENGTHS_TO_TRY) {
+        let Some(mut top_shifts_per_column) = (0..key_len)
+            .map(|i| {
+                let column = combined_column(alpha_uppers, key_len, i);
+                top_column_shifts(&column, min_chars_for_mic, scoring.column_method)
+                    .map(|shifts| shifts.into_iter().map(|(shift, _)| shift).collect::<Vec<u8>>())
+            })
+            .collect::<Option<Vec<Vec<u8>>>>()
+        else {
+            continue;
+        };
+        if let Some(max_combinations) = scoring.max_combinations {
+            cipher_utils::limit_candidate_combinations(&mut top_shifts_per_column, max_combinations);
         }
 
-        println!("INFO: Finished testing key length {}.", key_len);
+        for key_combination in top_shifts_per_column.into_iter().multi_cartesian_product() {
+            let key = analysis::shifts_to_keyword(&key_combination);
+            let score = score_key(&key);
+            if best.as_ref().is_none_or(|(_, best_score)| score > *best_score) {
+                best = Some((key, score));
+            }
+        }
+    }
+    best
+}
+
+pub(super) fn run_vigenere_multi_decryption(
+    ciphertexts: &[&str],
+    min_chars_for_mic: usize,
+    max_key_len: usize,
+    scoring: CandidateScoring,
+) -> Vec<(String, DecryptionAttempt)> {
+    let alpha_uppers: Vec<String> = ciphertexts
+        .iter()
+        .map(|ciphertext| analysis::get_alphabetic_chars(ciphertext).to_ascii_uppercase())
+        .collect();
+    let Some((key, _)) = best_shared_key(&alpha_uppers, min_chars_for_mic, max_key_len, scoring) else {
+        return Vec::new();
+    };
+    let key = shortest_key_period(&key);
+    log::info!("Shared Vigenere key across {} ciphertexts: {}", ciphertexts.len(), key);
+
+    ciphertexts
+        .iter()
+        .filter_map(|ciphertext| {
+            run_vigenere_decryption_with_key(ciphertext, key, scoring).map(|attempt| (ciphertext.to_string(), attempt))
+        })
+        .collect()
+}
+
+
+pub(super) fn run_vigenere_key_length_diagnostics(
+    ciphertext: &str,
+    key_len: usize,
+    min_chars_for_mic: usize,
+) -> Option<KeyLengthDiagnostics> {
+    if key_len == 0 {
+        return None;
     }
 
+    let alpha_text = analysis::get_alphabetic_chars(ciphertext);
+    let columns = (0..key_len)
+        .map(|i| {
+            let column: String = alpha_text.chars().skip(i).step_by(key_len).collect();
+            ColumnDiagnostics {
+                column: i,
+                letter_count: column.len(),
+                top_shifts: analysis::find_top_n_caesar_shifts_mic(&column, TOP_N_SHIFTS_PER_COLUMN, min_chars_for_mic)
+                    .unwrap_or_default(),
+            }
+        })
+        .collect();
 
+    Some(KeyLengthDiagnostics { key_len, columns })
+}
 
-    attempts.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(Ordering::Equal));
 
-    attempts
+// The reasoning behind `key`: where the key lengths tried came from, then each column's shift as
+// ranked by the configured column method, with the runner-up.
+pub(super) fn explain_key(
+    ciphertext: &str,
+    key: &str,
+    min_chars_for_mic: usize,
+    kasiski: &KasiskiSettings,
+    scoring: CandidateScoring,
+) -> Vec<String> {
+    let alpha_text = analysis::get_alphabetic_chars(ciphertext);
+    let key_len = key.len();
+    let (lengths, source) = key_lengths_to_try(&alpha_text, kasiski, scoring);
+    let mut lines = vec![format!(
+        "Key lengths tried: {:?}, from {}; key length {} gave the best plaintext.",
+        lengths, source.label(), key_len
+    )];
+
+    let method_label = match scoring.column_method {
+        ColumnShiftMethod::Mic => "MIC",
+        ColumnShiftMethod::ChiSquared => "chi-squared",
+    };
+    for (index, key_letter) in key.chars().enumerate() {
+        let shift = (key_letter.to_ascii_uppercase() as u8).wrapping_sub(b'A');
+        let column: String = alpha_text.chars().skip(index).step_by(key_len).collect();
+        let mut line = format!("Column {}: shift {} ({})", index + 1, shift, key_letter);
+        if let Some(top_shifts) = top_column_shifts(&column, min_chars_for_mic, scoring.column_method) {
+            match top_shifts.iter().find(|(s, _)| *s == shift) {
+                Some((_, score)) => line.push_str(&format!(", {} {:.4}", method_label, score)),
+                None => line.push_str(&format!(", outside the top {} {} shifts", TOP_N_SHIFTS_PER_COLUMN, method_label)),
+            }
+            if let Some((runner_up, score)) = top_shifts.iter().find(|(s, _)| *s != shift) {
+                line.push_str(&format!(", runner-up shift {} at {:.4}", runner_up, score));
+            }
+        }
+        line.push('.');
+        lines.push(line);
+    }
+    lines
 }
@@ -1,42 +1,91 @@
-use crate::identifier::IdentificationResult;
-use crate::analysis;
+use crate::identifier::{CipherParams, IdentificationResult};
+use crate::analysis::{self, KasiskiSettings};
+use crate::cipher_utils;
 
-const MIN_KASISKI_SEQ_LEN: usize = 3;
-const MAX_KASISKI_KEY_LEN: usize = 20;
-const VIGENERE_IC_UPPER_THRESHOLD: f64 = 0.060;
 
+const CONSENSUS_CONFIDENCE_BOOST: f64 = 0.05;
 
-pub(super) fn run_vigenere_identification(ciphertext: &str, min_text_len: usize) -> Option<IdentificationResult> {
+fn is_related(a: usize, b: usize) -> bool {
+    a != b && (a.is_multiple_of(b) || b.is_multiple_of(a))
+}
+
+// When several top lengths divide one another (e.g. 2, 3 and 6), both methods are seeing the same
+// period. Pick the candidate related to the most others, preferring the shorter on ties, as long as
+// some smaller candidate divides it.
+fn consensus_key_length(candidates: &[usize]) -> Option<usize> {
+    let mut unique = candidates.to_vec();
+    unique.sort_unstable();
+    unique.dedup();
+
+    unique
+        .iter()
+        .filter(|&&len| unique.iter().any(|&other| other < len && len.is_multiple_of(other)))
+        .map(|&len| (len, unique.iter().filter(|&&other| is_related(len, other)).count()))
+        .filter(|&(_, related)| related >= 2)
+        .max_by(|a, b| a.1.cmp(&b.1).then(b.0.cmp(&a.0)))
+        .map(|(len, _)| len)
+}
+
+
+// Trigram average of the plaintext from the single best MIC shift per column, or None when a column
+// is too short for MIC.
+fn quick_key_score(alpha_upper: &str, key_len: usize, min_chars_for_mic: usize) -> Option<f64> {
+    let shifts = (0..key_len)
+        .map(|i| {
+            let column: String = alpha_upper.chars().skip(i).step_by(key_len).collect();
+            analysis::find_top_n_caesar_shifts_mic(&column, 1, min_chars_for_mic).map(|top| top[0].0)
+        })
+        .collect::<Option<Vec<u8>>>()?;
+
+    let plaintext = cipher_utils::apply_keystream(alpha_upper, shifts.iter().map(|&s| -(s as i8)).cycle());
+    Some(analysis::score_trigram_avg_prepared(&plaintext))
+}
+
+// Beaufort (C = K - P) has the same statistics as Vigenere, but its Atbash-mirrored ciphertext is a
+// Vigenere cipher. Returns both quick-key scores when Beaufort reads better.
+fn beaufort_scores(alpha_upper: &str, key_len: usize, min_chars_for_mic: usize) -> Option<(f64, f64)> {
+    let vigenere = quick_key_score(alpha_upper, key_len, min_chars_for_mic)?;
+    let beaufort = quick_key_score(&cipher_utils::atbash(alpha_upper), key_len, min_chars_for_mic)?;
+    (beaufort > vigenere).then_some((beaufort, vigenere))
+}
+
+
+pub(super) fn run_vigenere_identification(
+    ciphertext: &str,
+    min_text_len: usize,
+    min_chars_for_mic: usize,
+    ic_upper_threshold: f64,
+    force: bool,
+    kasiski: KasiskiSettings,
+) -> Option<IdentificationResult> {
     let alpha_text = analysis::get_alphabetic_chars(ciphertext);
 
     if alpha_text.len() < min_text_len {
         return None;
     }
 
-    let ic = match analysis::calculate_ic(&alpha_text) {
-        Some(val) => val,
-        None => return None,
-    };
+    let ic = analysis::calculate_ic(&alpha_text)?;
 
 
-    if ic > VIGENERE_IC_UPPER_THRESHOLD {
+    let above_threshold = ic > ic_upper_threshold;
+    if above_threshold && !force {
         return None;
     }
 
 
-    let kasiski_estimates = analysis::estimate_key_lengths(
-        &alpha_text,
-        MIN_KASISKI_SEQ_LEN,
-        MAX_KASISKI_KEY_LEN
-    );
+    let kasiski_estimates = analysis::estimate_key_lengths_with(&alpha_text, &kasiski);
     let ic_periodicity_estimates = analysis::estimate_key_length_ic_periodicity(
         &alpha_text,
         2,
-        MAX_KASISKI_KEY_LEN
+        kasiski.max_key_len
     );
 
     let mut params_parts = Vec::new();
-    params_parts.push(format!("Low IC ({:.4})", ic));
+    if above_threshold {
+        params_parts.push(format!("High IC ({:.4}) above the {:.3} threshold, forced", ic, ic_upper_threshold));
+    } else {
+        params_parts.push(format!("Low IC ({:.4})", ic));
+    }
 
     if !kasiski_estimates.is_empty() {
         let top_kasiski = kasiski_estimates
@@ -62,14 +111,45 @@ pub(super) fn run_vigenere_identification(ciphertext: &str, min_text_len: usize)
         params_parts.push("IC Periodicity inconclusive".to_string());
     }
 
+    let top_lengths: Vec<usize> = kasiski_estimates
+        .iter()
+        .take(3)
+        .map(|(len, _)| *len)
+        .chain(ic_periodicity_estimates.iter().take(3).map(|(len, _)| *len))
+        .collect();
+    let consensus = consensus_key_length(&top_lengths);
+    let mut candidate_key_lengths: Vec<usize> = Vec::with_capacity(top_lengths.len());
+    for len in &top_lengths {
+        if !candidate_key_lengths.contains(len) {
+            candidate_key_lengths.push(*len);
+        }
+    }
+    if let Some(len) = consensus {
+        params_parts.push(format!("Consensus length: {}", len));
+    }
+
+    let likely_key_len = consensus
+        .or(ic_periodicity_estimates.first().map(|(len, _)| *len))
+        .or(kasiski_estimates.first().map(|(len, _)| *len));
+    let alpha_upper = alpha_text.to_ascii_uppercase();
+    let beaufort = likely_key_len.and_then(|len| beaufort_scores(&alpha_upper, len, min_chars_for_mic));
+    if let Some((beaufort_score, vigenere_score)) = beaufort {
+        params_parts.push(format!(
+            "Variant: Beaufort (Trigram Avg {:.2} vs {:.2} as Vigenere)",
+            beaufort_score, vigenere_score
+        ));
+    }
+
     let params_string = params_parts.join(". ");
 
 
     let confidence = ((ic - analysis::RANDOM_IC) / (analysis::ENGLISH_IC - analysis::RANDOM_IC))
-        .max(0.0)
-        .min(1.0);
+        .clamp(0.0, 1.0);
 
-    let inverted_confidence = 1.0 - confidence;
+    let mut inverted_confidence = 1.0 - confidence;
+    if consensus.is_some() {
+        inverted_confidence = (inverted_confidence + CONSENSUS_CONFIDENCE_BOOST).min(1.0);
+    }
 
 
     Some(IdentificationResult {
@@ -77,5 +157,11 @@ pub(super) fn run_vigenere_identification(ciphertext: &str, min_text_len: usize)
 
         confidence_score: inverted_confidence,
         parameters: Some(params_string),
+        params: CipherParams::Vigenere {
+            candidate_key_lengths,
+            consensus_key_length: consensus,
+            ic,
+            beaufort: beaufort.is_some(),
+        },
     })
 }
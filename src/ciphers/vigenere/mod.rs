@@ -2,49 +2,192 @@ mod identify;
 mod decode;
 
 use crate::identifier::{Identifier, IdentificationResult};
-use crate::decoder::{Decoder, DecryptionAttempt};
+use crate::decoder::{DecodeOutcome, Decoder, DecryptionAttempt, ScoringMethod};
 use crate::config::Config;
+use crate::analysis::{self, KasiskiSettings};
+
+pub use decode::TOP_N_SHIFTS_PER_COLUMN;
 
 
-#[derive(Default)]
 pub struct VigenereIdentifier {
     min_text_len: usize,
+    min_chars_for_mic: usize,
+    ic_upper_threshold: f64,
+    force_identify: bool,
+    kasiski: KasiskiSettings,
 }
 
-#[derive(Default)]
+#[derive(Debug)]
 pub struct VigenereDecoder {
     min_text_len: usize,
+    min_chars_for_mic: usize,
+    kasiski: KasiskiSettings,
+    scoring: decode::CandidateScoring,
+}
+
+// MIC ranking for one key-position column; columns too short for MIC have no shifts.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColumnDiagnostics {
+    pub column: usize,
+    pub letter_count: usize,
+    pub top_shifts: Vec<(u8, f64)>,
+}
+
+impl ColumnDiagnostics {
+    // A small gap between the two best MIC scores means the column's key letter is a near-tie.
+    pub fn score_gap(&self) -> Option<f64> {
+        match self.top_shifts.as_slice() {
+            [(_, best), (_, second), ..] => Some(best - second),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeyLengthDiagnostics {
+    pub key_len: usize,
+    pub columns: Vec<ColumnDiagnostics>,
 }
 
 impl VigenereIdentifier {
     pub fn new(config: &Config) -> Self {
         VigenereIdentifier {
             min_text_len: config.vigenere_min_id_len,
+            min_chars_for_mic: config.min_chars_for_mic,
+            ic_upper_threshold: config.vigenere_ic_upper_threshold,
+            force_identify: config.vigenere_force_identify,
+            kasiski: KasiskiSettings::from_config(config),
         }
     }
 }
 
+impl Default for VigenereIdentifier {
+    fn default() -> Self {
+        Self::new(&Config::default())
+    }
+}
+
 impl VigenereDecoder {
     pub fn new(config: &Config) -> Self {
         VigenereDecoder {
             min_text_len: config.vigenere_min_dec_len,
+            min_chars_for_mic: config.min_chars_for_mic,
+            kasiski: KasiskiSettings {
+                max_key_len: config.kasiski_max_dec_key_len,
+                ..KasiskiSettings::from_config(config)
+            },
+            scoring: decode::CandidateScoring {
+                column_method: config.vigenere_column_method,
+                bigram_scoring_max_len: config.bigram_scoring_max_len,
+                rank_by_trigram_avg: config.vigenere_rank_by_trigram_avg,
+                rank_lengths_by_expected_ic: config.vigenere_rank_lengths_by_expected_ic,
+                max_combinations: config.max_key_combinations,
+            },
         }
     }
+
+    pub fn key_length_diagnostics(&self, ciphertext: &str, key_len: usize) -> Option<KeyLengthDiagnostics> {
+        decode::run_vigenere_key_length_diagnostics(ciphertext, key_len, self.min_chars_for_mic)
+    }
+
+    // How this decoder arrived at `key`, one sentence per line; see `decode::explain_key`.
+    pub fn explain_key(&self, ciphertext: &str, key: &str) -> Vec<String> {
+        decode::explain_key(ciphertext, key, self.min_chars_for_mic, &self.kasiski, self.scoring)
+    }
+
+    // Best whole-key rotation of `attempt`, if one scores higher; see `decode::best_key_rotation`.
+    pub fn refine_key_rotation(&self, ciphertext: &str, attempt: &DecryptionAttempt) -> Option<DecryptionAttempt> {
+        decode::best_key_rotation(ciphertext, attempt, self.scoring).map(|mut rotated| {
+            decode::annotate_caesar_reduction(&mut rotated);
+            rotated
+        })
+    }
+
+    // Decrypts several ciphertexts assumed to share one key (each starting at the key's first
+    // letter), pooling their columns to recover it. Pairs each input with its attempt, in order.
+    pub fn decrypt_multi(&self, ciphertexts: &[&str]) -> Vec<(String, DecryptionAttempt)> {
+        decode::run_vigenere_multi_decryption(ciphertexts, self.min_chars_for_mic, self.kasiski.max_key_len, self.scoring)
+    }
+
+    pub fn decrypt_with_key_length(&self, ciphertext: &str, key_len: usize) -> Vec<DecryptionAttempt> {
+        decode::run_vigenere_decryption_for_key_length(ciphertext, key_len, self.min_chars_for_mic, self.scoring)
+    }
+}
+
+impl Default for VigenereDecoder {
+    fn default() -> Self {
+        Self::new(&Config::default())
+    }
 }
 
 
 impl Identifier for VigenereIdentifier {
+    fn name(&self) -> &'static str {
+        "Vigenere"
+    }
+
+    fn description(&self) -> &'static str {
+        "Looks for the low IC and periodic repeats of a repeating-key cipher"
+    }
+
     fn identify(&self, ciphertext: &str) -> Option<IdentificationResult> {
-        identify::run_vigenere_identification(ciphertext, self.min_text_len)
+        identify::run_vigenere_identification(
+            ciphertext,
+            self.min_text_len,
+            self.min_chars_for_mic,
+            self.ic_upper_threshold,
+            self.force_identify,
+            self.kasiski,
+        )
+    }
+
+    fn min_ciphertext_len(&self) -> usize {
+        self.min_text_len
     }
 }
 
 impl Decoder for VigenereDecoder {
     fn decrypt(&self, ciphertext: &str) -> Vec<DecryptionAttempt> {
-        decode::run_vigenere_decryption(ciphertext, self.min_text_len)
+        self.decrypt_outcome(ciphertext).into_attempts()
+    }
+
+    fn decrypt_outcome(&self, ciphertext: &str) -> DecodeOutcome {
+        decode::run_vigenere_decryption(
+            ciphertext,
+            self.min_text_len,
+            self.min_chars_for_mic,
+            self.kasiski,
+            self.scoring,
+        )
     }
 
     fn name(&self) -> &'static str {
         "Vigenere"
     }
+
+    fn description(&self) -> &'static str {
+        "Polyalphabetic substitution using a repeating keyword"
+    }
+
+    fn cache_key(&self) -> Option<String> {
+        Some(format!("{:?}", self))
+    }
+
+    fn min_ciphertext_len(&self) -> usize {
+        self.min_text_len
+    }
+
+    fn scoring_method(&self) -> ScoringMethod {
+        ScoringMethod::Trigram
+    }
+
+    fn scoring_method_for(&self, attempts: &[DecryptionAttempt]) -> ScoringMethod {
+        attempts.first().map_or(self.scoring_method(), |attempt| {
+            self.scoring.method(analysis::get_alphabetic_chars(&attempt.plaintext).len())
+        })
+    }
+
+    fn decrypt_with_key(&self, ciphertext: &str, key: &str) -> Option<DecryptionAttempt> {
+        decode::run_vigenere_decryption_with_key(ciphertext, key, self.scoring)
+    }
 }
@@ -0,0 +1,170 @@
+use crate::decoder::{self, DecryptionAttempt, ScoringMethod};
+use crate::analysis;
+use crate::cipher_utils;
+use crate::ciphers::encoding;
+use crate::config::OutputEncoding;
+use std::cmp::Ordering;
+
+
+const MIN_XOR_KEYSIZE: usize = 2;
+const MAX_XOR_KEYSIZE: usize = 40;
+const KEYSIZES_TO_TRY: usize = 3;
+const TOP_SINGLE_BYTE_RESULTS: usize = 5;
+const NON_PRINTABLE_PENALTY: f64 = 50.0;
+const NON_TEXT_PENALTY: f64 = 10.0;
+
+
+// Hex strings and text carrying control characters are byte data; readable text isn't XOR output.
+pub(super) fn is_byte_input(ciphertext: &str) -> bool {
+    encoding::decode_hex(ciphertext.trim()).is_some()
+        || ciphertext.chars().any(|c| c.is_control() && !c.is_whitespace())
+}
+
+fn input_bytes(ciphertext: &str) -> Vec<u8> {
+    encoding::decode_hex(ciphertext.trim()).unwrap_or_else(|| ciphertext.as_bytes().to_vec())
+}
+
+fn xor_with_key(bytes: &[u8], key: &[u8]) -> Vec<u8> {
+    bytes
+        .iter()
+        .zip(key.iter().cycle())
+        .map(|(b, k)| b ^ k)
+        .collect()
+}
+
+fn hex_key(key: &[u8]) -> String {
+    encoding::encode_hex(key)
+}
+
+// Lower is better, in the same direction as chi-squared: letter frequencies against English,
+// plus penalties for control bytes and for bytes that are neither letters nor spaces.
+fn score_plaintext_bytes(bytes: &[u8]) -> f64 {
+    if bytes.is_empty() {
+        return f64::MAX;
+    }
+
+    let len = bytes.len() as f64;
+    let non_printable = bytes
+        .iter()
+        .filter(|&&b| !(b.is_ascii_graphic() || b == b' ' || b == b'\n' || b == b'\r' || b == b'\t'))
+        .count() as f64;
+    let non_text = bytes
+        .iter()
+        .filter(|&&b| !(b.is_ascii_alphabetic() || b == b' '))
+        .count() as f64;
+
+    let ascii: String = bytes.iter().filter(|b| b.is_ascii()).map(|&b| b as char).collect();
+    let chi2 = analysis::score_english_likelihood(&ascii).unwrap_or(NON_PRINTABLE_PENALTY);
+
+    chi2 + NON_PRINTABLE_PENALTY * non_printable / len + NON_TEXT_PENALTY * non_text / len
+}
+
+fn best_single_byte_key(bytes: &[u8]) -> (u8, f64) {
+    (0..=255u8)
+        .map(|key| (key, score_plaintext_bytes(&xor_with_key(bytes, &[key]))))
+        .min_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(Ordering::Equal).then(a.0.cmp(&b.0)))
+        .unwrap_or((0, f64::MAX))
+}
+
+// Average normalized Hamming distance between consecutive keysize blocks; the true keysize scores lowest.
+fn rank_keysizes(bytes: &[u8]) -> Vec<(usize, f64)> {
+    let max_keysize = std::cmp::min(MAX_XOR_KEYSIZE, bytes.len() / 2);
+    let mut ranked: Vec<(usize, f64)> = (MIN_XOR_KEYSIZE..=max_keysize)
+        .map(|keysize| {
+            let blocks: Vec<&[u8]> = bytes.chunks_exact(keysize).collect();
+            let pairs = blocks.len() - 1;
+            let total: f64 = blocks
+                .windows(2)
+                .filter_map(|pair| cipher_utils::hamming_distance(pair[0], pair[1]))
+                .map(|distance| distance as f64 / keysize as f64)
+                .sum();
+            (keysize, total / pairs as f64)
+        })
+        .collect();
+
+    ranked.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(Ordering::Equal).then(a.0.cmp(&b.0)));
+    ranked
+}
+
+// A multiple of the true keysize solves to the key repeated, e.g. "KEYKEY"; reduce it to its period.
+fn shortest_period(key: &[u8]) -> &[u8] {
+    (1..key.len())
+        .filter(|&period| key.len().is_multiple_of(period))
+        .find(|&period| key.chunks(period).all(|chunk| chunk == &key[..period]))
+        .map(|period| &key[..period])
+        .unwrap_or(key)
+}
+
+fn make_attempt(bytes: &[u8], key: &[u8], output_encoding: OutputEncoding) -> DecryptionAttempt {
+    let plain_bytes = xor_with_key(bytes, key);
+    DecryptionAttempt {
+        cipher_name: "XOR".to_string(),
+        key: hex_key(key),
+        plaintext: output_encoding.render(&plain_bytes),
+        score: score_plaintext_bytes(&plain_bytes),
+        note: None,
+        solver_stats: None,
+    }
+}
+
+
+pub(super) fn run_xor_decryption(ciphertext: &str, output_encoding: OutputEncoding) -> Vec<DecryptionAttempt> {
+    let bytes = input_bytes(ciphertext);
+    if bytes.is_empty() {
+        return Vec::new();
+    }
+
+    // Key 0x00 would just echo the input back, so it is never a useful candidate.
+    let mut single_byte_scores: Vec<(u8, f64)> = (1..=255u8)
+        .map(|key| (key, score_plaintext_bytes(&xor_with_key(&bytes, &[key]))))
+        .collect();
+    single_byte_scores.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(Ordering::Equal).then(a.0.cmp(&b.0)));
+
+    let mut attempts: Vec<DecryptionAttempt> = single_byte_scores
+        .iter()
+        .take(TOP_SINGLE_BYTE_RESULTS)
+        .map(|(key, _)| make_attempt(&bytes, &[*key], output_encoding))
+        .collect();
+
+    for (keysize, _) in rank_keysizes(&bytes).into_iter().take(KEYSIZES_TO_TRY) {
+        let key: Vec<u8> = (0..keysize)
+            .map(|i| {
+                let column: Vec<u8> = bytes.iter().skip(i).step_by(keysize).copied().collect();
+                best_single_byte_key(&column).0
+            })
+            .collect();
+
+        let key = shortest_period(&key);
+        if key.len() == 1 {
+            continue; // Already covered by the single-byte search.
+        }
+        let key_hex = hex_key(key);
+        if attempts.iter().any(|a| a.key == key_hex) {
+            continue;
+        }
+        attempts.push(make_attempt(&bytes, key, output_encoding));
+    }
+
+    decoder::sort_best_first(&mut attempts, ScoringMethod::ChiSquared);
+
+    attempts
+}
+
+
+pub(super) fn run_xor_decryption_with_key(ciphertext: &str, key: &str, output_encoding: OutputEncoding) -> Option<DecryptionAttempt> {
+    let key_bytes = encoding::decode_hex(key.trim()).or_else(|| {
+        // Short hex keys (e.g. "58") are below the encoding detector's minimum length.
+        let trimmed = key.trim();
+        if trimmed.len() == 2 {
+            u8::from_str_radix(trimmed, 16).ok().map(|b| vec![b])
+        } else {
+            None
+        }
+    })?;
+
+    let bytes = input_bytes(ciphertext);
+    if bytes.is_empty() {
+        return None;
+    }
+    Some(make_attempt(&bytes, &key_bytes, output_encoding))
+}
@@ -0,0 +1,43 @@
+mod decode;
+
+use crate::decoder::{Decoder, DecryptionAttempt};
+use crate::config::{Config, OutputEncoding};
+
+#[derive(Debug, Default)]
+pub struct XorDecoder {
+    output_encoding: OutputEncoding,
+}
+
+impl XorDecoder {
+    pub fn new(config: &Config) -> Self {
+        XorDecoder {
+            output_encoding: config.output_encoding,
+        }
+    }
+}
+
+impl Decoder for XorDecoder {
+    fn decrypt(&self, ciphertext: &str) -> Vec<DecryptionAttempt> {
+        decode::run_xor_decryption(ciphertext, self.output_encoding)
+    }
+
+    fn name(&self) -> &'static str {
+        "XOR"
+    }
+
+    fn description(&self) -> &'static str {
+        "Bytes XORed with a repeating key"
+    }
+
+    fn cache_key(&self) -> Option<String> {
+        Some(format!("{:?}", self))
+    }
+
+    fn skip_reason(&self, text: &str) -> Option<String> {
+        (!decode::is_byte_input(text)).then(|| "input is neither hex nor binary".to_string())
+    }
+
+    fn decrypt_with_key(&self, ciphertext: &str, key: &str) -> Option<DecryptionAttempt> {
+        decode::run_xor_decryption_with_key(ciphertext, key, self.output_encoding)
+    }
+}
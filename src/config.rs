@@ -1,11 +1,68 @@
 // src/config.rs
 
+use std::fmt;
+use crate::ciphers::encoding;
+
+// How the Vigenere solver ranks candidate shifts for each key-position column.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ColumnShiftMethod {
+    #[default]
+    Mic,
+    ChiSquared,
+}
+
+// How byte-oriented decoders show plaintext bytes that are not valid UTF-8.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OutputEncoding {
+    #[default]
+    Utf8Lossy,
+    Hex,
+    Base64,
+}
+
+impl OutputEncoding {
+    // Valid UTF-8 is always shown as text; only binary results use the chosen encoding.
+    pub fn render(self, bytes: &[u8]) -> String {
+        if let Ok(text) = std::str::from_utf8(bytes) {
+            return text.to_string();
+        }
+        match self {
+            OutputEncoding::Utf8Lossy => String::from_utf8_lossy(bytes).into_owned(),
+            OutputEncoding::Hex => encoding::encode_hex(bytes),
+            OutputEncoding::Base64 => encoding::encode_base64(bytes),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
 pub struct Config {
     pub vigenere_min_id_len: usize,
     pub vigenere_min_dec_len: usize,
-    // Add other configurable parameters here later if needed
-    // pub kasiski_min_seq_len: usize,
-    // pub kasiski_max_key_len: usize,
+    pub vigenere_ic_upper_threshold: f64,
+    pub vigenere_force_identify: bool,
+    pub detect_encodings: bool,
+    pub min_chars_for_mic: usize,
+    pub kasiski_min_seq_len: usize,
+    pub kasiski_max_key_len: usize,
+    pub kasiski_max_dec_key_len: usize,
+    pub max_key_combinations: Option<usize>,
+    pub kasiski_max_positions: usize,
+    pub random_seed: Option<u64>,
+    pub bigram_scoring_max_len: usize,
+    pub caesar_strong_match_chi2: Option<f64>,
+    pub vigenere_rank_by_trigram_avg: bool,
+    pub vigenere_column_method: ColumnShiftMethod,
+    pub vigenere_rank_lengths_by_expected_ic: bool,
+    pub try_reversed: bool,
+    pub output_encoding: OutputEncoding,
+    pub solver_restarts: usize,
+    pub solver_max_iterations: usize,
+    pub known_plaintext_cribs: Vec<String>,
+    pub transposition_chi2_max: f64,
+    pub mono_ic_min: f64,
+    pub mono_chi2_min: f64,
+    pub caesar_preference_chi2: f64,
+    pub enabled_ciphers: Option<Vec<String>>,
 }
 
 impl Default for Config {
@@ -14,8 +71,277 @@ impl Default for Config {
             // Set default values matching the previous constants
             vigenere_min_id_len: 30,
             vigenere_min_dec_len: 20,
-            // kasiski_min_seq_len: 3,
-            // kasiski_max_key_len: 20,
+            vigenere_ic_upper_threshold: 0.060,
+            // Skip the IC gate so short keys, which leave the IC high, still get identified.
+            vigenere_force_identify: false,
+            detect_encodings: true,
+            min_chars_for_mic: crate::analysis::DEFAULT_MIN_CHARS_FOR_MIC,
+            kasiski_min_seq_len: 3,
+            kasiski_max_key_len: 20,
+            // Key length search limit for the Vigenere and Gronsfeld decoders; identification looks further.
+            kasiski_max_dec_key_len: 12,
+            // None tries every combination of column shifts; Some(n) trims each key length's to n.
+            max_key_combinations: None,
+            // Occurrences per repeated sequence paired up by Kasiski examination.
+            kasiski_max_positions: crate::analysis::DEFAULT_KASISKI_MAX_POSITIONS,
+            random_seed: None,
+            // 0 keeps trigram-only scoring; texts with fewer letters than this are ranked by bigrams.
+            bigram_scoring_max_len: 0,
+            // None searches all 26 shifts; Some(chi2) stops at the first shift scoring below it.
+            caesar_strong_match_chi2: None,
+            vigenere_rank_by_trigram_avg: false,
+            vigenere_column_method: ColumnShiftMethod::Mic,
+            // Order candidate key lengths by `analysis::rank_key_lengths_by_expected_ic`.
+            vigenere_rank_lengths_by_expected_ic: false,
+            // Also decode the character-reversed ciphertext and keep whichever orientation scores better.
+            try_reversed: false,
+            output_encoding: OutputEncoding::Utf8Lossy,
+            // Hill-climbing budget for the stochastic solvers: independent climbs, and key changes
+            // allowed per climb. usize::MAX leaves a climb to stop on its own once it stops improving.
+            solver_restarts: 8,
+            solver_max_iterations: usize::MAX,
+            // Text expected in the plaintext; a candidate containing one becomes the best guess.
+            known_plaintext_cribs: Vec::new(),
+            // Raw ciphertext chi-squared below this suggests English letters, merely rearranged.
+            transposition_chi2_max: 3.0,
+            // An IC near English with frequencies unlike English suggests monoalphabetic substitution.
+            mono_ic_min: 0.058,
+            mono_chi2_min: 5.0,
+            caesar_preference_chi2: crate::ranking::CAESAR_CHI2_PREFERENCE_THRESHOLD,
+            // None runs every cipher; otherwise only identifiers and decoders named here (any case).
+            enabled_ciphers: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConfigError {
+    ZeroValue { field: &'static str },
+    OutOfRange { field: &'static str, value: f64, min: f64, max: f64 },
+    UnknownCipher { name: String },
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigError::ZeroValue { field } => write!(f, "{} must be greater than zero", field),
+            ConfigError::OutOfRange { field, value, min, max } => {
+                write!(f, "{} must be between {} and {}, got {}", field, min, max, value)
+            }
+            ConfigError::UnknownCipher { name } => {
+                write!(f, "unknown cipher '{}'; expected one of {}", name, crate::engine::cipher_names().join(", "))
+            }
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+impl Config {
+    pub fn builder() -> ConfigBuilder {
+        ConfigBuilder::default()
+    }
+
+    pub fn cipher_enabled(&self, name: &str) -> bool {
+        self.enabled_ciphers
+            .as_ref()
+            .is_none_or(|enabled| enabled.iter().any(|cipher| cipher.eq_ignore_ascii_case(name)))
+    }
+
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        let non_zero = [
+            ("vigenere_min_id_len", self.vigenere_min_id_len),
+            ("vigenere_min_dec_len", self.vigenere_min_dec_len),
+            ("min_chars_for_mic", self.min_chars_for_mic),
+            ("kasiski_min_seq_len", self.kasiski_min_seq_len),
+            ("kasiski_max_key_len", self.kasiski_max_key_len),
+            ("kasiski_max_dec_key_len", self.kasiski_max_dec_key_len),
+            ("kasiski_max_positions", self.kasiski_max_positions),
+            ("solver_restarts", self.solver_restarts),
+            ("solver_max_iterations", self.solver_max_iterations),
+        ];
+        if let Some((field, _)) = non_zero.iter().find(|(_, value)| *value == 0) {
+            return Err(ConfigError::ZeroValue { field });
+        }
+
+        // IC values live between 1/26 (random) and 1.0 (a single repeated letter).
+        let threshold = self.vigenere_ic_upper_threshold;
+        if !(crate::analysis::RANDOM_IC..=1.0).contains(&threshold) {
+            return Err(ConfigError::OutOfRange {
+                field: "vigenere_ic_upper_threshold",
+                value: threshold,
+                min: crate::analysis::RANDOM_IC,
+                max: 1.0,
+            });
+        }
+
+        // A misspelled name would otherwise silently disable every cipher.
+        if let Some(enabled) = &self.enabled_ciphers {
+            let known = crate::engine::cipher_names();
+            if let Some(name) = enabled.iter().find(|name| !known.iter().any(|cipher| cipher.eq_ignore_ascii_case(name))) {
+                return Err(ConfigError::UnknownCipher { name: name.clone() });
+            }
         }
+
+        if let Some(chi2) = self.caesar_strong_match_chi2 {
+            if !chi2.is_finite() || chi2 <= 0.0 {
+                return Err(ConfigError::OutOfRange {
+                    field: "caesar_strong_match_chi2",
+                    value: chi2,
+                    min: 0.0,
+                    max: f64::MAX,
+                });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ConfigBuilder {
+    config: Config,
+}
+
+impl ConfigBuilder {
+    pub fn vigenere_min_id_len(mut self, value: usize) -> Self {
+        self.config.vigenere_min_id_len = value;
+        self
+    }
+
+    pub fn vigenere_min_dec_len(mut self, value: usize) -> Self {
+        self.config.vigenere_min_dec_len = value;
+        self
+    }
+
+    pub fn vigenere_ic_upper_threshold(mut self, value: f64) -> Self {
+        self.config.vigenere_ic_upper_threshold = value;
+        self
+    }
+
+    pub fn vigenere_force_identify(mut self, value: bool) -> Self {
+        self.config.vigenere_force_identify = value;
+        self
+    }
+
+    pub fn detect_encodings(mut self, value: bool) -> Self {
+        self.config.detect_encodings = value;
+        self
+    }
+
+    pub fn min_chars_for_mic(mut self, value: usize) -> Self {
+        self.config.min_chars_for_mic = value;
+        self
+    }
+
+    pub fn kasiski_min_seq_len(mut self, value: usize) -> Self {
+        self.config.kasiski_min_seq_len = value;
+        self
+    }
+
+    pub fn kasiski_max_key_len(mut self, value: usize) -> Self {
+        self.config.kasiski_max_key_len = value;
+        self
+    }
+
+    pub fn kasiski_max_dec_key_len(mut self, value: usize) -> Self {
+        self.config.kasiski_max_dec_key_len = value;
+        self
+    }
+
+    pub fn max_key_combinations(mut self, value: usize) -> Self {
+        self.config.max_key_combinations = Some(value);
+        self
+    }
+
+    pub fn kasiski_max_positions(mut self, value: usize) -> Self {
+        self.config.kasiski_max_positions = value;
+        self
+    }
+
+    pub fn random_seed(mut self, value: u64) -> Self {
+        self.config.random_seed = Some(value);
+        self
+    }
+
+    pub fn bigram_scoring_max_len(mut self, value: usize) -> Self {
+        self.config.bigram_scoring_max_len = value;
+        self
+    }
+
+    pub fn caesar_strong_match_chi2(mut self, value: f64) -> Self {
+        self.config.caesar_strong_match_chi2 = Some(value);
+        self
+    }
+
+    pub fn vigenere_rank_by_trigram_avg(mut self, value: bool) -> Self {
+        self.config.vigenere_rank_by_trigram_avg = value;
+        self
+    }
+
+    pub fn vigenere_column_method(mut self, value: ColumnShiftMethod) -> Self {
+        self.config.vigenere_column_method = value;
+        self
+    }
+
+    pub fn vigenere_rank_lengths_by_expected_ic(mut self, value: bool) -> Self {
+        self.config.vigenere_rank_lengths_by_expected_ic = value;
+        self
     }
-}
\ No newline at end of file
+
+    pub fn try_reversed(mut self, value: bool) -> Self {
+        self.config.try_reversed = value;
+        self
+    }
+
+    pub fn output_encoding(mut self, value: OutputEncoding) -> Self {
+        self.config.output_encoding = value;
+        self
+    }
+
+    pub fn solver_restarts(mut self, value: usize) -> Self {
+        self.config.solver_restarts = value;
+        self
+    }
+
+    pub fn solver_max_iterations(mut self, value: usize) -> Self {
+        self.config.solver_max_iterations = value;
+        self
+    }
+
+    pub fn known_plaintext_crib(mut self, crib: &str) -> Self {
+        self.config.known_plaintext_cribs.push(crib.to_string());
+        self
+    }
+
+    pub fn transposition_chi2_max(mut self, value: f64) -> Self {
+        self.config.transposition_chi2_max = value;
+        self
+    }
+
+    pub fn mono_ic_min(mut self, value: f64) -> Self {
+        self.config.mono_ic_min = value;
+        self
+    }
+
+    pub fn mono_chi2_min(mut self, value: f64) -> Self {
+        self.config.mono_chi2_min = value;
+        self
+    }
+
+    pub fn caesar_preference_chi2(mut self, value: f64) -> Self {
+        self.config.caesar_preference_chi2 = value;
+        self
+    }
+
+    // Restricts analysis to the named ciphers; call once per cipher.
+    pub fn enabled_cipher(mut self, name: &str) -> Self {
+        self.config.enabled_ciphers.get_or_insert_with(Vec::new).push(name.to_string());
+        self
+    }
+
+    pub fn build(self) -> Result<Config, ConfigError> {
+        self.config.validate()?;
+        Ok(self.config)
+    }
+}
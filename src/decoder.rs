@@ -1,12 +1,279 @@
+use crate::analysis;
+use std::cmp::Ordering;
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct DecryptionAttempt {
     pub cipher_name: String,
     pub key: String,
     pub plaintext: String,
     pub score: f64,
+    pub note: Option<String>,
+    // Effort the stochastic solver spent on the run that produced this attempt.
+    pub solver_stats: Option<SolverStats>,
+}
+
+// Search effort actually spent, for tuning `solver_restarts` and `solver_max_iterations`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct SolverStats {
+    pub restarts: usize,
+    pub iterations: usize,
+}
+
+impl DecryptionAttempt {
+    // `method` is the scoring method of the decoder that produced both attempts. Scores only
+    // compare within one cipher, so attempts from different ciphers are never better.
+    pub fn better_than(&self, other: &DecryptionAttempt, method: ScoringMethod) -> bool {
+        self.cipher_name == other.cipher_name && method.compare(self.score, other.score) == Ordering::Less
+    }
+}
+
+// Puts the best attempt first under the producing decoder's scoring method. The sort is stable,
+// so equal scores keep their order.
+pub fn sort_best_first(attempts: &mut [DecryptionAttempt], method: ScoringMethod) {
+    attempts.sort_by(|a, b| method.compare(a.score, b.score));
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum DecodeOutcome {
+    Results(Vec<DecryptionAttempt>),
+    TooShort { needed: usize, got: usize },
+    ColumnsTooShort { key_len: usize },
+    NoCandidates,
+}
+
+impl DecodeOutcome {
+    pub fn from_attempts(attempts: Vec<DecryptionAttempt>) -> Self {
+        if attempts.is_empty() {
+            DecodeOutcome::NoCandidates
+        } else {
+            DecodeOutcome::Results(attempts)
+        }
+    }
+
+    pub fn into_attempts(self) -> Vec<DecryptionAttempt> {
+        match self {
+            DecodeOutcome::Results(attempts) => attempts,
+            _ => Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScoringMethod {
+    ChiSquared,
+    Trigram,
+    Quadgram,
+    Bigram,
+}
+
+impl ScoringMethod {
+    pub fn higher_is_better(self) -> bool {
+        !matches!(self, ScoringMethod::ChiSquared)
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ScoringMethod::ChiSquared => "Chi^2 Score",
+            ScoringMethod::Trigram => "Trigram Score",
+            ScoringMethod::Quadgram => "Quadgram Score",
+            ScoringMethod::Bigram => "Bigram Score",
+        }
+    }
+
+    // Orders two scores best-first under this method.
+    pub fn compare(self, a: f64, b: f64) -> Ordering {
+        let ordering = a.partial_cmp(&b).unwrap_or(Ordering::Equal);
+        if self.higher_is_better() { ordering.reverse() } else { ordering }
+    }
 }
 
 pub trait Decoder {
     fn decrypt(&self, ciphertext: &str) -> Vec<DecryptionAttempt>;
     fn name(&self) -> &'static str;
-}
\ No newline at end of file
+
+    // One-line explanation of the cipher for display next to `name()`.
+    fn description(&self) -> &'static str {
+        ""
+    }
+
+    fn scoring_method(&self) -> ScoringMethod {
+        ScoringMethod::ChiSquared
+    }
+
+    // The method behind the scores of `attempts`, as returned by this decoder. Decoders that switch
+    // scorers by input override this; `scoring_method` is what they use otherwise.
+    fn scoring_method_for(&self, _attempts: &[DecryptionAttempt]) -> ScoringMethod {
+        self.scoring_method()
+    }
+
+    // Alphabetic characters needed before this decoder is worth running.
+    fn min_ciphertext_len(&self) -> usize {
+        0
+    }
+
+    // Identifies the settings this decoder was built with, so a cached outcome is only reused while
+    // they stay the same. `None` means outcomes are never cached.
+    fn cache_key(&self) -> Option<String> {
+        None
+    }
+
+    // Why `text` isn't worth decoding with this cipher, if it isn't. Dot-dash input has no letter
+    // statistics, so by default it is left to the Morse decoder.
+    fn skip_reason(&self, text: &str) -> Option<String> {
+        analysis::looks_like_morse(text).then(|| "input looks like Morse".to_string())
+    }
+
+    fn decrypt_with_key(&self, _ciphertext: &str, _key: &str) -> Option<DecryptionAttempt> {
+        None
+    }
+
+    fn decrypt_outcome(&self, ciphertext: &str) -> DecodeOutcome {
+        DecodeOutcome::from_attempts(self.decrypt(ciphertext))
+    }
+}
+
+// Runs `primary`, and only when its best attempt misses `threshold` (in the primary's scoring
+// direction) runs `fallback` too. Scores from different ciphers don't compare, so the two best
+// plaintexts are weighed by trigram average and the winning decoder's attempts are returned.
+pub struct FallbackDecoder {
+    primary: Box<dyn Decoder>,
+    fallback: Box<dyn Decoder>,
+    threshold: f64,
+}
+
+impl FallbackDecoder {
+    pub fn new(primary: Box<dyn Decoder>, fallback: Box<dyn Decoder>, threshold: f64) -> Self {
+        FallbackDecoder { primary, fallback, threshold }
+    }
+
+    fn meets_threshold(&self, attempts: &[DecryptionAttempt]) -> bool {
+        let method = self.primary.scoring_method_for(attempts);
+        attempts.first().is_some_and(|best| method.compare(best.score, self.threshold) != Ordering::Greater)
+    }
+}
+
+impl Decoder for FallbackDecoder {
+    fn decrypt(&self, ciphertext: &str) -> Vec<DecryptionAttempt> {
+        let run = |decoder: &dyn Decoder| {
+            if decoder.skip_reason(ciphertext).is_some() { Vec::new() } else { decoder.decrypt(ciphertext) }
+        };
+        let primary = run(self.primary.as_ref());
+        if self.meets_threshold(&primary) {
+            return primary;
+        }
+
+        let fallback = run(self.fallback.as_ref());
+        match (primary.first(), fallback.first()) {
+            (Some(first), Some(second))
+                if analysis::score_trigram_avg(&first.plaintext) >= analysis::score_trigram_avg(&second.plaintext) =>
+            {
+                primary
+            }
+            (_, Some(_)) => fallback,
+            _ => primary,
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        "Fallback"
+    }
+
+    fn description(&self) -> &'static str {
+        "Tries one decoder, then another when the first scores poorly"
+    }
+
+    fn scoring_method(&self) -> ScoringMethod {
+        self.primary.scoring_method()
+    }
+
+    // Scores are only comparable within one decoder, so follow whichever one produced the results.
+    fn scoring_method_for(&self, attempts: &[DecryptionAttempt]) -> ScoringMethod {
+        match attempts.first() {
+            Some(best) if best.cipher_name == self.fallback.name() => self.fallback.scoring_method_for(attempts),
+            _ => self.primary.scoring_method_for(attempts),
+        }
+    }
+
+    fn min_ciphertext_len(&self) -> usize {
+        self.primary.min_ciphertext_len().min(self.fallback.min_ciphertext_len())
+    }
+
+    fn skip_reason(&self, text: &str) -> Option<String> {
+        self.primary.skip_reason(text).and(self.fallback.skip_reason(text))
+    }
+
+    fn cache_key(&self) -> Option<String> {
+        Some(format!(
+            "{}({}) -> {}({}) at {}",
+            self.primary.name(),
+            self.primary.cache_key()?,
+            self.fallback.name(),
+            self.fallback.cache_key()?,
+            self.threshold
+        ))
+    }
+
+    fn decrypt_with_key(&self, ciphertext: &str, key: &str) -> Option<DecryptionAttempt> {
+        self.primary
+            .decrypt_with_key(ciphertext, key)
+            .or_else(|| self.fallback.decrypt_with_key(ciphertext, key))
+    }
+}
+
+const REVERSED_NOTE: &str = "Ciphertext was reversed before decoding.";
+
+// Both orientations can be merged into one ranking only when the same decoder and method scored them.
+fn scores_comparable(decoder: &dyn Decoder, forward: &[DecryptionAttempt], reversed: &[DecryptionAttempt]) -> bool {
+    let same_cipher = match (forward.first(), reversed.first()) {
+        (Some(a), Some(b)) => a.cipher_name == b.cipher_name,
+        _ => true,
+    };
+    same_cipher && decoder.scoring_method_for(forward) == decoder.scoring_method_for(reversed)
+}
+
+// Runs the decoder on the ciphertext and, when `try_reversed` is set, on its character-reversed
+// form too, merging both so whichever orientation scores better comes first.
+pub fn decrypt_outcome_with_reversal(decoder: &dyn Decoder, ciphertext: &str, try_reversed: bool) -> DecodeOutcome {
+    let forward = decoder.decrypt_outcome(ciphertext);
+    if !try_reversed {
+        return forward;
+    }
+
+    let reversed_text: String = ciphertext.chars().rev().collect();
+    let mut reversed = decoder.decrypt_outcome(&reversed_text).into_attempts();
+    for attempt in &mut reversed {
+        attempt.note = Some(match attempt.note.take() {
+            Some(note) => format!("{} {}", REVERSED_NOTE, note),
+            None => REVERSED_NOTE.to_string(),
+        });
+    }
+
+    match forward {
+        DecodeOutcome::Results(attempts) if !reversed.is_empty() && !scores_comparable(decoder, &attempts, &reversed) => {
+            // Different decoders or methods scored each side, so compare the best plaintexts instead.
+            let best_avg = |attempts: &[DecryptionAttempt]| {
+                attempts.first().map_or(f64::NEG_INFINITY, |best| analysis::score_trigram_avg(&best.plaintext))
+            };
+            if best_avg(&reversed) > best_avg(&attempts) {
+                DecodeOutcome::Results(reversed)
+            } else {
+                DecodeOutcome::Results(attempts)
+            }
+        }
+        DecodeOutcome::Results(mut attempts) => {
+            attempts.extend(reversed);
+            let method = decoder.scoring_method_for(&attempts);
+            // Letter-frequency scores can't tell the orientations apart, so ties go to the more English-like plaintext.
+            attempts.sort_by(|a, b| {
+                method.compare(a.score, b.score).then_with(|| {
+                    let trigram_a = analysis::score_trigram_avg(&a.plaintext);
+                    let trigram_b = analysis::score_trigram_avg(&b.plaintext);
+                    trigram_b.partial_cmp(&trigram_a).unwrap_or(Ordering::Equal)
+                })
+            });
+            DecodeOutcome::Results(attempts)
+        }
+        outcome if reversed.is_empty() => outcome,
+        _ => DecodeOutcome::Results(reversed),
+    }
+}
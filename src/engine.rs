@@ -0,0 +1,422 @@
+// src/engine.rs
+
+use crate::analysis;
+use crate::cipher_utils;
+use crate::ciphers::{
+    a1z26::A1Z26Decoder,
+    bacon::BaconDecoder,
+    caesar::{CaesarDecoder, CaesarIdentifier},
+    gronsfeld::GronsfeldDecoder,
+    keyed_caesar::KeyedCaesarDecoder,
+    morse::MorseDecoder,
+    polybius::PolybiusDecoder,
+    substitution::SubstitutionDecoder,
+    vigenere::{VigenereDecoder, VigenereIdentifier},
+    xor::XorDecoder,
+};
+use crate::config::Config;
+use crate::decoder::{decrypt_outcome_with_reversal, DecodeOutcome, Decoder, DecryptionAttempt, ScoringMethod};
+use crate::identifier::{IdentificationResult, Identifier};
+use crate::preprocess::{Base64Preprocessor, HexPreprocessor, MorsePreprocessor, Preprocessor};
+use crate::ranking::{self, BestGuess};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+pub const PLAINTEXT_LABEL: &str = "Plaintext";
+// Below this many letters no statistic or decoder has anything to work with.
+pub const MIN_ANALYSIS_LETTERS: usize = 2;
+
+// Morse, A1Z26, Polybius and hex input carry few or no letters, so their symbols count as content too.
+pub fn is_too_short(text: &str) -> bool {
+    let letters = text.chars().filter(|c| c.is_ascii_alphabetic()).count();
+    let symbols = text.chars().filter(|c| c.is_ascii_digit() || matches!(c, '.' | '-')).count();
+    letters < MIN_ANALYSIS_LETTERS && symbols < MIN_ANALYSIS_LETTERS
+}
+
+pub fn available_identifiers(config: &Config) -> Vec<Box<dyn Identifier>> {
+    let identifiers: Vec<Box<dyn Identifier>> = vec![
+        Box::new(CaesarIdentifier::new(config)),
+        Box::new(VigenereIdentifier::new(config)),
+    ];
+    identifiers.into_iter().filter(|identifier| config.cipher_enabled(identifier.name())).collect()
+}
+
+pub fn available_decoders(config: &Config) -> Vec<Box<dyn Decoder>> {
+    let decoders: Vec<Box<dyn Decoder>> = vec![
+        Box::new(CaesarDecoder::new(config)),
+        Box::new(KeyedCaesarDecoder::new(config)),
+        Box::new(VigenereDecoder::new(config)),
+        Box::new(GronsfeldDecoder::new(config)),
+        Box::new(MorseDecoder::new(config)),
+        Box::new(PolybiusDecoder::new(config)),
+        Box::new(A1Z26Decoder::new(config)),
+        Box::new(BaconDecoder::new(config)),
+        Box::new(SubstitutionDecoder::new(config)),
+        Box::new(XorDecoder::new(config)),
+    ];
+    decoders.into_iter().filter(|decoder| config.cipher_enabled(decoder.name())).collect()
+}
+
+// Every decoder and identifier name `Config::enabled_ciphers` can refer to, in registry order.
+pub fn cipher_names() -> Vec<&'static str> {
+    let config = Config::default();
+    let mut names: Vec<&'static str> = available_decoders(&config).iter().map(|decoder| decoder.name()).collect();
+    for identifier in available_identifiers(&config) {
+        if !names.contains(&identifier.name()) {
+            names.push(identifier.name());
+        }
+    }
+    names
+}
+
+// Hex is tried first: hex digits are valid Base64 too.
+pub fn available_preprocessors(config: &Config) -> Vec<Box<dyn Preprocessor>> {
+    if !config.detect_encodings {
+        return Vec::new();
+    }
+    vec![
+        Box::new(HexPreprocessor),
+        Box::new(Base64Preprocessor),
+        Box::new(MorsePreprocessor),
+    ]
+}
+
+// Guards against preprocessors that keep matching their own output.
+const MAX_PREPROCESSING_LAYERS: usize = 8;
+
+// Peels encoding layers until no preprocessor matches, returning the text and the names applied in order.
+pub fn preprocess(text: &str, preprocessors: &[Box<dyn Preprocessor>]) -> (String, Vec<String>) {
+    let mut text = text.to_string();
+    let mut chain = Vec::new();
+    while chain.len() < MAX_PREPROCESSING_LAYERS {
+        let Some(layer) = preprocessors.iter().find(|p| p.detect(&text)) else {
+            break;
+        };
+        text = layer.transform(&text);
+        chain.push(layer.name().to_string());
+    }
+    (text, chain)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AnalysisReport {
+    pub input: String,
+    // Set when the input was base64/hex/etc. and the decoded text was analyzed instead; layers are
+    // joined with " -> ".
+    pub encoding: Option<String>,
+    // Preprocessor names in the order they were applied.
+    pub preprocessing_chain: Vec<String>,
+    // What the identifiers and decoders saw: `input` normalized and with every encoding layer removed.
+    pub analyzed_text: String,
+    pub looks_like_plaintext: bool,
+    // True when the input was too short to analyze; everything else is then left empty.
+    pub too_short: bool,
+    pub identifications: Vec<IdentificationResult>,
+    pub decoder_results: Vec<(String, Option<DecryptionAttempt>)>,
+    // Every available decoder in registry order, with its full outcome or the reason it was skipped.
+    pub decoder_runs: Vec<DecoderRun>,
+    pub best_guess: Option<BestGuess>,
+    // Decoder name and flag for the first decoder result containing a CTF-style flag.
+    pub possible_flag: Option<(String, String)>,
+    // One "<method>: applied" or "<method>: skipped (<reason>)" line per analysis; filled in by `analyze`.
+    pub applied_methods: Vec<String>,
+    // 0..1 confidence that the best guess is a real crack; see `ranking::overall_confidence`.
+    pub overall_confidence: f64,
+    // Warning listing non-ASCII input characters, which analysis ignores; see `analysis::non_ascii_warning`.
+    pub non_ascii_warning: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DecoderRun {
+    pub name: String,
+    // How this decoder's attempts are ranked; follows the attempts for decoders that switch methods.
+    pub scoring_method: ScoringMethod,
+    pub skip_reason: Option<String>,
+    // None when the decoder was skipped.
+    pub outcome: Option<Arc<DecodeOutcome>>,
+}
+
+impl AnalysisReport {
+    // Input that already reads as English is reported as plaintext rather than a shift-0 cipher.
+    pub fn top_cipher(&self) -> Option<&str> {
+        if self.looks_like_plaintext {
+            return Some(PLAINTEXT_LABEL);
+        }
+        self.best_guess.as_ref().map(|guess| guess.cipher_name.as_str())
+    }
+}
+
+const TRIGRAM_MIN_LETTERS: usize = 3;
+
+fn method_status(method: &str, skip_reason: Option<String>) -> String {
+    match skip_reason {
+        None => format!("{}: applied", method),
+        Some(reason) => format!("{}: skipped ({})", method, reason),
+    }
+}
+
+fn letters_needed(needed: usize, got: usize) -> Option<String> {
+    (got < needed).then(|| format!("needs {} letters, got {}", needed, got))
+}
+
+const REVERSED_IDENTIFICATION_NOTE: &str = "ciphertext reversed";
+
+// Runs the identifier on the text and, when `try_reversed` is set, on its character-reversed form too,
+// keeping whichever result has the higher normalized confidence.
+pub fn identify_with_reversal(identifier: &dyn Identifier, text: &str, try_reversed: bool) -> Option<IdentificationResult> {
+    let forward = identifier.identify(text);
+    if !try_reversed {
+        return forward;
+    }
+
+    let reversed_text: String = text.chars().rev().collect();
+    let reversed = identifier.identify(&reversed_text).map(|mut result| {
+        result.parameters = Some(match result.parameters.take() {
+            Some(parameters) => format!("{}; {}", parameters, REVERSED_IDENTIFICATION_NOTE),
+            None => REVERSED_IDENTIFICATION_NOTE.to_string(),
+        });
+        result
+    });
+    match (forward, reversed) {
+        (Some(forward), Some(reversed)) if ranking::normalized_confidence(&reversed) > ranking::normalized_confidence(&forward) => {
+            Some(reversed)
+        }
+        (Some(forward), _) => Some(forward),
+        (None, reversed) => reversed,
+    }
+}
+
+// Identification plus every decoder's best attempt for one input, without printing anything.
+// Each statistic, identifier and decoder is recorded in `applied_methods` as it runs or is skipped;
+// Kasiski and IC periodicity only run inside the Vigenere identifier and decoder.
+pub fn analyze(input: &str, config: &Config) -> AnalysisReport {
+    analyze_with_cache(input, config, &mut AnalysisCache::new())
+}
+
+// `analyze`, reusing decoder outcomes from earlier passes over the same text.
+pub fn analyze_with_cache(input: &str, config: &Config, cache: &mut AnalysisCache) -> AnalysisReport {
+    let normalized = cipher_utils::normalize_input(input);
+    let (text, preprocessing_chain) = preprocess(&normalized, &available_preprocessors(config));
+    let encoding = (!preprocessing_chain.is_empty()).then(|| preprocessing_chain.join(" -> "));
+
+    let alpha_len = analysis::get_alphabetic_chars(&text).len();
+    let too_short = is_too_short(&text);
+    // Dot-dash input has no letter statistics for the identifiers; decoders check it via `skip_reason`.
+    let morse = analysis::looks_like_morse(&text);
+    let input_skip = || {
+        if too_short {
+            Some(format!("fewer than {} letters or symbols", MIN_ANALYSIS_LETTERS))
+        } else {
+            morse.then(|| "input looks like Morse".to_string())
+        }
+    };
+    let statistic_skip = |needed: usize| {
+        if too_short { input_skip() } else { letters_needed(needed, alpha_len) }
+    };
+    let vigenere_min = config.vigenere_min_id_len.min(config.vigenere_min_dec_len);
+    let mut applied_methods = vec![
+        method_status("Index of Coincidence", statistic_skip(MIN_ANALYSIS_LETTERS)),
+        method_status("Chi-squared", statistic_skip(1)),
+        method_status("Trigram scoring", statistic_skip(TRIGRAM_MIN_LETTERS)),
+        method_status("Kasiski examination", input_skip().or_else(|| letters_needed(vigenere_min, alpha_len))),
+        method_status("IC periodicity", input_skip().or_else(|| letters_needed(vigenere_min, alpha_len))),
+    ];
+
+    if too_short {
+        applied_methods.extend(available_identifiers(config).iter().map(|identifier| {
+            method_status(&format!("Identifier {}", identifier.name()), input_skip())
+        }));
+        applied_methods.extend(available_decoders(config).iter().map(|decoder| {
+            method_status(&format!("Decoder {}", decoder.name()), input_skip())
+        }));
+        return AnalysisReport {
+            input: input.to_string(),
+            encoding,
+            preprocessing_chain,
+            analyzed_text: text.clone(),
+            looks_like_plaintext: false,
+            too_short: true,
+            identifications: Vec::new(),
+            decoder_results: Vec::new(),
+            decoder_runs: Vec::new(),
+            best_guess: None,
+            possible_flag: None,
+            applied_methods,
+            overall_confidence: 0.0,
+            non_ascii_warning: analysis::non_ascii_warning(&text),
+        };
+    }
+
+    let mut identifications: Vec<IdentificationResult> = Vec::new();
+    for identifier in available_identifiers(config) {
+        let skip = input_skip().or_else(|| letters_needed(identifier.min_ciphertext_len(), alpha_len));
+        if skip.is_none() {
+            identifications.extend(identify_with_reversal(identifier.as_ref(), &text, config.try_reversed));
+        }
+        applied_methods.push(method_status(&format!("Identifier {}", identifier.name()), skip));
+    }
+
+    let mut all_attempts: Vec<(String, Vec<DecryptionAttempt>)> = Vec::new();
+    let mut decoder_runs: Vec<DecoderRun> = Vec::new();
+    for decoder in available_decoders(config) {
+        let skip = decoder.skip_reason(&text).or_else(|| letters_needed(decoder.min_ciphertext_len(), alpha_len));
+        let outcome = skip.is_none().then(|| cache.decode(decoder.as_ref(), &text, config));
+        let attempts = match outcome.as_deref() {
+            Some(DecodeOutcome::Results(attempts)) => attempts.clone(),
+            _ => Vec::new(),
+        };
+        applied_methods.push(method_status(&format!("Decoder {}", decoder.name()), skip.clone()));
+        decoder_runs.push(DecoderRun {
+            name: decoder.name().to_string(),
+            scoring_method: decoder.scoring_method_for(&attempts),
+            skip_reason: skip,
+            outcome,
+        });
+        all_attempts.push((decoder.name().to_string(), attempts));
+    }
+    let decoder_results: Vec<(String, Option<DecryptionAttempt>)> = all_attempts
+        .iter()
+        .map(|(name, attempts)| (name.clone(), attempts.first().cloned()))
+        .collect();
+
+    let best_guess = ranking::promote_crib_match(&identifications, &all_attempts, &config.known_plaintext_cribs)
+        .or_else(|| ranking::best_overall_guess_with_threshold(&identifications, &decoder_results, config.caesar_preference_chi2));
+    let overall_confidence = ranking::overall_confidence(best_guess.as_ref());
+    let possible_flag = decoder_results.iter().find_map(|(name, attempt)| {
+        let flag = analysis::contains_flag_pattern(&attempt.as_ref()?.plaintext)?;
+        Some((name.clone(), flag))
+    });
+
+    AnalysisReport {
+        input: input.to_string(),
+        encoding,
+        preprocessing_chain,
+        looks_like_plaintext: analysis::looks_like_plaintext(&text),
+        too_short: false,
+        identifications,
+        decoder_results,
+        decoder_runs,
+        best_guess,
+        possible_flag,
+        applied_methods,
+        overall_confidence,
+        non_ascii_warning: analysis::non_ascii_warning(&text),
+        analyzed_text: text,
+    }
+}
+
+// Plaintext of the overall best guess, for callers that only want an answer.
+pub fn best_plaintext(ciphertext: &str, config: &Config) -> Option<String> {
+    analyze(ciphertext, config).best_guess.map(|guess| guess.attempt.plaintext)
+}
+
+// Config-independent statistics of the raw ciphertext.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RawStats {
+    pub ic: Option<f64>,
+    pub chi_squared: Option<f64>,
+    pub doubled_letter_ratio: Option<f64>,
+    pub looks_like_plaintext: bool,
+    pub randomness: analysis::RandomnessLevel,
+}
+
+// Memoizes work between analysis passes over the same ciphertext, so a pass that only changes the
+// Vigenere settings reruns just the decoders built from them. Switching text clears the cache.
+#[derive(Default)]
+pub struct AnalysisCache {
+    text: String,
+    raw_stats: Option<Arc<RawStats>>,
+    outcomes: HashMap<(String, String, bool), Arc<DecodeOutcome>>,
+}
+
+impl AnalysisCache {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    fn switch_to(&mut self, text: &str) {
+        if self.text != text {
+            *self = AnalysisCache { text: text.to_string(), ..Default::default() };
+        }
+    }
+
+    pub fn raw_stats(&mut self, text: &str) -> Arc<RawStats> {
+        self.switch_to(text);
+        self.raw_stats
+            .get_or_insert_with(|| {
+                Arc::new(RawStats {
+                    ic: analysis::calculate_ic(text),
+                    chi_squared: analysis::score_english_likelihood(text),
+                    doubled_letter_ratio: analysis::doubled_letter_ratio(text),
+                    looks_like_plaintext: analysis::looks_like_plaintext(text),
+                    randomness: analysis::randomness_indicator(text),
+                })
+            })
+            .clone()
+    }
+
+    // `decrypt_outcome_with_reversal`, reusing the previous outcome while the decoder's
+    // `cache_key` and `try_reversed` are unchanged.
+    pub fn decode(&mut self, decoder: &dyn Decoder, text: &str, config: &Config) -> Arc<DecodeOutcome> {
+        self.switch_to(text);
+        let run = || Arc::new(decrypt_outcome_with_reversal(decoder, text, config.try_reversed));
+        let Some(settings) = decoder.cache_key() else {
+            return run();
+        };
+
+        self.outcomes
+            .entry((decoder.name().to_string(), settings, config.try_reversed))
+            .or_insert_with(run)
+            .clone()
+    }
+}
+
+fn explain_ic(ic: f64) -> String {
+    let implication = if ic >= analysis::ENGLISH_IC - 0.01 {
+        "close to English, so letter frequencies survived: a monoalphabetic cipher or transposition"
+    } else if ic <= analysis::RANDOM_IC + 0.005 {
+        "close to random: a long-key polyalphabetic cipher, or not a letter cipher at all"
+    } else {
+        "between random and English: likely a short-key polyalphabetic cipher"
+    };
+    format!(
+        "Ciphertext IC is {:.4} (English ~{:.4}, random ~{:.4}), {}.",
+        ic, analysis::ENGLISH_IC, analysis::RANDOM_IC, implication
+    )
+}
+
+// Short derivation of how `attempt` was reached, one sentence per line, for learners.
+pub fn explain_attempt(text: &str, attempt: &DecryptionAttempt, method: ScoringMethod, config: &Config) -> Vec<String> {
+    let mut lines = Vec::new();
+    if let Some(ic) = analysis::calculate_ic(text) {
+        lines.push(explain_ic(ic));
+    }
+
+    match attempt.cipher_name.as_str() {
+        "Caesar" if method == ScoringMethod::ChiSquared => lines.push(format!(
+            "Tried all 26 shifts; shift {} has the lowest chi-squared score against English letter frequencies ({:.4}).",
+            attempt.key, attempt.score
+        )),
+        "Caesar" => lines.push(format!("Tried all 26 shifts; shift {} scored best.", attempt.key)),
+        "Vigenere" if attempt.key.chars().all(|c| c.is_ascii_alphabetic()) => {
+            lines.extend(VigenereDecoder::new(config).explain_key(text, &attempt.key));
+        }
+        _ => lines.push(format!("{} key {} scored best.", attempt.cipher_name, attempt.key)),
+    }
+
+    lines.push(format!("Final {}: {:.4} ({} is better).", method.label(), attempt.score,
+        if method.higher_is_better() { "higher" } else { "lower" }));
+    lines
+}
+
+#[cfg(not(feature = "parallel"))]
+pub fn analyze_batch(inputs: &[&str], config: &Config) -> Vec<AnalysisReport> {
+    inputs.iter().map(|input| analyze(input, config)).collect()
+}
+
+// Inputs are independent, so the batch can be spread across threads; report order matches input order.
+#[cfg(feature = "parallel")]
+pub fn analyze_batch(inputs: &[&str], config: &Config) -> Vec<AnalysisReport> {
+    use rayon::prelude::*;
+    inputs.par_iter().map(|input| analyze(input, config)).collect()
+}
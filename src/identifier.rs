@@ -1,10 +1,44 @@
+// Typed counterpart of `IdentificationResult::parameters`, for callers that need the numbers.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub enum CipherParams {
+    Caesar {
+        shift: u8,
+    },
+    Vigenere {
+        // Kasiski tops first, then IC-periodicity tops, without duplicates.
+        candidate_key_lengths: Vec<usize>,
+        consensus_key_length: Option<usize>,
+        ic: f64,
+        // Set when the text scores better as Beaufort (C = K - P) at the likely key length.
+        beaufort: bool,
+    },
+    #[default]
+    Other,
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct IdentificationResult {
     pub cipher_name: String,
     pub confidence_score: f64,
     pub parameters: Option<String>,
+    pub params: CipherParams,
 }
 
 pub trait Identifier {
     fn identify(&self, ciphertext: &str) -> Option<IdentificationResult>;
+    fn name(&self) -> &'static str;
+
+    // One-line explanation of what this identifier looks for, for display.
+    fn description(&self) -> &'static str {
+        ""
+    }
+
+    // Alphabetic characters needed before this identifier does any analysis.
+    fn min_ciphertext_len(&self) -> usize {
+        0
+    }
+
+    fn identify_all(&self, ciphertext: &str) -> Vec<IdentificationResult> {
+        self.identify(ciphertext).into_iter().collect()
+    }
 }
\ No newline at end of file
@@ -6,16 +6,29 @@ pub mod cipher_utils;
 pub mod ciphers;
 pub mod config;
 pub mod decoder;
+pub mod engine;
 pub mod identifier;
+pub mod output;
+pub mod pipeline;
+pub mod preprocess;
+pub mod ranking;
 pub mod text_stats;
 
 // Re-export items needed by main.rs and tests
 pub use config::Config;
 pub use decoder::{DecryptionAttempt, Decoder};
-pub use identifier::{IdentificationResult, Identifier};
+pub use identifier::{CipherParams, IdentificationResult, Identifier};
 // Add pub use for specific cipher structs if needed directly by main/tests
+pub use ciphers::a1z26::A1Z26Decoder;
+pub use ciphers::bacon::BaconDecoder;
 pub use ciphers::caesar::{CaesarDecoder, CaesarIdentifier};
+pub use ciphers::gronsfeld::GronsfeldDecoder;
+pub use ciphers::keyed_caesar::KeyedCaesarDecoder;
+pub use ciphers::morse::MorseDecoder;
+pub use ciphers::polybius::PolybiusDecoder;
+pub use ciphers::substitution::SubstitutionDecoder;
 pub use ciphers::vigenere::{VigenereDecoder, VigenereIdentifier};
+pub use ciphers::xor::XorDecoder;
 // Add pub use for analysis functions needed by tests
 // (Alternatively, tests can use peekaboo::analysis::function_name)
 
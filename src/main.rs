@@ -1,15 +1,15 @@
+use std::env;
 use std::io::{self, Write};
 use std::process;
 
 use peekaboo::{
     analysis, // Import the analysis module directly
+    cipher_utils,
     config::Config,
-    decoder::{DecryptionAttempt, Decoder},
-    identifier::{IdentificationResult, Identifier},
-    ciphers::{
-        caesar::{CaesarDecoder, CaesarIdentifier},
-        vigenere::{VigenereDecoder, VigenereIdentifier},
-    },
+    decoder::{DecodeOutcome, ScoringMethod},
+    output::{self, OutputOptions},
+    ranking,
+    engine,
     text_stats,
 };
 
@@ -37,40 +37,59 @@ fn read_usize_input(prompt: &str, default: usize) -> usize {
     }
 }
 
-fn run_analysis_pass(
-    config: &Config,
-    ciphertext: &str,
-    first_run: bool
-) -> (Vec<IdentificationResult>, Vec<(String, Option<DecryptionAttempt>)>) {
-    let ciphertext_len = ciphertext.chars().filter(|c| c.is_ascii_alphabetic()).count();
+// Reads lines until a blank line or end of input, so multi-line ciphertext can be pasted.
+fn read_ciphertext() -> String {
+    let mut ciphertext = String::new();
+    for line in io::stdin().lines() {
+        let line = line.expect("Failed to read line");
+        if line.trim().is_empty() {
+            break;
+        }
+        if !ciphertext.is_empty() {
+            ciphertext.push('\n');
+        }
+        ciphertext.push_str(&line);
+    }
+    cipher_utils::normalize_input(&ciphertext)
+}
 
+fn score_description(method: ScoringMethod) -> String {
+    let direction = if method.higher_is_better() { "Higher is better" } else { "Lower is better" };
+    format!("({} - {})", direction, method.label())
+}
 
-    let available_identifiers: Vec<Box<dyn Identifier>> = vec![
-        Box::new(CaesarIdentifier::new(config)),
-        Box::new(VigenereIdentifier::new(config)),
-    ];
-    let available_decoders: Vec<Box<dyn Decoder>> = vec![
-        Box::new(CaesarDecoder::new(config)),
-        Box::new(VigenereDecoder::new(config)),
-    ];
+fn print_analysis_pass(
+    config: &Config,
+    options: &OutputOptions,
+    report: &engine::AnalysisReport,
+    first_run: bool,
+    cache: &mut engine::AnalysisCache,
+) {
+    let ciphertext = report.analyzed_text.as_str();
 
     println!("\n--- Identifying Cipher ---");
-    println!("(Note: Statistical methods effectiveness depends on text length and settings)");
+    if !options.quiet {
+        println!("(Note: Statistical methods effectiveness depends on text length and settings)");
+    }
 
     // --- Raw Ciphertext Analysis ---
     println!("\n--- Raw Ciphertext Analysis ---");
-    let ic_option = analysis::calculate_ic(ciphertext);
-    let chi2_option = analysis::score_english_likelihood(ciphertext);
+    let raw_stats = cache.raw_stats(ciphertext);
+    let ic_option = raw_stats.ic;
+    let chi2_option = raw_stats.chi_squared;
 
     // Report IC
     if let Some(ic) = ic_option {
         println!("  -> Raw Ciphertext Index of Coincidence (IC): {:.4}", ic);
-        if ic < (analysis::RANDOM_IC + 0.005) { // Close to random
-            println!("     (IC is low, suggests Polyalphabetic Cipher like Vigenere)");
+        let ic_hint = if ic < (analysis::RANDOM_IC + 0.005) { // Close to random
+            "IC is low, suggests Polyalphabetic Cipher like Vigenere"
         } else if ic > (analysis::ENGLISH_IC - 0.01) { // Close to English
-            println!("     (IC is high, similar to English, suggests Substitution or Transposition)");
+            "IC is high, similar to English, suggests Substitution or Transposition"
         } else {
-            println!("     (IC is intermediate)");
+            "IC is intermediate"
+        };
+        if !options.quiet {
+            println!("     ({})", ic_hint);
         }
     } else {
         println!("  -> Raw Ciphertext Index of Coincidence (IC): Could not calculate");
@@ -79,10 +98,12 @@ fn run_analysis_pass(
     // Report Chi-Squared
     if let Some(chi2_score) = chi2_option {
         println!("  -> Raw Ciphertext Chi-Squared Score: {:.4} (vs English)", chi2_score);
-        if chi2_score < 3.0 { // Significantly adjusted threshold based on testing
-            println!("     (Score < 3.0 suggests frequencies are close to English - possible Transposition Cipher)");
-        } else {
-            println!("     (Score suggests frequencies differ from English - likely Substitution/Polyalphabetic)");
+        if !options.quiet {
+            if chi2_score < config.transposition_chi2_max {
+                println!("     (Score < {} suggests frequencies are close to English - possible Transposition Cipher)", config.transposition_chi2_max);
+            } else {
+                println!("     (Score suggests frequencies differ from English - likely Substitution/Polyalphabetic)");
+            }
         }
     } else {
         println!("  -> Raw Ciphertext Chi-Squared Score: Could not calculate");
@@ -90,67 +111,68 @@ fn run_analysis_pass(
 
     // Check for Monoalphabetic Substitution Signature
     if let (Some(ic), Some(chi2_score)) = (ic_option, chi2_option) {
-        // Thresholds might need tuning
-        const MONO_IC_LOWER_THRESHOLD: f64 = 0.058; // Slightly below English
-        const MONO_CHI2_LOWER_THRESHOLD: f64 = 5.0; // Reasonably different from English
-
-        if ic > MONO_IC_LOWER_THRESHOLD && chi2_score > MONO_CHI2_LOWER_THRESHOLD {
+        if analysis::is_monoalphabetic_signature(ic, chi2_score, config) {
             println!("  -> Combined Check: High IC + High Chi2 -> **Possible Monoalphabetic Substitution**");
         }
     }
+
+    if let Some(ratio) = raw_stats.doubled_letter_ratio {
+        println!("  -> Doubled Letter Ratio: {:.4}", ratio);
+        if !options.quiet {
+            println!("     (English and monoalphabetic ciphers keep doubled letters; near 0 on long text suggests Playfair)");
+        }
+    }
+    if raw_stats.looks_like_plaintext {
+        println!("  -> Input appears to already be English plaintext (English-like frequencies, trigrams and IC).");
+    }
+    match raw_stats.randomness {
+        analysis::RandomnessLevel::Likely => {
+            println!("  -> Randomness: LIKELY (flat frequencies, random IC, no repeated trigrams).");
+            println!("     This may be a one-time pad or random data, which cannot be broken statistically.");
+        }
+        analysis::RandomnessLevel::Possible => {
+            println!("  -> Randomness: POSSIBLE (some random-looking statistics; could be a long-key polyalphabetic cipher).");
+        }
+        analysis::RandomnessLevel::Unlikely => {}
+    }
     println!("--- End Raw Analysis ---");
     // --- End Raw Ciphertext Analysis ---
 
 
-    let mut identification_results: Vec<IdentificationResult> = Vec::new();
-    let mut skipped_identifiers = 0;
-
-    for id_tool in &available_identifiers {
-        if let Some(result) = id_tool.identify(ciphertext) {
-
-            let score_context = match result.cipher_name.as_str() {
-                "Caesar" => "(Lower is better)",
-                "Vigenere" => "(Higher is better)",
-                _ => "",
-            };
-            println!(
-                "  -> Identifier [{}] suggests: {} Score: {:.4} {} | Params: {}",
-                result.cipher_name,
-                result.cipher_name,
-                result.confidence_score,
-                score_context,
-                result.parameters.as_deref().unwrap_or("N/A")
-            );
-            identification_results.push(result);
-        } else {
-            skipped_identifiers += 1;
-        }
+    // Identifiers that ran are in `identifications`; the rest say why they were skipped.
+    for status in report.applied_methods.iter().filter(|status| status.starts_with("Identifier ") && status.contains(": skipped")) {
+        println!("  (Note: {})", status);
     }
-
-    if skipped_identifiers > 0 {
-        println!("  (Note: {} identifier(s) might have skipped analysis due to text length below configured minimums).", skipped_identifiers);
+    for result in &report.identifications {
+        let score_context = match result.cipher_name.as_str() {
+            "Caesar" => "(Lower is better)",
+            "Vigenere" => "(Higher is better)",
+            _ => "",
+        };
+        println!(
+            "  -> Identifier [{}] suggests: {} Score: {:.4} {} | Params: {}",
+            result.cipher_name,
+            result.cipher_name,
+            result.confidence_score,
+            score_context,
+            result.parameters.as_deref().unwrap_or("N/A")
+        );
     }
 
 
-    if identification_results.is_empty() {
+    if report.identifications.is_empty() {
         println!("Could not identify a likely cipher type based on available identifiers.");
         if first_run {
-            println!("Statistical analysis might require longer ciphertext or adjusted settings.");
+            match analysis::sufficiency(ciphertext, config).advice() {
+                Some(advice) => println!("{}", advice),
+                None => println!("Statistical analysis might require adjusted settings."),
+            }
         }
     } else {
 
-        let best_guess = identification_results.iter().min_by(|a, b| {
-            // Use the corrected Vigenere confidence score (higher is better)
-            let score_a = match a.cipher_name.as_str() {
-                "Caesar" => 1.0 / (1.0 + a.confidence_score.max(0.0)), // Normalize Chi2
-                "Vigenere" => a.confidence_score, // Already 0-1, higher is better
-                _ => 0.0,
-            };
-            let score_b = match b.cipher_name.as_str() {
-                "Caesar" => 1.0 / (1.0 + b.confidence_score.max(0.0)), // Normalize Chi2
-                "Vigenere" => b.confidence_score, // Already 0-1, higher is better
-                _ => 0.0,
-            };
+        let best_guess = report.identifications.iter().min_by(|a, b| {
+            let score_a = ranking::normalized_confidence(a);
+            let score_b = ranking::normalized_confidence(b);
             score_b.partial_cmp(&score_a).unwrap_or(std::cmp::Ordering::Equal) // Higher normalized score is better
         });
 
@@ -167,98 +189,108 @@ fn run_analysis_pass(
 
     println!("\n--- Attempting Decryption ---");
 
-    let mut top_results: Vec<(String, Option<DecryptionAttempt>)> = Vec::with_capacity(available_decoders.len());
-
+    for run in &report.decoder_runs {
+        println!("\n--- Trying Decoder: {} ---", run.name);
 
-    for decoder in &available_decoders {
-        let decoder_name = decoder.name();
-        println!("\n--- Trying Decoder: {} ---", decoder_name);
-
-        let min_len_required = match decoder_name {
-            "Vigenere" => config.vigenere_min_dec_len,
-            _ => 0
-        };
-
-        if ciphertext_len < min_len_required {
-            println!("Skipping {}: Ciphertext alphabetic length ({}) is less than required minimum ({}).",
-                     decoder_name, ciphertext_len, min_len_required);
-            if first_run {
+        if let Some(reason) = &run.skip_reason {
+            println!("Skipping {}: {}.", run.name, reason);
+            if first_run && !options.quiet && reason.starts_with("needs ") {
                 println!("If analysis fails, you'll be offered a chance to lower this setting.");
             }
-            top_results.push((decoder_name.to_string(), None));
             continue;
         }
 
-
-        let decryption_attempts = decoder.decrypt(ciphertext);
-
-        if decryption_attempts.is_empty() {
-            println!("No successful decryption found for {}.", decoder_name);
-            if decoder_name == "Vigenere" {
-                println!("Common reasons include: Incorrect key length determined by Kasiski analysis,");
-                println!("or columns too short for MIC analysis, or no candidate key produced valid plaintext.");
+        let decryption_attempts = match run.outcome.as_deref() {
+            Some(DecodeOutcome::Results(attempts)) => attempts.as_slice(),
+            outcome => {
+                println!("No successful decryption found for {}.", run.name);
+                match outcome {
+                    Some(DecodeOutcome::TooShort { needed, got }) => {
+                        println!("Reason: Ciphertext has {} alphabetic characters; at least {} are required.", got, needed);
+                    }
+                    Some(DecodeOutcome::ColumnsTooShort { key_len }) => {
+                        println!("Reason: Columns for key length {} are too short for MIC analysis.", key_len);
+                        println!("Lowering the 'Minimum Letters per Column for MIC' setting may help.");
+                    }
+                    _ => {
+                        println!("Reason: No candidate key produced a usable plaintext.");
+                    }
+                }
+                continue;
             }
-            top_results.push((decoder_name.to_string(), None));
-        } else {
+        };
 
-            top_results.push((decoder_name.to_string(), decryption_attempts.first().cloned()));
+        let score_desc = score_description(run.scoring_method);
+        println!("Top {} Decryption Results {}:", run.name, score_desc);
 
-            let score_desc = if decoder_name == "Vigenere" {
-                "(Higher is better - Trigram Score)"
-            } else {
-                "(Lower is better - Chi^2 Score)"
-            };
-            println!("Top {} Decryption Results {}:", decoder_name, score_desc);
-
-
-            for attempt in decryption_attempts.iter().take(10) {
-                let plaintext_preview = attempt.plaintext.chars().take(70).collect::<String>();
-                let ellipsis = if attempt.plaintext.chars().count() > 70 { "..." } else { "" };
-                let key_preview = attempt.key.chars().take(10).collect::<String>()
-                    + if attempt.key.chars().count() > 10 { "..." } else { "" };
-
-
-                let score_str = if decoder_name == "Vigenere" {
-                    format!("{:<8.2}", attempt.score)
-                } else {
-                    format!("{:<8.4}", attempt.score)
-                };
-
-                println!(
-                    "  Key: {:<10} | Score: {} | Plaintext: \"{}{}\"",
-                    key_preview,
-                    score_str,
-                    plaintext_preview,
-                    ellipsis
-                );
+
+        for (rank, attempt) in decryption_attempts.iter().take(options.top_n).enumerate() {
+            // With --full, only the top result is printed untruncated to keep the list readable.
+            let plaintext_width = if options.full_plaintext && rank == 0 { usize::MAX } else { options.preview_width };
+            println!("  {}", output::format_attempt_preview(attempt, plaintext_width, options.key_width, run.scoring_method));
+            if let Some(note) = &attempt.note {
+                println!("    Note: {}", note);
             }
-            if decryption_attempts.len() > 10 {
-                println!("  ... (more results available for {})", decoder_name);
+            if let Some(flag) = analysis::contains_flag_pattern(&attempt.plaintext) {
+                println!("    Possible flag found: {}", flag);
             }
         }
+        if decryption_attempts.len() > options.top_n {
+            println!("  ... (more results available for {})", run.name);
+        }
     }
 
 
-    let actually_decrypted = top_results.iter().any(|(_, r)| r.is_some());
+    let actually_decrypted = report.decoder_results.iter().any(|(_, r)| r.is_some());
     if !actually_decrypted {
         println!("\nNo usable decryptions found by any available decoder during this pass.");
         if first_run {
-            println!("Consider providing longer ciphertext.");
+            if let Some(advice) = analysis::sufficiency(ciphertext, config).advice() {
+                println!("{}", advice);
+            }
         }
     }
 
-    (identification_results, top_results)
+}
+
+
+// Decoder progress goes through the `log` facade; RUST_LOG overrides the default level.
+fn init_logging(quiet: bool) {
+    let default_level = if quiet { "warn" } else { "info" };
+    env_logger::Builder::from_env(env_logger::Env::default().default_filter_or(default_level))
+        .target(env_logger::Target::Stdout)
+        .format(|buf, record| writeln!(buf, "{}: {}", record.level(), record.args()))
+        .init();
 }
 
 
 fn main() {
+    let options = match output::parse_args(env::args().skip(1)) {
+        Ok(options) => options,
+        Err(message) => {
+            eprintln!("{}", message);
+            eprintln!("Usage: peekaboo [--top N] [--full] [--preview-width N] [--key-width N] [--quiet] [--reversed] [--explain] [--max-key-combinations N] [--cipher NAME]...");
+            process::exit(2);
+        }
+    };
+    let mut config = Config {
+        try_reversed: options.try_reversed,
+        max_key_combinations: options.max_key_combinations,
+        enabled_ciphers: options.enabled_ciphers.clone(),
+        ..Default::default()
+    };
+    if let Err(error) = config.validate() {
+        eprintln!("Invalid settings: {}", error);
+        process::exit(2);
+    }
+    init_logging(options.quiet);
+
     println!("--- Crypto Decoder Tool ---");
     println!("Current Date: April 21, 2025");
 
-    print!("\nEnter ciphertext: ");
+    print!("\nEnter ciphertext (finish with an empty line): ");
     io::stdout().flush().unwrap();
-    let mut ciphertext = String::new();
-    io::stdin().read_line(&mut ciphertext).expect("Failed to read line");
+    let ciphertext = read_ciphertext();
     let ciphertext = ciphertext.trim();
 
     if ciphertext.is_empty() {
@@ -267,10 +299,27 @@ fn main() {
     }
     let alpha_len = ciphertext.chars().filter(|c| c.is_ascii_alphabetic()).count();
     println!("\nReceived Ciphertext (Alphabetic Length: {}): \"{}\"", alpha_len, ciphertext);
+    if let Some(warning) = analysis::non_ascii_warning(ciphertext) {
+        println!("Warning: {}.", warning);
+    }
 
+    let mut cache = engine::AnalysisCache::new();
+    let mut report = engine::analyze_with_cache(ciphertext, &config, &mut cache);
+    if let Some(encoding) = &report.encoding {
+        println!("\n--- Encoding Pre-Stage ---");
+        println!("Input appears to be {}-encoded. Analyzing decoded text instead:", encoding);
+        println!("\"{}\"", report.analyzed_text);
+    }
 
+    if report.too_short {
+        println!("Ciphertext has fewer than {} letters or cipher symbols; there is nothing to analyze. Exiting.", engine::MIN_ANALYSIS_LETTERS);
+        process::exit(1);
+    }
+
+    // Later passes only change settings, so every pass analyzes this same decoded text.
+    let text = report.analyzed_text.clone();
     println!("\n--- Basic Text Statistics ---");
-    if let Some(stats) = text_stats::calculate_basic_stats(ciphertext) {
+    if let Some(stats) = text_stats::calculate_basic_stats(&text) {
         println!("Total Characters: {}", stats.char_count_total);
         println!("Alphabetic Characters: {}", stats.char_count_alpha);
         println!("Word Count: {}", stats.word_count);
@@ -278,9 +327,24 @@ fn main() {
             println!("Min Word Length: {}", stats.min_word_length);
             println!("Max Word Length: {}", stats.max_word_length);
             println!("Average Word Length: {:.2}", stats.average_word_length);
+            if let Some(size) = text_stats::uniform_group_size(&text) {
+                println!("Grouped in blocks of {}; spacing is likely not word boundaries.", size);
+            } else if text_stats::has_word_structure(&text) {
+                println!("Word boundaries appear preserved.");
+            }
+        }
+        if let Some((repeat, starts)) = text_stats::longest_repeated_substring(&text) {
+            println!("Longest Repeated Substring: {} (letter offsets {:?})", repeat, starts);
+        }
+        let repeated_words = text_stats::repeated_words(&text, 2);
+        if !repeated_words.is_empty() {
+            let listed: Vec<String> = repeated_words.iter().take(5).map(|(word, count)| format!("{} x{}", word, count)).collect();
+            println!("Repeated Words: {}", listed.join(", "));
         }
         if stats.char_count_alpha > 0 {
             println!("Uppercase / Lowercase: {:.1}% / {:.1}%", stats.uppercase_percent, stats.lowercase_percent);
+            println!("Distinct Letters: {} (Repetition Ratio: {:.2})", stats.distinct_alpha_letters, stats.repetition_ratio);
+            println!("Vowels / Consonants: {} / {} (Vowel Ratio: {:.2}, English ~0.38)", stats.vowel_count, stats.consonant_count, stats.vowel_ratio);
         }
         println!("Numeric Chars: {}", stats.char_count_numeric);
         println!("Whitespace Chars: {}", stats.char_count_whitespace);
@@ -292,10 +356,9 @@ fn main() {
 
 
 
-    let mut config = Config::default();
     let mut first_run = true;
 
-    let final_results: (Vec<IdentificationResult>, Vec<(String, Option<DecryptionAttempt>)>);
+    let final_report: engine::AnalysisReport;
 
 
     loop {
@@ -303,11 +366,11 @@ fn main() {
         println!("\n--- Running Analysis Pass ({}) ---", pass_name);
 
 
-        let (id_results, top_dec_results) = run_analysis_pass(&config, &ciphertext, first_run);
+        print_analysis_pass(&config, &options, &report, first_run, &mut cache);
 
 
-        let identified = !id_results.is_empty();
-        let decrypted = top_dec_results.iter().any(|(_, r)| r.is_some());
+        let identified = !report.identifications.is_empty();
+        let decrypted = report.decoder_results.iter().any(|(_, r)| r.is_some());
 
 
         if first_run && !(identified || decrypted) {
@@ -319,142 +382,147 @@ fn main() {
             let mut choice = String::new();
             if io::stdin().read_line(&mut choice).is_err() {
                 println!("Error reading input. Exiting.");
-                final_results = (id_results, top_dec_results);
+                final_report = report;
                 break;
             }
 
             if choice.trim().to_lowercase().starts_with('y') {
 
+                let previous_config = config.clone();
+
                 println!("\n--- Custom Configuration ---");
-                println!("You can adjust settings affecting analysis.");
-                println!("Press Enter at the prompt to accept the default value shown in [brackets].");
-
-                println!("\n[Vigenere Identification Minimum Length]");
-                println!(" - What it is: The shortest ciphertext length (alphabetic characters only)");
-                println!("   for which the program will attempt Vigenere IDENTIFICATION using");
-                println!("   statistical methods (Index of Coincidence, Kasiski Examination).");
-                println!(" - Why it matters: These methods need enough data to be reliable.");
-                println!("   Analyzing very short texts statistically often gives misleading results.");
-                println!(" - Implications: Setting this too low (e.g., below 25-30) may lead");
-                println!("   to incorrect identification or errors. Setting it higher requires");
-                println!("   longer ciphertexts but gives more reliable identification.");
+                if !options.quiet {
+                    println!("You can adjust settings affecting analysis.");
+                    println!("Press Enter at the prompt to accept the default value shown in [brackets].");
+                }
+
+                println!("{}", output::setting_help("Vigenere Identification Minimum Length", &[
+                    " - What it is: The shortest ciphertext length (alphabetic characters only)",
+                    "   for which the program will attempt Vigenere IDENTIFICATION using",
+                    "   statistical methods (Index of Coincidence, Kasiski Examination).",
+                    " - Why it matters: These methods need enough data to be reliable.",
+                    "   Analyzing very short texts statistically often gives misleading results.",
+                    " - Implications: Setting this too low (e.g., below 25-30) may lead",
+                    "   to incorrect identification or errors. Setting it higher requires",
+                    "   longer ciphertexts but gives more reliable identification.",
+                ], options.quiet));
                 config.vigenere_min_id_len = read_usize_input(
                     &format!("Enter minimum length for Vigenere ID [{}]: ", config.vigenere_min_id_len),
                     config.vigenere_min_id_len
                 );
 
-                println!("\n[Vigenere Decryption Minimum Length]");
-                println!(" - What it is: The shortest ciphertext length (alphabetic characters only)");
-                println!("   for which the program will attempt Vigenere DECRYPTION by trying");
-                println!("   to automatically determine the key length and keyword.");
-                println!(" - Why it matters: Finding the key length (Kasiski) and determining");
-                println!("   the key letters (column frequency analysis) require sufficient text.");
-                println!(" - Implications: Setting this too low (e.g., below 20-25) will likely");
-                println!("   fail to find the correct key/plaintext for short texts. If you have");
-                println!("   a very short text you want to try anyway (e.g., for testing), you");
-                println!("   can lower this, but expect unreliable results.");
+                println!("{}", output::setting_help("Vigenere Decryption Minimum Length", &[
+                    " - What it is: The shortest ciphertext length (alphabetic characters only)",
+                    "   for which the program will attempt Vigenere DECRYPTION by trying",
+                    "   to automatically determine the key length and keyword.",
+                    " - Why it matters: Finding the key length (Kasiski) and determining",
+                    "   the key letters (column frequency analysis) require sufficient text.",
+                    " - Implications: Setting this too low (e.g., below 20-25) will likely",
+                    "   fail to find the correct key/plaintext for short texts. If you have",
+                    "   a very short text you want to try anyway (e.g., for testing), you",
+                    "   can lower this, but expect unreliable results.",
+                ], options.quiet));
                 config.vigenere_min_dec_len = read_usize_input(
                     &format!("Enter minimum length for Vigenere Decryption [{}]: ", config.vigenere_min_dec_len),
                     config.vigenere_min_dec_len
                 );
 
+                println!("{}", output::setting_help("Minimum Letters per Column for MIC", &[
+                    " - What it is: The fewest letters a single key-position column must",
+                    "   contain before its shift is estimated with the Mutual Index of",
+                    "   Coincidence (MIC) during Vigenere/Gronsfeld decryption.",
+                    " - Why it matters: Very short columns have unreliable letter frequencies,",
+                    "   so their best shift is often wrong.",
+                    " - Implications: Lowering this (e.g., to 3) lets very short ciphertexts",
+                    "   be attempted at all, but expect unreliable keys.",
+                ], options.quiet));
+                config.min_chars_for_mic = read_usize_input(
+                    &format!("Enter minimum letters per column for MIC [{}]: ", config.min_chars_for_mic),
+                    config.min_chars_for_mic
+                );
+
+                println!("{}", output::setting_help("Bigram Scoring Length", &[
+                    " - What it is: Texts with fewer letters than this are ranked with",
+                    "   letter-pair (bigram) statistics instead of trigrams or Chi-Squared.",
+                    " - Why it matters: Very short texts contain too few trigrams for",
+                    "   reliable scores; bigrams give a steadier signal.",
+                    " - Implications: 0 disables bigram scoring. Values around 15-20 help",
+                    "   with short Caesar/Vigenere fragments.",
+                ], options.quiet));
+                config.bigram_scoring_max_len = read_usize_input(
+                    &format!("Enter bigram scoring length [{}]: ", config.bigram_scoring_max_len),
+                    config.bigram_scoring_max_len
+                );
+
+                if let Err(err) = config.validate() {
+                    println!("Invalid setting: {}. Keeping the previous configuration.", err);
+                    config = previous_config;
+                }
+
                 println!("Configuration updated. Re-running analysis...");
+                report = engine::analyze_with_cache(ciphertext, &config, &mut cache);
                 first_run = false;
 
             } else {
                 println!("Exiting without trying custom settings.");
-                final_results = (id_results, top_dec_results);
+                final_report = report;
                 break;
             }
         } else {
 
             println!("\nAnalysis pass complete.");
-            final_results = (id_results, top_dec_results);
+            final_report = report;
             break;
         }
 
     }
 
 
-    let (final_id_results, final_top_dec_results) = final_results;
 
 
     // --- Determine and Print Overall Best Guess ---
 
-    const CAESAR_CHI2_PREFERENCE_THRESHOLD: f64 = 3.0;
-
-    let mut best_overall_decoder_index: Option<usize> = None;
-    let mut highest_normalized_confidence = -1.0;
-    let mut preferred_caesar_index: Option<usize> = None;
-
+    println!("\n--- Overall Best Guess ---");
+    match &final_report.best_guess {
+        Some(guess) => {
+            let best_attempt = &guess.attempt;
+            let scoring_method = final_report.decoder_runs[guess.decoder_index].scoring_method;
+
+
+            println!("Based on identification confidence and plaintext readability, the most likely result is:");
+            println!("Cipher: {} {}", best_attempt.cipher_name, score_description(scoring_method));
+            // The best guess is the answer, so its plaintext is never cut short.
+            println!("{}", output::format_attempt_preview(best_attempt, usize::MAX, options.key_width, scoring_method));
+            if let Some(note) = &best_attempt.note {
+                println!("Note: {}", note);
+            }
 
-    for (index, id_result) in final_id_results.iter().enumerate() {
-        if id_result.cipher_name == "Caesar" {
-            if final_top_dec_results.get(index).map_or(false, |(_, opt)| opt.is_some()) {
-                if id_result.confidence_score < CAESAR_CHI2_PREFERENCE_THRESHOLD {
-                    preferred_caesar_index = Some(index);
-                    break;
+            if options.explain {
+                println!("\nExplanation:");
+                for line in engine::explain_attempt(&text, best_attempt, scoring_method, &config) {
+                    println!("  - {}", line);
                 }
             }
-        }
-    }
 
-    if preferred_caesar_index.is_some() {
-        best_overall_decoder_index = preferred_caesar_index;
-    } else {
-
-        for (index, id_result) in final_id_results.iter().enumerate() {
-            if final_top_dec_results.get(index).map_or(false, |(_, opt)| opt.is_some()) {
-                let normalized_confidence = match id_result.cipher_name.as_str() {
-                    "Caesar" => 1.0 / (1.0 + id_result.confidence_score.max(0.0)),
-                    "Vigenere" => id_result.confidence_score,
-                    _ => 0.0,
-                };
-
-                if normalized_confidence > highest_normalized_confidence {
-                    highest_normalized_confidence = normalized_confidence;
-                    best_overall_decoder_index = Some(index);
+            match &guess.runner_up {
+                Some((runner_up_name, runner_up_confidence)) => {
+                    println!("\nRunner-up: {} (Normalized Confidence: {:.4} vs {:.4}, Gap: {:.4})",
+                             runner_up_name, runner_up_confidence, guess.confidence, guess.confidence_gap);
+                    if guess.is_ambiguous() {
+                        println!("Warning: Low confidence - the top candidates are close, results are ambiguous.");
+                    }
                 }
+                None => println!("\nNo runner-up: no other cipher produced a different decryption."),
             }
         }
-    }
-
-
-    println!("\n--- Overall Best Guess ---");
-    if let Some(index) = best_overall_decoder_index {
-
-        if let Some(best_attempt) = &final_top_dec_results[index].1 {
-            let decoder_name = &best_attempt.cipher_name;
-            let score_desc = if decoder_name == "Vigenere" {
-                "(Higher is better - Trigram Score)"
-            } else {
-                "(Lower is better - Chi^2 Score)"
-            };
-            let score_str = if decoder_name == "Vigenere" {
-                format!("{:<8.2}", best_attempt.score)
-            } else {
-                format!("{:<8.4}", best_attempt.score)
-            };
-            let key_preview = best_attempt.key.chars().take(10).collect::<String>()
-                + if best_attempt.key.chars().count() > 10 { "..." } else { "" };
-
-
-            println!("Based on identification confidence, the most likely result is:");
-            println!("Cipher: {}", decoder_name);
-            println!("Score: {} {}", score_str, score_desc);
-            println!("Key: {}", key_preview);
-
-            println!("Plaintext:");
-            println!("{}", best_attempt.plaintext);
-        } else {
-
-            println!("Internal Error: No decryption result found for the best identified cipher index.");
+        None => {
+            println!("Could not determine a single best guess based on combined identification and successful decryption.");
+            println!("Review the results from individual decoders above (if any).");
+            match analysis::sufficiency(&text, &config).advice() {
+                Some(advice) => println!("{}", advice),
+                None => println!("Consider adjusting the configuration."),
+            }
         }
-
-    } else {
-        println!("Could not determine a single best guess based on combined identification and successful decryption.");
-        println!("Review the results from individual decoders above (if any).");
-        println!("Consider providing longer ciphertext or adjusting configuration.");
     }
 
 
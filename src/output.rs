@@ -0,0 +1,110 @@
+// src/output.rs
+
+use crate::decoder::{DecryptionAttempt, ScoringMethod};
+
+pub const DEFAULT_TOP_N: usize = 10;
+pub const PLAINTEXT_PREVIEW_CHARS: usize = 70;
+pub const KEY_PREVIEW_CHARS: usize = 10;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct OutputOptions {
+    pub top_n: usize,
+    pub full_plaintext: bool,
+    pub preview_width: usize,
+    pub key_width: usize,
+    pub quiet: bool,
+    pub try_reversed: bool,
+    pub explain: bool,
+    pub max_key_combinations: Option<usize>,
+    // Set by repeated `--cipher NAME`; None runs every cipher.
+    pub enabled_ciphers: Option<Vec<String>>,
+}
+
+impl Default for OutputOptions {
+    fn default() -> Self {
+        OutputOptions {
+            top_n: DEFAULT_TOP_N,
+            full_plaintext: false,
+            preview_width: PLAINTEXT_PREVIEW_CHARS,
+            key_width: KEY_PREVIEW_CHARS,
+            quiet: false,
+            try_reversed: false,
+            explain: false,
+            max_key_combinations: None,
+            enabled_ciphers: None,
+        }
+    }
+}
+
+pub fn parse_args<I: IntoIterator<Item = String>>(args: I) -> Result<OutputOptions, String> {
+    let mut options = OutputOptions::default();
+    let mut args = args.into_iter();
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--top" => options.top_n = parse_number(&mut args, "--top")?,
+            "--preview-width" => options.preview_width = parse_number(&mut args, "--preview-width")?,
+            "--key-width" => options.key_width = parse_number(&mut args, "--key-width")?,
+            "--full" => options.full_plaintext = true,
+            "--quiet" | "-q" => options.quiet = true,
+            "--reversed" => options.try_reversed = true,
+            "--explain" => options.explain = true,
+            "--max-key-combinations" => {
+                options.max_key_combinations = Some(parse_number(&mut args, "--max-key-combinations")?)
+            }
+            "--cipher" => {
+                let name = args.next().ok_or("--cipher requires a cipher name")?;
+                options.enabled_ciphers.get_or_insert_with(Vec::new).push(name);
+            }
+            other => return Err(format!("Unknown argument: '{}'", other)),
+        }
+    }
+
+    Ok(options)
+}
+
+fn parse_number<I: Iterator<Item = String>>(args: &mut I, flag: &str) -> Result<usize, String> {
+    let value = args.next().ok_or(format!("{} requires a number", flag))?;
+    value
+        .parse::<usize>()
+        .map_err(|_| format!("Invalid value for {}: '{}'", flag, value))
+}
+
+// Keeps the first `width` characters and marks the cut with "..."; shorter text is returned as is.
+pub fn truncate_with_ellipsis(text: &str, width: usize) -> String {
+    if text.chars().count() <= width {
+        return text.to_string();
+    }
+    text.chars().take(width).collect::<String>() + "..."
+}
+
+pub fn format_score(method: ScoringMethod, score: f64) -> String {
+    if method.higher_is_better() {
+        format!("{:<8.2}", score)
+    } else {
+        format!("{:<8.4}", score)
+    }
+}
+
+// One results-table row: key, score and plaintext, each truncated to its column width.
+pub fn format_attempt_preview(attempt: &DecryptionAttempt, plaintext_width: usize, key_width: usize, method: ScoringMethod) -> String {
+    format!(
+        "Key: {:<width$} | Score: {} | Plaintext: \"{}\"",
+        truncate_with_ellipsis(&attempt.key, key_width),
+        format_score(method, attempt.score),
+        truncate_with_ellipsis(&attempt.plaintext, plaintext_width),
+        width = key_width
+    )
+}
+
+// Heading for a custom-settings prompt, followed by its explanation unless running quietly.
+pub fn setting_help(title: &str, explanation: &[&str], quiet: bool) -> String {
+    let mut help = format!("\n[{}]", title);
+    if !quiet {
+        for line in explanation {
+            help.push('\n');
+            help.push_str(line);
+        }
+    }
+    help
+}
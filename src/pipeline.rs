@@ -0,0 +1,72 @@
+// src/pipeline.rs
+
+use crate::decoder::{Decoder, DecryptionAttempt};
+
+struct Stage {
+    decoder: Box<dyn Decoder>,
+    key: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PipelineOutput {
+    // One attempt per stage, in order; the last one is the final result.
+    pub stages: Vec<DecryptionAttempt>,
+}
+
+impl PipelineOutput {
+    // None only when `stages` has been emptied; `Pipeline::run` never returns an empty output.
+    pub fn final_attempt(&self) -> Option<&DecryptionAttempt> {
+        self.stages.last()
+    }
+}
+
+// Chains decoders for multi-layer puzzles: each stage's best plaintext is the next stage's input.
+#[derive(Default)]
+pub struct Pipeline {
+    stages: Vec<Stage>,
+}
+
+impl Pipeline {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    // Searches for the key and passes on the best-ranked attempt.
+    pub fn then(mut self, decoder: Box<dyn Decoder>) -> Self {
+        self.stages.push(Stage { decoder, key: None });
+        self
+    }
+
+    // Decrypts with a known key instead of searching.
+    pub fn then_with_key(mut self, decoder: Box<dyn Decoder>, key: &str) -> Self {
+        self.stages.push(Stage { decoder, key: Some(key.to_string()) });
+        self
+    }
+
+    pub fn len(&self) -> usize {
+        self.stages.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.stages.is_empty()
+    }
+
+    // None if the pipeline is empty or any stage produces no attempt.
+    pub fn run(&self, input: &str) -> Option<PipelineOutput> {
+        if self.stages.is_empty() {
+            return None;
+        }
+
+        let mut attempts: Vec<DecryptionAttempt> = Vec::with_capacity(self.stages.len());
+        for stage in &self.stages {
+            let stage_input = attempts.last().map_or(input, |attempt| attempt.plaintext.as_str());
+            let attempt = match &stage.key {
+                Some(key) => stage.decoder.decrypt_with_key(stage_input, key),
+                None => stage.decoder.decrypt(stage_input).into_iter().next(),
+            }?;
+            attempts.push(attempt);
+        }
+
+        Some(PipelineOutput { stages: attempts })
+    }
+}
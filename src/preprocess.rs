@@ -0,0 +1,69 @@
+// src/preprocess.rs
+
+use crate::ciphers::{encoding, morse};
+use crate::ranking;
+
+// An encoding layer (Base64, hex, ...) peeled off before cryptanalysis. Unlike a `Decoder`, it
+// involves no key search: if `detect` says the text is in this form, `transform` undoes it.
+pub trait Preprocessor {
+    fn name(&self) -> &'static str;
+    fn detect(&self, text: &str) -> bool;
+    fn transform(&self, text: &str) -> String;
+}
+
+#[derive(Default)]
+pub struct HexPreprocessor;
+
+impl Preprocessor for HexPreprocessor {
+    fn name(&self) -> &'static str {
+        "Hex"
+    }
+
+    fn detect(&self, text: &str) -> bool {
+        encoding::decode_hex_text(text).is_some()
+    }
+
+    fn transform(&self, text: &str) -> String {
+        encoding::decode_hex_text(text).unwrap_or_else(|| text.to_string())
+    }
+}
+
+#[derive(Default)]
+pub struct Base64Preprocessor;
+
+impl Preprocessor for Base64Preprocessor {
+    fn name(&self) -> &'static str {
+        "Base64"
+    }
+
+    fn detect(&self, text: &str) -> bool {
+        encoding::decode_base64_text(text).is_some()
+    }
+
+    fn transform(&self, text: &str) -> String {
+        encoding::decode_base64_text(text).unwrap_or_else(|| text.to_string())
+    }
+}
+
+// Decoded Morse reading at least this well is the answer itself and is left to `MorseDecoder`.
+const MORSE_PLAINTEXT_READABILITY: f64 = 0.5;
+
+// Morse often wraps the real cipher in CTF inputs, so dots and dashes that decode to something
+// other than English are unwrapped and the letters analyzed like any other ciphertext.
+#[derive(Default)]
+pub struct MorsePreprocessor;
+
+impl Preprocessor for MorsePreprocessor {
+    fn name(&self) -> &'static str {
+        "Morse"
+    }
+
+    fn detect(&self, text: &str) -> bool {
+        morse::decode_morse_text(text)
+            .is_some_and(|decoded| ranking::plaintext_readability(&decoded) < MORSE_PLAINTEXT_READABILITY)
+    }
+
+    fn transform(&self, text: &str) -> String {
+        morse::decode_morse_text(text).unwrap_or_else(|| text.to_string())
+    }
+}
@@ -0,0 +1,240 @@
+// src/ranking.rs
+
+use crate::analysis;
+use crate::decoder::DecryptionAttempt;
+use crate::identifier::IdentificationResult;
+use crate::text_stats;
+use std::cmp::Ordering;
+
+// Gaps below this between the best and runner-up normalized confidences are reported as ambiguous.
+pub const AMBIGUOUS_CONFIDENCE_GAP: f64 = 0.1;
+// A Caesar identification with chi-squared below this wins outright over the confidence comparison.
+pub const CAESAR_CHI2_PREFERENCE_THRESHOLD: f64 = 3.0;
+
+// Ciphers without an identifier (Morse, Substitution, XOR, ...) are ranked on their plaintext alone.
+fn has_identifier(cipher_name: &str) -> bool {
+    matches!(cipher_name, "Caesar" | "Vigenere")
+}
+
+// How much a plaintext reads as English, 0..1: the share of its words found in the dictionary, or
+// full marks for unspaced text passing the plaintext check. Spaced text is judged by its words since
+// a hill-climbed substitution key can give gibberish English letter statistics.
+pub fn plaintext_readability(plaintext: &str) -> f64 {
+    let coverage = analysis::dictionary_word_coverage(plaintext, analysis::WordList::english());
+    if text_stats::has_word_structure(plaintext) || !analysis::looks_like_plaintext(plaintext) {
+        coverage
+    } else {
+        1.0
+    }
+}
+
+// Identified ciphers use their best normalized confidence; the rest their readability, if any.
+fn candidate_confidence(cipher_name: &str, attempt: &DecryptionAttempt, ids: &[IdentificationResult]) -> Option<f64> {
+    if has_identifier(cipher_name) {
+        ids.iter()
+            .filter(|id| id.cipher_name == cipher_name)
+            .map(normalized_confidence)
+            .max_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal))
+    } else {
+        Some(plaintext_readability(&attempt.plaintext)).filter(|&readability| readability > 0.0)
+    }
+}
+
+pub fn normalized_confidence(result: &IdentificationResult) -> f64 {
+    match result.cipher_name.as_str() {
+        "Caesar" => 1.0 / (1.0 + result.confidence_score.max(0.0)), // Normalize Chi2
+        "Vigenere" => result.confidence_score, // Already 0-1, higher is better
+        _ => 0.0,
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BestGuess {
+    pub cipher_name: String,
+    pub decoder_index: usize,
+    pub attempt: DecryptionAttempt,
+    pub confidence: f64,
+    pub caesar_preferred: bool,
+    // Set when the guess was promoted because its plaintext contains this crib or flag.
+    pub matched_crib: Option<String>,
+    // Best other decrypted cipher and its confidence on the same scale, measured against this guess.
+    pub runner_up: Option<(String, f64)>,
+    pub confidence_gap: f64,
+}
+
+impl BestGuess {
+    pub fn is_ambiguous(&self) -> bool {
+        self.runner_up.is_some() && self.confidence_gap < AMBIGUOUS_CONFIDENCE_GAP
+    }
+
+    // The strongest other cipher with a different plaintext; ciphers agreeing with the guess are not
+    // rivals. A guess picked over a better-identified cipher (by a Caesar preference or a crib, say)
+    // gets a negative gap.
+    fn with_runner_up<'a>(
+        mut self,
+        ids: &[IdentificationResult],
+        candidates: impl Iterator<Item = (&'a str, &'a DecryptionAttempt)>,
+    ) -> Self {
+        self.runner_up = candidates
+            .filter(|(name, attempt)| {
+                *name != self.cipher_name && !attempt.plaintext.eq_ignore_ascii_case(&self.attempt.plaintext)
+            })
+            .filter_map(|(name, attempt)| Some((name.to_string(), candidate_confidence(name, attempt, ids)?)))
+            .max_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(Ordering::Equal));
+        // With nothing to compare against, the whole confidence is the margin.
+        self.confidence_gap = match &self.runner_up {
+            Some((_, runner_up_confidence)) => self.confidence - runner_up_confidence,
+            None => self.confidence,
+        };
+        self
+    }
+}
+
+// Combines identification with decryption: only ciphers whose decoder produced an attempt are
+// eligible. A strong Caesar identification is preferred; otherwise the highest normalized
+// confidence wins. Decoder results are matched to identifications by cipher name. Ciphers without
+// an identifier win only when their plaintext reads better than the identified pick's.
+pub fn best_overall_guess(
+    ids: &[IdentificationResult],
+    decs: &[(String, Option<DecryptionAttempt>)],
+) -> Option<BestGuess> {
+    best_overall_guess_with_threshold(ids, decs, CAESAR_CHI2_PREFERENCE_THRESHOLD)
+}
+
+// `best_overall_guess` with a custom chi-squared below which a Caesar identification wins outright.
+pub fn best_overall_guess_with_threshold(
+    ids: &[IdentificationResult],
+    decs: &[(String, Option<DecryptionAttempt>)],
+    caesar_preference_chi2: f64,
+) -> Option<BestGuess> {
+    let decrypted = |id: &IdentificationResult| {
+        decs.iter()
+            .position(|(name, attempt)| *name == id.cipher_name && attempt.is_some())
+    };
+
+    let preferred_caesar = ids.iter().find_map(|id| {
+        if id.cipher_name == "Caesar" && id.confidence_score < caesar_preference_chi2 {
+            decrypted(id).map(|index| (id, index))
+        } else {
+            None
+        }
+    });
+
+    let identified = match preferred_caesar {
+        Some(choice) => Some(choice),
+        None => ids
+            .iter()
+            .filter_map(|id| decrypted(id).map(|index| (id, index)))
+            .fold(None, |best: Option<(&IdentificationResult, usize)>, candidate| match best {
+                Some(current) if normalized_confidence(current.0) >= normalized_confidence(candidate.0) => Some(current),
+                _ => Some(candidate),
+            }),
+    };
+    let mut guess = identified.and_then(|(chosen, decoder_index)| {
+        Some(BestGuess {
+            cipher_name: chosen.cipher_name.clone(),
+            decoder_index,
+            attempt: decs[decoder_index].1.clone()?,
+            confidence: normalized_confidence(chosen),
+            caesar_preferred: preferred_caesar.is_some(),
+            matched_crib: None,
+            runner_up: None,
+            confidence_gap: 0.0,
+        })
+    });
+
+    // Equally readable plaintexts fall back to the trigram average.
+    let unidentified = decs
+        .iter()
+        .enumerate()
+        .filter(|(_, (name, _))| !has_identifier(name))
+        .filter_map(|(index, (name, attempt))| {
+            let attempt = attempt.as_ref()?;
+            Some((index, name, attempt, candidate_confidence(name, attempt, ids)?))
+        })
+        .max_by(|a, b| {
+            a.3.partial_cmp(&b.3).unwrap_or(Ordering::Equal).then_with(|| {
+                let trigrams = |attempt: &DecryptionAttempt| analysis::score_trigram_avg(&attempt.plaintext);
+                trigrams(a.2).partial_cmp(&trigrams(b.2)).unwrap_or(Ordering::Equal)
+            })
+        });
+    if let Some((decoder_index, name, attempt, readability)) = unidentified {
+        if guess.as_ref().is_none_or(|identified| readability > plaintext_readability(&identified.attempt.plaintext)) {
+            guess = Some(BestGuess {
+                cipher_name: name.clone(),
+                decoder_index,
+                attempt: attempt.clone(),
+                confidence: readability,
+                caesar_preferred: false,
+                matched_crib: None,
+                runner_up: None,
+                confidence_gap: 0.0,
+            });
+        }
+    }
+
+    let candidates = decs.iter().filter_map(|(name, attempt)| Some((name.as_str(), attempt.as_ref()?)));
+    guess.map(|guess| guess.with_runner_up(ids, candidates))
+}
+
+// Known plaintext trumps statistics: the first candidate, in decoder order and then rank order,
+// whose plaintext contains a crib (case-insensitively) or a CTF flag becomes the guess.
+pub fn promote_crib_match(
+    ids: &[IdentificationResult],
+    decs: &[(String, Vec<DecryptionAttempt>)],
+    cribs: &[String],
+) -> Option<BestGuess> {
+    let cribs: Vec<String> = cribs.iter().filter(|crib| !crib.is_empty()).map(|crib| crib.to_uppercase()).collect();
+
+    let guess = decs.iter().enumerate().find_map(|(decoder_index, (name, attempts))| {
+        attempts.iter().find_map(|attempt| {
+            let upper = attempt.plaintext.to_uppercase();
+            let matched = cribs
+                .iter()
+                .find(|crib| upper.contains(crib.as_str()))
+                .cloned()
+                .or_else(|| analysis::contains_flag_pattern(&attempt.plaintext))?;
+            Some(BestGuess {
+                cipher_name: name.clone(),
+                decoder_index,
+                attempt: attempt.clone(),
+                confidence: 1.0,
+                caesar_preferred: false,
+                matched_crib: Some(matched),
+                runner_up: None,
+                confidence_gap: 0.0,
+            })
+        })
+    })?;
+    let candidates = decs.iter().filter_map(|(name, attempts)| Some((name.as_str(), attempts.first()?)));
+    Some(guess.with_runner_up(ids, candidates))
+}
+
+// Weights of the parts of `overall_confidence`; they sum to 1.
+const OVERALL_WEIGHT_IDENTIFICATION: f64 = 0.3;
+const OVERALL_WEIGHT_GAP: f64 = 0.2;
+const OVERALL_WEIGHT_PLAINTEXT: f64 = 0.5;
+// A CTF flag in the plaintext is near-certain evidence of a crack.
+const OVERALL_FLAG_CONFIDENCE: f64 = 0.95;
+
+// One 0..1 answer to "was this cracked?", for pipelines deciding whether to accept a result. Blends
+// the guess's confidence, its margin over its runner-up, and `plaintext_readability`.
+// Crib matches score 1; a flag in the plaintext lifts the score to at least `OVERALL_FLAG_CONFIDENCE`.
+pub fn overall_confidence(guess: Option<&BestGuess>) -> f64 {
+    let Some(guess) = guess else {
+        return 0.0;
+    };
+    if guess.matched_crib.is_some() {
+        return 1.0;
+    }
+
+    let plaintext = &guess.attempt.plaintext;
+    let blended = OVERALL_WEIGHT_IDENTIFICATION * guess.confidence.clamp(0.0, 1.0)
+        + OVERALL_WEIGHT_GAP * guess.confidence_gap.clamp(0.0, 1.0)
+        + OVERALL_WEIGHT_PLAINTEXT * plaintext_readability(plaintext);
+    if analysis::contains_flag_pattern(plaintext).is_some() {
+        blended.max(OVERALL_FLAG_CONFIDENCE)
+    } else {
+        blended
+    }
+}
@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 #[derive(Debug, PartialEq, Default)]
 pub struct BasicStats {
     pub char_count_total: usize,
@@ -14,6 +16,11 @@ pub struct BasicStats {
     pub average_word_length: f64,
     pub uppercase_percent: f64,
     pub lowercase_percent: f64,
+    pub distinct_alpha_letters: usize,
+    pub repetition_ratio: f64,
+    pub vowel_count: usize,
+    pub consonant_count: usize,
+    pub vowel_ratio: f64,
 }
 
 pub fn calculate_basic_stats(text: &str) -> Option<BasicStats> {
@@ -27,12 +34,19 @@ pub fn calculate_basic_stats(text: &str) -> Option<BasicStats> {
     };
 
     let mut total_word_length_sum: usize = 0;
+    let mut letters_seen = [false; 26];
 
     stats.char_count_total = text.chars().count();
 
     for c in text.chars() {
         if c.is_ascii_alphabetic() {
             stats.char_count_alpha += 1;
+            letters_seen[(c.to_ascii_uppercase() as u8 - b'A') as usize] = true;
+            if matches!(c.to_ascii_uppercase(), 'A' | 'E' | 'I' | 'O' | 'U') {
+                stats.vowel_count += 1;
+            } else {
+                stats.consonant_count += 1;
+            }
             if c.is_ascii_uppercase() {
                 stats.char_count_upper += 1;
 
@@ -72,17 +86,128 @@ pub fn calculate_basic_stats(text: &str) -> Option<BasicStats> {
         stats.average_word_length = total_word_length_sum as f64 / stats.word_count as f64;
     }
 
+    stats.distinct_alpha_letters = letters_seen.iter().filter(|&&seen| seen).count();
+
     if stats.char_count_alpha == 0 {
         stats.uppercase_percent = 0.0;
         stats.lowercase_percent = 0.0;
+        stats.repetition_ratio = 0.0;
+        stats.vowel_ratio = 0.0;
     } else {
         stats.uppercase_percent = (stats.char_count_upper as f64 / stats.char_count_alpha as f64) * 100.0;
         stats.lowercase_percent = (stats.char_count_lower as f64 / stats.char_count_alpha as f64) * 100.0;
+        stats.repetition_ratio = stats.char_count_alpha as f64 / stats.distinct_alpha_letters as f64;
+        stats.vowel_ratio = stats.vowel_count as f64 / stats.char_count_alpha as f64;
     }
 
     Some(stats)
 }
 
+// Fewer tokens than this can't show a length distribution.
+const MIN_WORD_TOKENS: usize = 3;
+// English averages around 4.5 letters per word.
+const MIN_AVG_WORD_LEN: f64 = 2.0;
+const MAX_AVG_WORD_LEN: f64 = 10.0;
+
+// True when the text still looks split into words: several whitespace-separated tokens of varied,
+// word-like lengths. Continuous streams and fixed-size groups (e.g. blocks of five) are false.
+pub fn has_word_structure(text: &str) -> bool {
+    let lengths: Vec<usize> = text
+        .split_whitespace()
+        .map(|token| token.chars().filter(|c| c.is_ascii_alphabetic()).count())
+        .filter(|&len| len > 0)
+        .collect();
+    if lengths.len() < MIN_WORD_TOKENS || uniform_group_size(text).is_some() {
+        return false;
+    }
+
+    let average = lengths.iter().sum::<usize>() as f64 / lengths.len() as f64;
+    let varied = lengths.iter().any(|&len| len != lengths[0]);
+    varied && (MIN_AVG_WORD_LEN..=MAX_AVG_WORD_LEN).contains(&average)
+}
+
+// Telegraph-style grouping ("ABCDE FGHIJ KLMNO"): every token has the same length, except that the
+// last may be a shorter remainder. Returns that length; spacing in such text carries no meaning.
+pub fn uniform_group_size(text: &str) -> Option<usize> {
+    let lengths: Vec<usize> = text.split_whitespace().map(|token| token.chars().count()).collect();
+    let (last, groups) = lengths.split_last()?;
+    let size = *groups.first()?;
+    let uniform = lengths.len() >= MIN_WORD_TOKENS
+        && size >= 2
+        && groups.iter().all(|&len| len == size)
+        && *last <= size;
+    uniform.then_some(size)
+}
+
+// Start positions of every length-`len` window that occurs more than once, for the earliest such
+// window; None when all windows are distinct.
+fn first_repeat_of_len(letters: &[u8], len: usize) -> Option<Vec<usize>> {
+    let mut positions: HashMap<&[u8], Vec<usize>> = HashMap::new();
+    for (start, window) in letters.windows(len).enumerate() {
+        positions.entry(window).or_default().push(start);
+    }
+    positions
+        .into_values()
+        .filter(|starts| starts.len() > 1)
+        .min_by_key(|starts| starts[0])
+}
+
+// Longest substring of the uppercased letters-only text that occurs at least twice, with every
+// start position (in letters, overlaps allowed). Ties go to the earliest substring.
+pub fn longest_repeated_substring(text: &str) -> Option<(String, Vec<usize>)> {
+    let letters: Vec<u8> = text
+        .bytes()
+        .filter(|b| b.is_ascii_alphabetic())
+        .map(|b| b.to_ascii_uppercase())
+        .collect();
+
+    // A repeat of length n implies one of length n - 1, so binary search the length.
+    let mut best = (1, first_repeat_of_len(&letters, 1)?);
+    let mut high = letters.len() - 1;
+    while best.0 < high {
+        let len = (best.0 + high).div_ceil(2);
+        match first_repeat_of_len(&letters, len) {
+            Some(starts) => best = (len, starts),
+            None => high = len - 1,
+        }
+    }
+
+    let (len, starts) = best;
+    let substring = String::from_utf8_lossy(&letters[starts[0]..starts[0] + len]).into_owned();
+    Some((substring, starts))
+}
+
+// Frequency of each letter among word-initial letters: the first letter of every
+// whitespace-separated token. English initials lean heavily on T, A and S, unlike overall text.
+pub fn initial_letter_frequencies(text: &str) -> Option<[f64; 26]> {
+    let mut counts = [0usize; 26];
+    let mut total = 0;
+    for initial in text.split_whitespace().filter_map(|token| token.chars().find(|c| c.is_ascii_alphabetic())) {
+        counts[(initial.to_ascii_uppercase() as u8 - b'A') as usize] += 1;
+        total += 1;
+    }
+    if total == 0 {
+        return None;
+    }
+    Some(std::array::from_fn(|i| counts[i] as f64 / total as f64))
+}
+
+// Whitespace-delimited words occurring at least `min_count` times, lowercased and with surrounding
+// punctuation trimmed. Most frequent first, then alphabetical.
+pub fn repeated_words(text: &str, min_count: usize) -> Vec<(String, usize)> {
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    for token in text.split_whitespace() {
+        let word = token.trim_matches(|c: char| !c.is_alphanumeric());
+        if !word.is_empty() {
+            *counts.entry(word.to_lowercase()).or_default() += 1;
+        }
+    }
+
+    let mut repeated: Vec<(String, usize)> = counts.into_iter().filter(|&(_, count)| count >= min_count).collect();
+    repeated.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    repeated
+}
+
 
 #[cfg(test)]
 mod tests {
@@ -107,6 +232,18 @@ mod tests {
         assert_eq!(stats.char_count_numeric, 0);
         assert_eq!(stats.char_count_other, 0);
         assert_eq!(stats.char_count_total, 89);
+        assert_eq!(stats.distinct_alpha_letters, 18);
+        assert!((stats.repetition_ratio - (72.0 / 18.0)).abs() < 1e-6);
+        assert_eq!(stats.vowel_count, 28);
+        assert_eq!(stats.consonant_count, 44);
+        assert!((stats.vowel_ratio - (28.0 / 72.0)).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_stats_distinct_letters() {
+        let stats = calculate_basic_stats("AaBb").unwrap();
+        assert_eq!(stats.distinct_alpha_letters, 2);
+        assert!((stats.repetition_ratio - 2.0).abs() < 1e-6);
     }
 
     #[test]
@@ -123,6 +260,9 @@ mod tests {
         assert_eq!(stats.max_word_length, 0);
         assert_eq!(stats.average_word_length, 0.0);
         assert_eq!(stats.char_count_alpha, 0);
+        assert_eq!(stats.distinct_alpha_letters, 0);
+        assert_eq!(stats.repetition_ratio, 0.0);
+        assert_eq!(stats.vowel_ratio, 0.0);
     }
 
     #[test]
@@ -138,6 +278,10 @@ mod tests {
         assert_eq!(stats.lowercase_percent, 0.0);
         assert_eq!(stats.char_count_numeric, 6);
         assert_eq!(stats.char_count_punctuation, 3);
+        assert_eq!(stats.distinct_alpha_letters, 0);
+        assert_eq!(stats.repetition_ratio, 0.0);
+        assert_eq!(stats.vowel_count, 0);
+        assert_eq!(stats.vowel_ratio, 0.0);
     }
 
     #[test]
@@ -167,4 +311,61 @@ mod tests {
         assert!((stats.uppercase_percent - (11.0 / 1149.0 * 100.0)).abs() < 0.01);
         assert!((stats.lowercase_percent - ((1149.0-11.0) / 1149.0 * 100.0)).abs() < 0.01);
     }
+
+    #[test]
+    fn test_longest_repeated_substring() {
+        // "VHVSSPQU" repeats at letter offsets 3 and 32; punctuation and case are ignored.
+        let text = "abc VHVSS pqu xyzwqlmnoprstabcdefgh, vhvsspqu KLM";
+        assert_eq!(
+            longest_repeated_substring(text),
+            Some(("VHVSSPQU".to_string(), vec![3, 32]))
+        );
+
+        let (repeat, starts) = longest_repeated_substring("ABABA").unwrap();
+        assert_eq!((repeat.as_str(), starts), ("ABA", vec![0, 2]));
+        assert_eq!(longest_repeated_substring("ABCDEF"), None);
+        assert_eq!(longest_repeated_substring(""), None);
+    }
+
+    #[test]
+    fn test_has_word_structure() {
+        assert!(has_word_structure("Four score and seven years ago our fathers brought forth on this continent."));
+
+        let block = "QPWKALVRXCQZIKGKGIGNOREQVXURPJTONDOCBUGQNGRSCQPWKALVRXCQZIKGKGIGNOREQVXURPJTONDOCBUGQNGRSCABCDEFGHIJ";
+        assert_eq!(block.len(), 100);
+        assert!(!has_word_structure(block));
+        assert!(!has_word_structure("QPWKA LVRXC QZIKG KGIGN OREQV"));
+    }
+
+    #[test]
+    fn test_uniform_group_size() {
+        assert_eq!(uniform_group_size("ABCDE FGHIJ KLMNO"), Some(5));
+        assert_eq!(uniform_group_size("ABCDE FGHIJ KLMNO PQ"), Some(5));
+        assert_eq!(uniform_group_size("Four score and seven years ago our fathers brought forth"), None);
+        assert_eq!(uniform_group_size("ABCDE FGHIJ"), None);
+        assert_eq!(uniform_group_size(""), None);
+    }
+
+    #[test]
+    fn test_initial_letter_frequencies() {
+        let freqs = initial_letter_frequencies("The tall tree threw its shade across the town, \"Thank them,\" they said.").unwrap();
+        let top = (0..26).max_by(|&a, &b| freqs[a].total_cmp(&freqs[b])).unwrap();
+        assert_eq!(top, (b'T' - b'A') as usize);
+        assert!((freqs[top] - 9.0 / 13.0).abs() < 1e-9);
+        assert!((freqs.iter().sum::<f64>() - 1.0).abs() < 1e-9);
+
+        assert_eq!(initial_letter_frequencies("123 ... !!"), None);
+        assert_eq!(initial_letter_frequencies(""), None);
+    }
+
+    #[test]
+    fn test_repeated_words() {
+        assert_eq!(repeated_words("the cat the dog the bird", 2), vec![("the".to_string(), 3)]);
+        assert_eq!(
+            repeated_words("The cat, THE dog; the cat.", 2),
+            vec![("the".to_string(), 3), ("cat".to_string(), 2)]
+        );
+        assert!(repeated_words("QPWKA LVRXC QZIKG", 2).is_empty());
+        assert!(repeated_words("", 1).is_empty());
+    }
 }
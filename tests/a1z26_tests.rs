@@ -0,0 +1,41 @@
+use peekaboo::ciphers::a1z26::A1Z26Decoder;
+use peekaboo::decoder::Decoder;
+use peekaboo::config::Config;
+
+
+#[test]
+fn test_a1z26_dash_separated() {
+    let config = Config::default();
+    let decoder = A1Z26Decoder::new(&config);
+
+    let results = decoder.decrypt("8-5-12-12-15");
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].cipher_name, "A1Z26");
+    assert_eq!(results[0].key, "a1z26");
+    assert_eq!(results[0].plaintext, "HELLO");
+
+    assert_eq!(decoder.decrypt("8-5-12-12-15 23-15-18-12-4")[0].plaintext, "HELLO WORLD");
+}
+
+#[test]
+fn test_a1z26_space_and_comma_separated() {
+    let config = Config::default();
+    let decoder = A1Z26Decoder::new(&config);
+
+    assert_eq!(decoder.decrypt("8 5 12 12 15")[0].plaintext, "HELLO");
+    assert_eq!(decoder.decrypt("8,5,12,12,15")[0].plaintext, "HELLO");
+    assert_eq!(decoder.decrypt("8, 5, 12, 12, 15")[0].plaintext, "HELLO");
+    assert_eq!(decoder.decrypt("8 9 / 20 8 5 18 5")[0].plaintext, "HI THERE");
+}
+
+#[test]
+fn test_a1z26_declines_out_of_range_or_non_numeric() {
+    let config = Config::default();
+    let decoder = A1Z26Decoder::new(&config);
+
+    assert!(decoder.decrypt("8-5-27-12-15").is_empty());
+    assert!(decoder.decrypt("0-5-12").is_empty());
+    assert!(decoder.decrypt("8-5-x-12").is_empty());
+    assert!(decoder.decrypt("Hello").is_empty());
+    assert!(decoder.decrypt("").is_empty());
+}
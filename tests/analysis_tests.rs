@@ -1,6 +1,9 @@
 use peekaboo::analysis::*;
 use peekaboo::cipher_utils;
 
+mod common;
+use common::{substitution_encrypt, vigenere_encrypt, SUBSTITUTION_KEY};
+
 
 
 #[test]
@@ -34,6 +37,7 @@ fn test_calculate_ic_original() {
 }
 
 #[test]
+#[allow(clippy::assertions_on_constants)]
 fn test_find_factors_test() {
 
     assert!(true);
@@ -82,6 +86,17 @@ fn test_freq_calc_basic() {
     assert!((freqs[2] - 0.0).abs() < 1e-9);
 }
 
+#[test]
+fn test_letter_counts_basic() {
+    let (counts, total) = letter_counts("AaBb").unwrap();
+    let mut expected = [0usize; 26];
+    expected[0] = 2;
+    expected[1] = 2;
+    assert_eq!(counts, expected);
+    assert_eq!(total, 4);
+    assert!(letter_counts("123 !@#").is_none());
+}
+
 #[test]
 fn test_freq_calc_empty_or_no_alpha() {
     assert!(calculate_frequencies("").is_none());
@@ -89,6 +104,7 @@ fn test_freq_calc_empty_or_no_alpha() {
 }
 
 #[test]
+#[allow(clippy::assertions_on_constants)]
 fn test_chi_squared_perfect_match_test() {
 
     assert!(true);
@@ -139,6 +155,7 @@ fn test_ic_invariance_caesar() {
 }
 
 #[test]
+#[allow(clippy::needless_borrow)]
 fn test_ic_reduction_vigenere() {
     let plaintext = "ALICEWASBEGINNINGTOGETVERYTIREDOFSITTINGBYHERSISTERONTHEBANKANDOFHAVINGNOTHINGTODOONCEORTWICESHEHADPEEPEDINTOTHEBOOKHERSISTERWASREADINGBUTITHADNOPICTURESORCONVERSATIONSINIT";
     let _keyword = "CRYPTO";
@@ -184,28 +201,45 @@ fn test_find_top_n_caesar_shifts_mic_test() {
     let key_shift: i8 = 3;
     let ciphertext = cipher_utils::shift_char_string(plaintext, key_shift);
 
-    let top3 = find_top_n_caesar_shifts_mic(&ciphertext, 3).expect("MIC failed to find top 3");
+    let top3 = find_top_n_caesar_shifts_mic(&ciphertext, 3, DEFAULT_MIN_CHARS_FOR_MIC).expect("MIC failed to find top 3");
     println!("MIC top 3 shifts: {:?}", top3);
     assert_eq!(top3.len(), 3);
     assert_eq!(top3[0].0, key_shift as u8, "MIC top shift was not correct");
     assert!(top3[0].1 >= top3[1].1);
     assert!(top3[1].1 >= top3[2].1);
 
-    let top1 = find_top_n_caesar_shifts_mic(&ciphertext, 1).expect("MIC failed to find top 1");
+    let top1 = find_top_n_caesar_shifts_mic(&ciphertext, 1, DEFAULT_MIN_CHARS_FOR_MIC).expect("MIC failed to find top 1");
     assert_eq!(top1.len(), 1);
     assert_eq!(top1[0].0, key_shift as u8);
 
-    let top5 = find_top_n_caesar_shifts_mic(&ciphertext, 5).expect("MIC failed to find top 5");
+    let top5 = find_top_n_caesar_shifts_mic(&ciphertext, 5, DEFAULT_MIN_CHARS_FOR_MIC).expect("MIC failed to find top 5");
     assert_eq!(top5.len(), 5);
 
     let short_text = "SHORT";
-    let top_short = find_top_n_caesar_shifts_mic(short_text, 3);
+    let top_short = find_top_n_caesar_shifts_mic(short_text, 3, DEFAULT_MIN_CHARS_FOR_MIC);
     assert!(top_short.is_some());
 
-    let zero_n = find_top_n_caesar_shifts_mic(&ciphertext, 0);
+    let zero_n = find_top_n_caesar_shifts_mic(&ciphertext, 0, DEFAULT_MIN_CHARS_FOR_MIC);
     assert!(zero_n.is_none());
 }
 
+#[test]
+fn test_find_top_n_caesar_shifts_mic_ties() {
+    // A flat distribution gives every shift the same MIC, so ordering falls back to the shift value.
+    let flat_column = "ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+    let top3 = find_top_n_caesar_shifts_mic(flat_column, 3, DEFAULT_MIN_CHARS_FOR_MIC).expect("MIC failed on flat column");
+    assert_eq!(top3.len(), 3);
+    assert_eq!(top3[0].1, top3[1].1);
+    assert_eq!(top3[1].1, top3[2].1);
+    let shifts: Vec<u8> = top3.iter().map(|(shift, _)| *shift).collect();
+    assert_eq!(shifts, vec![0, 1, 2]);
+
+    for _ in 0..5 {
+        assert_eq!(find_top_n_caesar_shifts_mic(flat_column, 3, DEFAULT_MIN_CHARS_FOR_MIC).unwrap(), top3);
+    }
+}
+
 #[test]
 fn test_score_trigram_log_prob_test() {
 
@@ -272,3 +306,470 @@ fn test_estimate_key_length_ic_periodicity() {
 
     assert!(!results_short.is_empty());
 }
+
+#[test]
+fn test_looks_like_plaintext() {
+    let english = "This is a reasonably long sentence in English which should hopefully get a fairly low chi squared score when compared against standard letter frequencies";
+    assert!(looks_like_plaintext(english));
+
+    let shifted = cipher_utils::shift_char_string(english, 3);
+    assert!(!looks_like_plaintext(&shifted));
+
+    assert!(!looks_like_plaintext(""));
+    assert!(!looks_like_plaintext("123 !@#"));
+}
+
+#[test]
+fn test_segment_preserving() {
+    assert_eq!(segment_preserving("HE LLO!", "HELLO"), "HE LLO!");
+    assert_eq!(segment_preserving("Khoor, Zruog!", "HELLOWORLD"), "Hello, World!");
+    assert_eq!(segment_preserving("AB CD", "WXY"), "WX Y");
+    assert_eq!(segment_preserving("AB", "WXYZ"), "WXYZ");
+    assert_eq!(segment_preserving("", ""), "");
+}
+
+#[test]
+fn test_kasiski_sequences() {
+    // "GSWKEM" repeats 19 letters apart, so each of its sub-sequences carries the same distance.
+    let ciphertext = "THGSWKEMDUSQDZPYFQIGNGSWKEMNBUIFQI";
+    let sequences = kasiski_sequences(ciphertext, 3, 10);
+    println!("Kasiski sequences: {:?}", sequences);
+    assert!(!sequences.is_empty());
+
+    let (_, positions, distances) = sequences
+        .iter()
+        .find(|(seq, _, _)| seq == "GSWKEM")
+        .expect("Repeated sequence GSWKEM not reported");
+    assert_eq!(positions, &vec![2, 21]);
+    assert_eq!(distances, &vec![19]);
+
+    assert!(sequences.windows(2).all(|w| w[0].0.len() >= w[1].0.len()));
+
+    let abc = kasiski_sequences("ABCXYZABC", 3, 4);
+    assert_eq!(abc, vec![("ABC".to_string(), vec![0, 6], vec![6])]);
+
+    assert!(kasiski_sequences("ABCDEFGHIJKLMNOPQRSTUVWXYZ", 3, 10).is_empty());
+    assert!(kasiski_sequences("", 3, 10).is_empty());
+}
+
+#[test]
+fn test_find_top_n_caesar_shifts_mic_min_chars() {
+    let four_char_column = "KHOR";
+    assert!(find_top_n_caesar_shifts_mic(four_char_column, 3, DEFAULT_MIN_CHARS_FOR_MIC).is_none());
+
+    let relaxed = find_top_n_caesar_shifts_mic(four_char_column, 3, 3);
+    assert!(relaxed.is_some());
+    assert_eq!(relaxed.unwrap().len(), 3);
+
+    assert!(find_top_n_caesar_shifts_mic("", 3, 0).is_none());
+}
+
+#[test]
+fn test_score_trigram_log_prob_prepared_matches() {
+    let text = "Here is some reasonably normal English text, containing common trigrams!";
+    let prepared = get_alphabetic_chars(text).to_ascii_uppercase();
+
+    assert_eq!(score_trigram_log_prob_prepared(&prepared), score_trigram_log_prob(text));
+    assert_eq!(score_trigram_log_prob_prepared("TH"), -f64::INFINITY);
+    assert_eq!(score_trigram_log_prob_prepared(""), score_trigram_log_prob("123"));
+}
+
+#[test]
+fn test_rolling_ic() {
+    let high_ic = "ALICEWASBEGINNINGTOGETVERYTIREDOFSITTINGBYHERSISTERONTHEBANKANDOFHAVINGNOTHINGTODO";
+    let low_ic = "ABCDEFGHIJKLMNOPQRSTUVWXYZABCDEFGHIJKLMNOPQRSTUVWXYZABCDEFGHIJKLMNOPQRSTUVWXYZABCDE";
+    let mixed = format!("{}{}", high_ic, low_ic);
+    let window = 40;
+
+    let values = rolling_ic(&mixed, window);
+    assert_eq!(values.len(), mixed.len() - window + 1);
+
+    // Each window value must agree with a direct IC calculation.
+    assert!((values[0] - calculate_ic(&mixed[..window]).unwrap()).abs() < 1e-9);
+    let last_start = mixed.len() - window;
+    assert!((values[last_start] - calculate_ic(&mixed[last_start..]).unwrap()).abs() < 1e-9);
+
+    let first = values[0];
+    let last = *values.last().unwrap();
+    println!("Rolling IC first: {:.4}, last: {:.4}", first, last);
+    assert!(first > 0.05);
+    assert!(last < 0.02);
+    assert!(first > last);
+
+    assert!(rolling_ic("SHORT", 10).is_empty());
+    assert!(rolling_ic(high_ic, 1).is_empty());
+}
+
+#[test]
+fn test_dictionary_word_coverage() {
+    let words = WordList::english();
+    assert!(!words.is_empty());
+
+    assert_eq!(dictionary_word_coverage("THE CAT SAT", words), 1.0);
+    assert_eq!(dictionary_word_coverage("Xqz ZZ qqq", words), 0.0);
+    assert_eq!(dictionary_word_coverage("Attack at dawn!", words), 1.0);
+    assert!((dictionary_word_coverage("the cat xqz zzq", words) - 0.5).abs() < 1e-9);
+    assert_eq!(dictionary_word_coverage("", words), 0.0);
+    assert_eq!(dictionary_word_coverage("123 !!", words), 0.0);
+
+    let custom = WordList::from_text("alpha\nbravo\n");
+    assert_eq!(custom.len(), 2);
+    assert_eq!(dictionary_word_coverage("ALPHA bravo charlie delta", &custom), 0.5);
+}
+
+#[test]
+fn test_calculate_ic_matches_reference() {
+    fn reference_ic(text: &str) -> Option<f64> {
+        let alpha_text = get_alphabetic_chars(text);
+        let n = alpha_text.len();
+        if n < 2 {
+            return None;
+        }
+        let mut counts = [0usize; 26];
+        for c in alpha_text.chars() {
+            counts[(c.to_ascii_uppercase() as u8 - b'A') as usize] += 1;
+        }
+        let mut sum = 0.0;
+        for count in counts.iter() {
+            sum += (*count as f64) * (*count as f64 - 1.0);
+        }
+        Some(sum / (n as f64 * (n as f64 - 1.0)))
+    }
+
+    let inputs = [
+        "",
+        "A",
+        "Aa",
+        "Hello, World! 123",
+        "The Quick Brown Fox -- jumps over the LAZY dog...",
+        "naïve café déjà vu ÆØÅ",
+        "CBGRXKQIWPSUYENEKDPELSZNAGMFWEAKDPJDQSHEYPGVXJURTJLFMSHRPEEVEPKWPBBTVOVPHISBUG",
+    ];
+    for input in inputs {
+        assert_eq!(calculate_ic(input), reference_ic(input), "IC mismatch for {:?}", input);
+    }
+}
+
+#[test]
+fn test_bigram_score_ranks_short_plaintext_where_trigrams_do_not() {
+    let plaintext = "WHYNOTYOU!";
+    let shifted = cipher_utils::shift_char_string(plaintext, 20);
+
+    assert!(score_trigram_log_prob(&shifted) > score_trigram_log_prob(plaintext));
+    assert!(score_bigram_log_prob(plaintext) > score_bigram_log_prob(&shifted));
+    assert_eq!(score_bigram_log_prob("A"), -f64::INFINITY);
+}
+
+#[test]
+fn test_language_profile_with_zero_entry() {
+    let mut frequencies = [1.0; 26];
+    frequencies[(b'Q' - b'A') as usize] = 0.0;
+    let profile = LanguageProfile::from_frequencies(frequencies).unwrap();
+    assert!((profile.frequencies().iter().sum::<f64>() - 1.0).abs() < 1e-9);
+
+    assert_eq!(score_likelihood_with_profile("queen", &profile), Some(f64::MAX));
+    let without_q = score_likelihood_with_profile("green", &profile).unwrap();
+    assert!(without_q.is_finite() && without_q < f64::MAX);
+
+    assert!(LanguageProfile::from_frequencies([0.0; 26]).is_none());
+    frequencies[0] = -1.0;
+    assert!(LanguageProfile::from_frequencies(frequencies).is_none());
+}
+
+#[test]
+fn test_likelihood_case_insensitive_on_tiny_text() {
+    assert_eq!(score_english_likelihood("AB"), score_english_likelihood("ab"));
+    assert_eq!(score_english_likelihood("Z"), score_english_likelihood("z"));
+    assert_eq!(
+        score_likelihood_with_profile("Hi", &LanguageProfile::english()),
+        score_english_likelihood("hI")
+    );
+}
+
+#[test]
+fn test_streaming_stats_matches_whole_text() {
+    let text = "It was the best of times, it was the worst of times, it was the age of wisdom.";
+    let mut stats = StreamingStats::new();
+    for chunk in ["It was the be", "st of times, it w", "as the worst of times,", " it was the age of wisdom."] {
+        stats.push(chunk);
+    }
+    let summary = stats.finish();
+
+    assert_eq!(summary.ic, calculate_ic(text));
+    assert_eq!(summary.chi_squared, score_english_likelihood(text));
+    assert_eq!(summary.alpha_count, get_alphabetic_chars(text).len());
+    assert_eq!(summary.char_count, text.chars().count());
+    assert_eq!(summary.word_count, text.split_whitespace().count());
+    assert_eq!(summary.letter_counts[(b'T' - b'A') as usize], 10);
+
+    let empty = StreamingStats::new().finish();
+    assert_eq!(empty.ic, None);
+    assert_eq!(empty.chi_squared, None);
+}
+
+#[test]
+fn test_randomness_indicator() {
+    let mut state: u64 = 12345;
+    let random: String = (0..200)
+        .map(|_| {
+            state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            (b'A' + ((state >> 33) % 26) as u8) as char
+        })
+        .collect();
+    let english = "It was the best of times, it was the worst of times, it was the age of wisdom, it was the age of foolishness, it was the epoch of belief, it was the epoch of incredulity, it was the season of Light";
+
+    assert_eq!(randomness_indicator(&random), RandomnessLevel::Likely);
+    assert_eq!(randomness_indicator(english), RandomnessLevel::Unlikely);
+    assert_eq!(randomness_indicator("XQZJ"), RandomnessLevel::Unlikely);
+}
+
+#[test]
+fn test_trigram_avg_is_length_independent() {
+    let short = "The meeting will start at noon in the main hall.";
+    let long = "It was the best of times, it was the worst of times, it was the age of wisdom, it was the age of foolishness, it was the epoch of belief, it was the epoch of incredulity.";
+
+    let summed_ratio = score_trigram_log_prob(long) / score_trigram_log_prob(short);
+    assert!(summed_ratio > 2.5, "summed scores should differ greatly, ratio {}", summed_ratio);
+
+    let (avg_short, avg_long) = (score_trigram_avg(short), score_trigram_avg(long));
+    assert!((avg_short - avg_long).abs() < 0.5, "averages {} vs {}", avg_short, avg_long);
+    assert_eq!(score_trigram_avg("ab"), -f64::INFINITY);
+}
+
+#[test]
+fn test_frequency_rank_mapping() {
+    let text = "XXXXXXXXXX QQQQQ ZZZ AB";
+    let mapping = frequency_rank_mapping(text);
+
+    assert_eq!(mapping[(b'X' - b'A') as usize], 'E');
+    assert_eq!(mapping[(b'Q' - b'A') as usize], 'T');
+    assert_eq!(mapping[(b'Z' - b'A') as usize], 'A');
+
+    let mut sorted = mapping;
+    sorted.sort();
+    assert_eq!(sorted.iter().collect::<String>(), "ABCDEFGHIJKLMNOPQRSTUVWXYZ");
+}
+
+#[test]
+fn test_column_shift_methods_agree() {
+    let plaintext_column = "THEREWASNOTHINGSOVERYREMARKABLEINTHATNORDIDALICETHINK";
+    let column = cipher_utils::shift_char_string(plaintext_column, 11);
+
+    let mic = find_top_n_caesar_shifts_mic(&column, 3, DEFAULT_MIN_CHARS_FOR_MIC).unwrap();
+    let chi2 = find_top_n_caesar_shifts_chi2(&column, 3, DEFAULT_MIN_CHARS_FOR_MIC).unwrap();
+    assert_eq!(mic[0].0, 11);
+    assert_eq!(chi2[0].0, 11);
+    assert_eq!(chi2.len(), 3);
+    assert!(chi2.windows(2).all(|pair| pair[0].1 <= pair[1].1));
+
+    assert!(find_top_n_caesar_shifts_chi2("ABC", 3, DEFAULT_MIN_CHARS_FOR_MIC).is_none());
+    assert!(find_top_n_caesar_shifts_chi2(&column, 0, DEFAULT_MIN_CHARS_FOR_MIC).is_none());
+}
+
+#[test]
+fn test_compute_metrics_consistent() {
+    let plaintext = "THEREWASNOTHINGSOVERYREMARKABLEINTHATNORDIDALICETHINKITSOVERYMUCHOUTOFTHEWAYTOHEARTHERABBITSAYTOITSELF";
+    let ciphertext = vigenere_encrypt(plaintext, "LEMON");
+
+    let metrics = compute_metrics(&ciphertext, &peekaboo::config::Config::default()).unwrap();
+    let ic = metrics.ic.unwrap();
+
+    assert_eq!(metrics.alpha_count, plaintext.len());
+    assert!((ic - calculate_ic(&ciphertext).unwrap()).abs() < 1e-12);
+    assert!((metrics.normalized_ic.unwrap() - ic * 26.0).abs() < 1e-9);
+    assert!((metrics.chi_squared - score_english_likelihood(&ciphertext).unwrap()).abs() < 1e-12);
+    assert!(metrics.entropy > 0.0 && metrics.entropy <= 26f64.log2());
+    assert!(metrics.top_kasiski_lengths.contains(&5));
+
+    // English-level IC means a one-letter key; an IC at or below random gives no estimate.
+    let plain_metrics = compute_metrics(plaintext, &peekaboo::config::Config::default()).unwrap();
+    assert!(plain_metrics.friedman_key_length.unwrap() < 2.0);
+    assert!(metrics.friedman_key_length.is_none_or(|len| len > plain_metrics.friedman_key_length.unwrap()));
+
+    assert!(compute_metrics("1234 !?", &peekaboo::config::Config::default()).is_none());
+}
+
+#[test]
+fn test_doubled_letter_ratio() {
+    let doubled = "Bookkeeping committee needs all small essays soon";
+    let ratio = doubled_letter_ratio(doubled).unwrap();
+    assert!(ratio > 0.15, "ratio was {}", ratio);
+
+    // Playfair digraphs never repeat a letter within a pair, and this sample has none across pairs either.
+    let playfair_like = "BMODZBXDNABEKUDMUIXMKOUVIF";
+    assert_eq!(doubled_letter_ratio(playfair_like), Some(0.0));
+
+    let shifted = cipher_utils::shift_char_string(doubled, 5);
+    assert_eq!(doubled_letter_ratio(&shifted), doubled_letter_ratio(doubled));
+    assert!(doubled_letter_ratio("A").is_none());
+}
+
+#[test]
+fn test_crib_drag_finds_key_fragment() {
+    let plaintext = "WE WILL ATTACK THE NORTHERN BRIDGE AT DAWN";
+    let ciphertext = vigenere_encrypt(&get_alphabetic_chars(plaintext), "SECRET");
+
+    // "ATTACK" starts at letter 6, which lines up with the start of the keyword again.
+    let drags = crib_drag(&ciphertext, "attack");
+    assert_eq!(drags.len(), ciphertext.len() - 5);
+    assert_eq!(drags[6], (6, "SECRET".to_string()));
+
+    // "BRIDGE" starts at letter 23, so the fragment begins at the key's last letter and wraps.
+    assert_eq!(crib_drag(&ciphertext, "BRIDGE")[23].1, "TSECRE");
+
+    assert!(crib_drag(&ciphertext, "").is_empty());
+    assert!(crib_drag("ABC", "ABCD").is_empty());
+}
+
+#[test]
+fn test_ic_by_period_table() {
+    let plaintext = "THEREWASNOTHINGSOVERYREMARKABLEINTHATNORDIDALICETHINKITSOVERYMUCHOUTOFTHEWAYTOHEARTHERABBITSAYTOITSELF";
+    let ciphertext = vigenere_encrypt(plaintext, "KEY");
+
+    let table = ic_by_period(&ciphertext, 8);
+    assert_eq!(table.len(), 8);
+    assert!(table.iter().enumerate().all(|(i, (period, _))| *period == i + 1));
+    assert!((table[0].1 - calculate_ic(&ciphertext).unwrap()).abs() < 1e-12);
+
+    // The key length and its multiples stand out above the rest.
+    let best = table.iter().max_by(|a, b| a.1.partial_cmp(&b.1).unwrap()).unwrap().0;
+    assert!(best.is_multiple_of(3));
+
+    assert!(ic_by_period(&ciphertext, 0).is_empty());
+}
+
+#[test]
+fn test_expected_ic_for_key_length() {
+    assert_eq!(expected_ic_for_key_length(1), ENGLISH_IC);
+    assert!(expected_ic_for_key_length(2) < ENGLISH_IC);
+    assert!(expected_ic_for_key_length(5) < expected_ic_for_key_length(2));
+    assert!((expected_ic_for_key_length(10_000) - RANDOM_IC).abs() < 1e-5);
+}
+
+#[test]
+fn test_rank_key_lengths_by_expected_ic_prefers_true_length() {
+    let plaintext = "ALICEWASBEGINNINGTOGETVERYTIREDOFSITTINGBYHERSISTERONTHEBANKANDOFHAVINGNOTHINGTODOONCEORTWICESHEHADPEEPEDINTOTHEBOOKHERSISTERWASREADINGBUTITHADNOPICTURESORCONVERSATIONSINIT";
+    let ciphertext = cipher_utils::apply_keystream(plaintext, "CRYPTO".bytes().map(|b| (b - b'A') as i8).cycle());
+    let rank_of_6 = |ranking: &[(usize, f64)]| ranking.iter().position(|(len, _)| *len == 6).unwrap();
+
+    // Length 6 columns overshoot English IC on this text, which the proximity ranking punishes.
+    let old = estimate_key_length_ic_periodicity(&ciphertext, 2, 20);
+    let new = rank_key_lengths_by_expected_ic(&ciphertext, 2, 20);
+    assert_eq!(new[0].0, 6);
+    assert!(rank_of_6(&new) < rank_of_6(&old), "old: {:?}, new: {:?}", old, new);
+}
+
+#[test]
+fn test_ic_over_custom_alphabet() {
+    let symbols = cipher_utils::Alphabet::new("+-*/=").unwrap();
+    // Counts [2, 2, 1, 0, 0]: (2*1 + 2*1) / (5*4); spaces fall outside the alphabet.
+    assert!((calculate_ic_in("+ + - - *", &symbols).unwrap() - 0.2).abs() < 1e-12);
+    let (freqs, total) = calculate_frequencies_in("+-*/=+", &symbols).unwrap();
+    assert_eq!(total, 6);
+    assert_eq!(freqs.len(), 5);
+    assert!((freqs[0] - 2.0 / 6.0).abs() < 1e-12);
+    assert!(calculate_ic_in("abc", &symbols).is_none());
+
+    let english = "It is a truth universally acknowledged that a single man in possession of a good fortune must be in want of a wife.";
+    let latin = cipher_utils::Alphabet::latin();
+    assert!((calculate_ic_in(english, &latin).unwrap() - calculate_ic(english).unwrap()).abs() < 1e-12);
+}
+
+#[test]
+fn test_contains_flag_pattern() {
+    assert_eq!(contains_flag_pattern("the answer is FLAG{abc} good luck").as_deref(), Some("FLAG{abc}"));
+    assert_eq!(contains_flag_pattern("picoCTF{r0t_13_is_fun}").as_deref(), Some("picoCTF{r0t_13_is_fun}"));
+    assert!(contains_flag_pattern("It is a truth universally acknowledged that a single man must be in want of a wife.").is_none());
+    assert!(contains_flag_pattern("a set {1, 2, 3} in braces").is_none());
+
+    // Only known wrappers count: a Caesar shift of "FLAG{abc}" must not look like a flag too.
+    assert_eq!(contains_flag_pattern("tjctf{w3lc0me}").as_deref(), Some("tjctf{w3lc0me}"));
+    assert!(contains_flag_pattern("IODJ{DEF}").is_none());
+    assert!(contains_flag_pattern("WORD{abc}").is_none());
+}
+
+#[test]
+fn test_sufficiency() {
+    let config = peekaboo::config::Config::default();
+
+    let short = sufficiency("WKHTXLFNEURZ", &config);
+    assert_eq!(short.alpha_count, 12);
+    assert!(short.caesar);
+    assert!(!short.vigenere_identification);
+    assert!(!short.vigenere_decode);
+    assert!(short.additional_letters_advised > 0);
+    assert!(short.advice().unwrap().contains("12 letters"));
+
+    let long = sufficiency(&"THEQUICKBROWNFOXJUMPSOVERTHELAZYDOG".repeat(6)[..200], &config);
+    assert_eq!(long.alpha_count, 200);
+    assert!(long.caesar && long.vigenere_identification && long.vigenere_decode);
+    assert_eq!(long.additional_letters_advised, 0);
+    assert!(long.advice().is_none());
+
+    for symbols in ["... --- ... / ... --- ...", "8 5 12 12 15 / 23 15 18 12 4", "2315313134 5234423114"] {
+        let report = sufficiency(symbols, &config);
+        assert!(report.symbol_encoded, "{}", symbols);
+        assert!(report.advice().is_none(), "{}", symbols);
+    }
+    assert!(!short.symbol_encoded);
+}
+
+#[test]
+fn test_estimate_key_lengths_degenerate_and_capped() {
+    assert!(estimate_key_lengths(&"A".repeat(5000), 3, 12).is_empty());
+
+    let settings = KasiskiSettings { min_seq_len: 3, max_key_len: 12, max_positions: 10 };
+    let alternating = "AB".repeat(2500);
+    let capped = estimate_key_lengths_with(&alternating, &settings);
+    assert_eq!(capped.first().map(|&(len, _)| len), Some(2));
+}
+
+#[test]
+fn test_mutual_ic_same_shift() {
+    let first = "It was the best of times it was the worst of times it was the age of wisdom";
+    let second = "Call me Ishmael some years ago never mind how long precisely having little money";
+    let same = mutual_ic(&cipher_utils::shift_char_string(first, 5), &cipher_utils::shift_char_string(second, 5)).unwrap();
+    let different = mutual_ic(&cipher_utils::shift_char_string(first, 5), &cipher_utils::shift_char_string(second, 12)).unwrap();
+    assert!(same > different, "same {} vs different {}", same, different);
+    assert!(same > 0.055);
+
+    assert_eq!(mutual_ic("123", second), None);
+    assert_eq!(mutual_ic(first, ""), None);
+}
+
+#[test]
+fn test_monoalphabetic_signature_thresholds() {
+    let plaintext = "It was the best of times it was the worst of times it was the age of wisdom it was the age of foolishness";
+    let ciphertext = substitution_encrypt(plaintext, SUBSTITUTION_KEY);
+    let ic = calculate_ic(&ciphertext).unwrap();
+    let chi2 = score_english_likelihood(&ciphertext).unwrap();
+
+    assert!(is_monoalphabetic_signature(ic, chi2, &peekaboo::config::Config::default()));
+    let strict_ic = peekaboo::config::Config::builder().mono_ic_min(ic + 0.001).build().unwrap();
+    assert!(!is_monoalphabetic_signature(ic, chi2, &strict_ic));
+    let strict_chi2 = peekaboo::config::Config::builder().mono_chi2_min(chi2 + 1.0).build().unwrap();
+    assert!(!is_monoalphabetic_signature(ic, chi2, &strict_chi2));
+}
+
+#[test]
+fn test_keyword_shift_round_trip() {
+    let shifts = vec![2, 17, 24, 15, 19, 14];
+    assert_eq!(keyword_to_shifts("CRYPTO"), Some(shifts.clone()));
+    assert_eq!(shifts_to_keyword(&shifts), "CRYPTO");
+    assert_eq!(keyword_to_shifts("crypto"), Some(shifts));
+    assert_eq!(shifts_to_keyword(&[0, 25, 26]), "AZA");
+
+    assert_eq!(keyword_to_shifts("CRY PTO"), None);
+    assert_eq!(keyword_to_shifts(""), None);
+}
+
+#[test]
+fn test_non_ascii_report() {
+    assert_eq!(non_ascii_report("café"), vec![('é', 1)]);
+    assert_eq!(non_ascii_report("naïve café — résumé"), vec![('é', 3), ('ï', 1), ('—', 1)]);
+    assert!(non_ascii_report("plain ASCII only!").is_empty());
+
+    assert_eq!(non_ascii_warning("café").as_deref(), Some("1 non-ASCII character ignored (é x1)"));
+    assert_eq!(non_ascii_warning("café"), non_ascii_warning("CAFé"));
+    assert_eq!(non_ascii_warning("cafe"), None);
+}
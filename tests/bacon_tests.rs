@@ -0,0 +1,57 @@
+use peekaboo::ciphers::bacon::BaconDecoder;
+use peekaboo::decoder::Decoder;
+use peekaboo::config::Config;
+
+
+fn bacon_encode(plaintext: &str, alphabet: &str) -> String {
+    plaintext
+        .chars()
+        .map(|c| {
+            let code = alphabet.find(c).unwrap();
+            (0..5).rev().map(|bit| if code >> bit & 1 == 1 { 'B' } else { 'A' }).collect::<String>()
+        })
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+#[test]
+fn test_bacon_ab_both_variants() {
+    let decoder = BaconDecoder::new(&Config::default());
+
+    let full = bacon_encode("HELLO", "ABCDEFGHIJKLMNOPQRSTUVWXYZ");
+    assert_eq!(full, "AABBB AABAA ABABB ABABB ABBBA");
+    let results = decoder.decrypt(&full);
+    assert!(results.iter().any(|r| r.plaintext == "HELLO" && r.key == "26-letter, A/B"));
+    assert_eq!(results[0].cipher_name, "Bacon");
+
+    let classic = bacon_encode("HELLO", "ABCDEFGHIKLMNOPQRSTUWXYZ");
+    let results = decoder.decrypt(&classic.to_lowercase());
+    assert!(results.iter().any(|r| r.plaintext == "HELLO" && r.key == "24-letter, A/B"));
+}
+
+#[test]
+fn test_bacon_letter_case() {
+    let decoder = BaconDecoder::new(&Config::default());
+    let cover = "the quick brown fox jumps over";
+    let mut bits = bacon_encode("HELLO", "ABCDEFGHIJKLMNOPQRSTUVWXYZ").replace(' ', "").into_bytes().into_iter();
+    let hidden: String = cover
+        .chars()
+        .map(|c| match c.is_ascii_alphabetic().then(|| bits.next()).flatten() {
+            Some(b'B') => c.to_ascii_uppercase(),
+            _ => c,
+        })
+        .collect();
+
+    let results = decoder.decrypt(&hidden);
+    assert!(results.iter().any(|r| r.plaintext == "HELLO" && r.key == "26-letter, lowercase=A"));
+}
+
+#[test]
+fn test_bacon_declines_ordinary_text() {
+    let decoder = BaconDecoder::new(&Config::default());
+
+    assert!(decoder.decrypt("The quick brown fox jumps over the lazy dog.").is_empty());
+    assert!(decoder.decrypt("KHOOR ZRUOG").is_empty());
+    assert!(decoder.decrypt("AABB").is_empty());
+    assert!(decoder.decrypt("").is_empty());
+}
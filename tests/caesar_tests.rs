@@ -1,6 +1,6 @@
 use peekaboo::ciphers::caesar::{CaesarIdentifier, CaesarDecoder};
-use peekaboo::identifier::Identifier;
-use peekaboo::decoder::Decoder;
+use peekaboo::identifier::{CipherParams, Identifier};
+use peekaboo::decoder::{Decoder, ScoringMethod};
 use peekaboo::config::Config;
 use peekaboo::cipher_utils;
 
@@ -131,3 +131,131 @@ fn test_caesar_hardcoded() {
     assert_eq!(results3[0].key, shift3_pos.to_string());
     assert_eq!(results3[0].plaintext, plaintext, "Shift -5 failed"); // Direct string compare
 }
+
+#[test]
+fn test_caesar_decrypt_with_key() {
+    let config = Config::default();
+    let decoder = CaesarDecoder::new(&config);
+    let ciphertext = "Wkh txlfn eurzq grj mxpsv ryhu wkh odcb ira";
+
+    let attempt = decoder.decrypt_with_key(ciphertext, "3").expect("Known-key Caesar failed");
+    assert_eq!(attempt.cipher_name, "Caesar");
+    assert_eq!(attempt.key, "3");
+    assert_eq!(attempt.plaintext, "The quick brown dog jumps over the lazy fox");
+    assert_eq!(attempt.score, peekaboo::analysis::score_english_likelihood(&attempt.plaintext).unwrap());
+
+    assert!(decoder.decrypt_with_key(ciphertext, "abc").is_none());
+}
+
+#[test]
+fn test_caesar_identify_all() {
+    let config = Config::default();
+    let identifier = CaesarIdentifier::new(&config);
+    let plaintext = "This is a fairly standard sentence for testing purposes";
+    let shift = 8i8;
+    let ciphertext = cipher_utils::shift_char_string(plaintext, shift);
+
+    let results = identifier.identify_all(&ciphertext);
+    assert!(results.len() > 1);
+    assert_eq!(results[0].parameters, Some(format!("Potential Shift: {}", shift)));
+    assert_eq!(Some(results[0].clone()), identifier.identify(&ciphertext));
+    assert!(results.windows(2).all(|w| w[0].confidence_score <= w[1].confidence_score));
+    assert!(results.iter().all(|r| r.cipher_name == "Caesar"));
+
+    assert!(identifier.identify_all("123 !@#").is_empty());
+}
+
+#[test]
+fn test_caesar_bigram_scoring_for_short_text() {
+    let ciphertext = cipher_utils::shift_char_string("Why not you", 3);
+
+    let default_decoder = CaesarDecoder::new(&Config::default());
+    let default_results = default_decoder.decrypt(&ciphertext);
+    assert_eq!(default_decoder.scoring_method_for(&default_results), ScoringMethod::ChiSquared);
+
+    let config = Config {
+        bigram_scoring_max_len: 15,
+        ..Default::default()
+    };
+    let decoder = CaesarDecoder::new(&config);
+    let results = decoder.decrypt(&ciphertext);
+    assert_eq!(decoder.scoring_method_for(&results), ScoringMethod::Bigram);
+    assert_eq!(results[0].key, "3");
+    assert_eq!(results[0].plaintext, "Why not you");
+    assert_eq!(results[0].score, peekaboo::analysis::score_bigram_log_prob("Why not you"));
+    assert!(results.windows(2).all(|pair| pair[0].score >= pair[1].score));
+    assert_eq!(decoder.decrypt_with_key(&ciphertext, "3").unwrap().score, results[0].score);
+}
+
+#[test]
+fn test_caesar_decrypt_shifts_subset() {
+    let config = Config::default();
+    let decoder = CaesarDecoder::new(&config);
+    let ciphertext = cipher_utils::shift_char_string("Meet me by the old oak tree at noon.", 10);
+
+    let results = decoder.decrypt_shifts(&ciphertext, &[3, 10, 21]);
+    assert_eq!(results.len(), 3);
+    assert_eq!(results[0].key, "10");
+    assert_eq!(results[0].plaintext, "Meet me by the old oak tree at noon.");
+    assert!(results.windows(2).all(|pair| pair[0].score <= pair[1].score));
+
+    let mut keys: Vec<&str> = results.iter().map(|r| r.key.as_str()).collect();
+    keys.sort();
+    assert_eq!(keys, vec!["10", "21", "3"]);
+
+    let all_results = decoder.decrypt(&ciphertext);
+    for result in &results {
+        let full = all_results.iter().find(|r| r.key == result.key).unwrap();
+        assert_eq!(full.score, result.score);
+    }
+}
+
+#[test]
+fn test_caesar_identification_early_exit() {
+    let plaintext = "It was a bright cold day in April, and the clocks were striking thirteen. Winston Smith slipped quickly through the glass doors.";
+    let config = Config {
+        caesar_strong_match_chi2: Some(0.5),
+        ..Default::default()
+    };
+    let identifier = CaesarIdentifier::new(&config);
+
+    let early = identifier.identify(plaintext).unwrap();
+    let full = identifier.identify_full_search(plaintext).unwrap();
+    assert_eq!(early.parameters.as_deref(), Some("Potential Shift: 0"));
+    assert_eq!(early, full);
+
+    // With no shift strong enough to stop early, the result still matches the full search.
+    let ciphertext = cipher_utils::shift_char_string(plaintext, 7);
+    let strict = CaesarIdentifier::new(&Config { caesar_strong_match_chi2: Some(1e-9), ..Default::default() });
+    assert_eq!(strict.identify(&ciphertext), strict.identify_full_search(&ciphertext));
+    assert_eq!(strict.identify(&ciphertext).unwrap().parameters.as_deref(), Some("Potential Shift: 7"));
+}
+
+#[test]
+fn test_caesar_typed_shift_matches_display() {
+    let identifier = CaesarIdentifier::new(&Config::default());
+    for shift in [0u8, 4, 19] {
+        let ciphertext = cipher_utils::shift_char_string("Typed parameters save callers from parsing display strings", shift as i8);
+        let result = identifier.identify(&ciphertext).unwrap();
+
+        assert_eq!(result.params, CipherParams::Caesar { shift });
+        assert_eq!(result.parameters, Some(format!("Potential Shift: {}", shift)));
+    }
+}
+
+#[test]
+fn test_caesar_decrypt_table() {
+    let plaintext = "The quick brown fox jumps over the lazy dog while the farmer watches from the porch.";
+    let ciphertext = cipher_utils::shift_char_string(plaintext, 7);
+    let decoder = CaesarDecoder::new(&Config::default());
+
+    let table = decoder.decrypt_table(&ciphertext);
+    assert_eq!(table.len(), 26);
+    for (shift, attempt) in table.iter().enumerate() {
+        assert_eq!(attempt.key, shift.to_string());
+    }
+
+    let best = &decoder.decrypt(&ciphertext)[0];
+    assert_eq!(best.key, "7");
+    assert_eq!(&table[7], best);
+}
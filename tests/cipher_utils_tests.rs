@@ -25,3 +25,123 @@ fn test_shift_char_string_test() {
     assert_eq!(shift_char_string("Test 123", 5), "Yjxy 123");
     assert_eq!(shift_char_string("", 5), "");
 }
+
+#[test]
+fn test_limit_candidate_combinations() {
+    let mut columns = vec![vec![1, 2, 3], vec![4, 5, 6], vec![7, 8, 9]];
+    limit_candidate_combinations(&mut columns, 8);
+    assert!(columns.iter().map(|c| c.len()).product::<usize>() <= 8);
+    assert!(columns.iter().all(|c| !c.is_empty()));
+    assert_eq!(columns[0][0], 1);
+
+    let mut untouched = vec![vec![1, 2], vec![3, 4]];
+    limit_candidate_combinations(&mut untouched, 100);
+    assert_eq!(untouched, vec![vec![1, 2], vec![3, 4]]);
+
+    let mut singletons = vec![vec![1], vec![2]];
+    limit_candidate_combinations(&mut singletons, 0);
+    assert_eq!(singletons, vec![vec![1], vec![2]]);
+}
+
+#[test]
+fn test_shift_char_in_reversed_alphabet() {
+    let mut reversed = STANDARD_ALPHABET;
+    reversed.reverse();
+
+    assert_eq!(shift_char_in_alphabet('Z', 1, &reversed), 'Y');
+    assert_eq!(shift_char_in_alphabet('B', 1, &reversed), 'A');
+    assert_eq!(shift_char_in_alphabet('A', 1, &reversed), 'Z');
+    assert_eq!(shift_char_in_alphabet('Z', -1, &reversed), 'A');
+    assert_eq!(shift_char_in_alphabet('c', 2, &reversed), 'a');
+    assert_eq!(shift_char_in_alphabet('M', 26, &reversed), 'M');
+    assert_eq!(shift_char_in_alphabet('!', 3, &reversed), '!');
+}
+
+#[test]
+fn test_shift_char_in_standard_alphabet_matches_shift_char() {
+    for c in ('A'..='Z').chain('a'..='z') {
+        for shift in [-27i8, -3, 0, 5, 25] {
+            assert_eq!(shift_char_in_alphabet(c, shift, &STANDARD_ALPHABET), shift_char(c, shift));
+        }
+    }
+}
+
+#[test]
+fn test_hamming_distance() {
+    assert_eq!(hamming_distance(b"this is a test", b"wokka wokka!!!"), Some(37));
+    assert_eq!(hamming_distance(b"same", b"same"), Some(0));
+    assert_eq!(hamming_distance(b"", b""), Some(0));
+    assert_eq!(hamming_distance(b"short", b"longer"), None);
+}
+
+#[test]
+fn test_normalize_input_line_endings() {
+    assert_eq!(normalize_input("a\r\nb"), "a\nb");
+    assert_eq!(normalize_input("a\rb\nc"), "a\nb\nc");
+
+    let stats = peekaboo::text_stats::calculate_basic_stats(&normalize_input("a\r\nb")).unwrap();
+    assert_eq!(stats, peekaboo::text_stats::calculate_basic_stats("a\nb").unwrap());
+    assert_eq!(stats.char_count_total, 3);
+    assert_eq!(stats.char_count_whitespace, 1);
+    assert_eq!(stats.char_count_other, 0);
+}
+
+#[test]
+fn test_collapse_whitespace() {
+    assert_eq!(collapse_whitespace("  KHOOR \n\n  ZRUOG\t"), "KHOOR ZRUOG");
+}
+
+#[test]
+fn test_shift_char36() {
+    assert_eq!(shift_char36('9', 1), 'A');
+    assert_eq!(shift_char36('Z', 1), '0');
+    assert_eq!(shift_char36('A', -1), '9');
+    assert_eq!(shift_char36('3', 4), '7');
+    assert_eq!(shift_char36('8', 30), '2');
+    assert_eq!(shift_char36('y', 3), '1');
+    assert_eq!(shift_char36('a', 2), 'c');
+    assert_eq!(shift_char36('-', 5), '-');
+}
+
+#[test]
+fn test_shift_char36_string_roundtrip() {
+    let text = "AGENT 007 MEETS AT 9";
+    let shifted = shift_char36_string(text, 11);
+    assert_eq!(shifted, "LRPY4 BBI XPP43 L4 K");
+    assert_eq!(shift_char36_string(&shifted, -11), text);
+}
+
+#[test]
+fn test_apply_keystream_vigenere_vector() {
+    let keystream = "LEMON".bytes().map(|b| -((b - b'A') as i8)).cycle();
+    assert_eq!(apply_keystream("LXFOPVEFRNHR", keystream), "ATTACKATDAWN");
+
+    // Punctuation doesn't consume key letters, and an exhausted keystream leaves the rest alone.
+    assert_eq!(apply_keystream("Lx, Fo!", [-11i8, -4].into_iter()), "At, Fo!");
+}
+
+#[test]
+fn test_restore_layout_matches_full_decryption() {
+    let ciphertext = "Lxf, OPV-efr nhr!";
+    let keystream = || "LEMON".bytes().map(|b| -((b - b'A') as i8)).cycle();
+    let letters = apply_keystream("LXFOPVEFRNHR", keystream());
+    assert_eq!(restore_layout(ciphertext, &letters), apply_keystream(ciphertext, keystream()));
+    assert_eq!(restore_layout(ciphertext, &letters), "Att, ACK-atd awn!");
+
+    // Template letters beyond the supplied ones are copied unchanged.
+    assert_eq!(restore_layout("Ab, cd", "XY"), "Xy, cd");
+}
+
+#[test]
+fn test_shift_char_in_custom_alphabet() {
+    let russian = Alphabet::new("АБВГДЕЁЖЗИЙКЛМНОПРСТУФХЦЧШЩЪЫЬЭЮЯ").unwrap();
+    assert_eq!(russian.size(), 33);
+    assert_eq!(shift_char_in('Я', 1, &russian), 'А');
+    assert_eq!(shift_char_in('б', -1, &russian), 'а');
+    assert_eq!(shift_char_in('Q', 5, &russian), 'Q');
+
+    let latin = Alphabet::latin();
+    assert_eq!(shift_char_in('y', 3, &latin), shift_char('y', 3));
+    assert!(Alphabet::new("AaB").is_none());
+    assert!(Alphabet::new("A").is_none());
+}
@@ -0,0 +1,47 @@
+// Fixtures shared by the integration tests; each test crate uses only some of them.
+
+use peekaboo::cipher_utils;
+
+
+#[allow(dead_code)]
+pub fn vigenere_encrypt(plaintext: &str, keyword: &str) -> String {
+    if keyword.is_empty() || !keyword.chars().all(|c| c.is_ascii_alphabetic()) {
+        return plaintext.to_string();
+    }
+    let keyword_bytes = keyword.to_ascii_uppercase().into_bytes();
+    let key_len = keyword_bytes.len();
+    let mut key_index = 0;
+    let mut ciphertext = String::with_capacity(plaintext.len());
+
+    for p in plaintext.chars() {
+        if p.is_ascii_alphabetic() {
+            let key_byte = keyword_bytes[key_index % key_len];
+            let key_shift = (key_byte - b'A') as i8;
+            let encrypted_char = cipher_utils::shift_char(p, key_shift);
+            ciphertext.push(encrypted_char);
+            key_index += 1;
+        } else {
+            ciphertext.push(p);
+        }
+    }
+    ciphertext
+}
+
+#[allow(dead_code)]
+pub const SUBSTITUTION_KEY: &str = "QWERTYUIOPASDFGHJKLZXCVBNM";
+
+#[allow(dead_code)]
+pub fn substitution_encrypt(plaintext: &str, key: &str) -> String {
+    let key_bytes = key.as_bytes();
+    plaintext
+        .chars()
+        .map(|p| {
+            if p.is_ascii_alphabetic() {
+                let c = key_bytes[(p.to_ascii_uppercase() as u8 - b'A') as usize] as char;
+                if p.is_ascii_lowercase() { c.to_ascii_lowercase() } else { c }
+            } else {
+                p
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,58 @@
+use peekaboo::config::{Config, ConfigError};
+
+
+#[test]
+fn test_config_builder_valid_settings() {
+    let config = Config::builder()
+        .vigenere_min_dec_len(10)
+        .min_chars_for_mic(3)
+        .random_seed(7)
+        .build()
+        .unwrap();
+
+    assert_eq!(config.vigenere_min_dec_len, 10);
+    assert_eq!(config.min_chars_for_mic, 3);
+    assert_eq!(config.random_seed, Some(7));
+    assert_eq!(config.vigenere_min_id_len, Config::default().vigenere_min_id_len);
+    assert!(Config::default().validate().is_ok());
+}
+
+#[test]
+fn test_config_builder_rejects_zero_minimum_length() {
+    let result = Config::builder().vigenere_min_dec_len(0).build();
+    assert_eq!(result.unwrap_err(), ConfigError::ZeroValue { field: "vigenere_min_dec_len" });
+
+    assert!(Config::builder().min_chars_for_mic(0).build().is_err());
+}
+
+#[test]
+fn test_config_builder_rejects_absurd_threshold() {
+    let err = Config::builder().vigenere_ic_upper_threshold(1.5).build().unwrap_err();
+    assert!(matches!(err, ConfigError::OutOfRange { field: "vigenere_ic_upper_threshold", .. }));
+    assert!(err.to_string().contains("vigenere_ic_upper_threshold"));
+
+    assert!(Config::builder().vigenere_ic_upper_threshold(0.0).build().is_err());
+}
+
+#[test]
+fn test_config_rejects_unknown_cipher_names() {
+    let err = Config::builder().enabled_cipher("Caesar").enabled_cipher("Vigener").build().unwrap_err();
+    assert_eq!(err, ConfigError::UnknownCipher { name: "Vigener".to_string() });
+    assert!(err.to_string().contains("Vigenere"));
+
+    assert!(Config::builder().enabled_cipher("xor").enabled_cipher("KEYED CAESAR").build().is_ok());
+    assert!(Config { enabled_ciphers: Some(vec!["Rot13".to_string()]), ..Default::default() }.validate().is_err());
+}
+
+#[test]
+fn test_config_kasiski_limits_split_identify_and_decode() {
+    let config = Config::default();
+    assert_eq!(config.kasiski_max_key_len, 20);
+    assert_eq!(config.kasiski_max_dec_key_len, 12);
+    assert_eq!(config.max_key_combinations, None);
+
+    let capped = Config::builder().kasiski_max_dec_key_len(15).max_key_combinations(500).build().unwrap();
+    assert_eq!(capped.kasiski_max_dec_key_len, 15);
+    assert_eq!(capped.max_key_combinations, Some(500));
+    assert!(Config::builder().kasiski_max_dec_key_len(0).build().is_err());
+}
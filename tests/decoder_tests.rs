@@ -0,0 +1,194 @@
+use peekaboo::ciphers::a1z26::A1Z26Decoder;
+use peekaboo::ciphers::caesar::CaesarDecoder;
+use peekaboo::ciphers::gronsfeld::GronsfeldDecoder;
+use peekaboo::ciphers::keyed_caesar::KeyedCaesarDecoder;
+use peekaboo::ciphers::morse::MorseDecoder;
+use peekaboo::ciphers::polybius::PolybiusDecoder;
+use peekaboo::ciphers::substitution::SubstitutionDecoder;
+use peekaboo::ciphers::vigenere::VigenereDecoder;
+use peekaboo::ciphers::xor::XorDecoder;
+use peekaboo::config::Config;
+use peekaboo::decoder::{decrypt_outcome_with_reversal, sort_best_first, Decoder, DecryptionAttempt, FallbackDecoder, ScoringMethod};
+
+mod common;
+use common::{substitution_encrypt, SUBSTITUTION_KEY};
+
+
+#[test]
+fn test_builtin_decoder_scoring_methods() {
+    let config = Config::default();
+    let decoders: Vec<(Box<dyn Decoder>, ScoringMethod)> = vec![
+        (Box::new(CaesarDecoder::new(&config)), ScoringMethod::ChiSquared),
+        (Box::new(VigenereDecoder::new(&config)), ScoringMethod::Trigram),
+        (Box::new(GronsfeldDecoder::new(&config)), ScoringMethod::Trigram),
+        (Box::new(KeyedCaesarDecoder::new(&config)), ScoringMethod::Trigram),
+        (Box::new(MorseDecoder::new(&config)), ScoringMethod::ChiSquared),
+        (Box::new(PolybiusDecoder::new(&config)), ScoringMethod::ChiSquared),
+        (Box::new(A1Z26Decoder::new(&config)), ScoringMethod::ChiSquared),
+        (Box::new(SubstitutionDecoder::new(&config)), ScoringMethod::Trigram),
+        (Box::new(XorDecoder::new(&config)), ScoringMethod::ChiSquared),
+    ];
+
+    for (decoder, expected) in decoders {
+        assert_eq!(decoder.scoring_method(), expected, "decoder {}", decoder.name());
+    }
+}
+
+#[test]
+fn test_scoring_method_direction() {
+    assert!(!ScoringMethod::ChiSquared.higher_is_better());
+    assert!(ScoringMethod::Trigram.higher_is_better());
+    assert!(ScoringMethod::Quadgram.higher_is_better());
+    assert!(ScoringMethod::Bigram.higher_is_better());
+}
+
+#[test]
+fn test_reversed_ciphertext_recovered_only_when_enabled() {
+    let plaintext = "Meet me by the old oak tree at midnight and bring the map";
+    let reversed: String = plaintext.chars().rev().collect();
+    let ciphertext = peekaboo::cipher_utils::shift_char_string(&reversed, 7);
+    let decoder = CaesarDecoder::new(&Config::default());
+
+    let forward = decrypt_outcome_with_reversal(&decoder, &ciphertext, false).into_attempts();
+    assert_ne!(forward[0].plaintext, plaintext);
+
+    let config = Config::builder().try_reversed(true).build().unwrap();
+    let both = decrypt_outcome_with_reversal(&decoder, &ciphertext, config.try_reversed).into_attempts();
+    assert_eq!(both[0].plaintext, plaintext);
+    assert_eq!(both[0].key, "7");
+    assert!(both[0].note.as_deref().is_some_and(|note| note.contains("reversed")));
+}
+
+#[test]
+fn test_skip_reason_leaves_dot_dash_input_to_morse() {
+    let config = Config::default();
+    let morse_text = "... --- ... / ... --- ...";
+    let letters = "Khoor zruog";
+    let caesar = CaesarDecoder::new(&config);
+    let morse = MorseDecoder::new(&config);
+
+    assert!(caesar.skip_reason(morse_text).is_some());
+    assert!(caesar.skip_reason(letters).is_none());
+    assert!(morse.skip_reason(morse_text).is_none());
+    assert!(morse.skip_reason(letters).is_some());
+
+    let fallback = FallbackDecoder::new(Box::new(CaesarDecoder::new(&config)), Box::new(MorseDecoder::new(&config)), 1.0);
+    assert!(fallback.skip_reason(morse_text).is_none());
+    assert_eq!(fallback.decrypt(morse_text)[0].cipher_name, "Morse");
+}
+
+#[test]
+fn test_min_ciphertext_len_metadata() {
+    let config = Config::builder().vigenere_min_dec_len(42).build().unwrap();
+
+    assert_eq!(VigenereDecoder::new(&config).min_ciphertext_len(), 42);
+    assert_eq!(GronsfeldDecoder::new(&config).min_ciphertext_len(), 42);
+    assert_eq!(CaesarDecoder::new(&config).min_ciphertext_len(), 0);
+    assert_eq!(
+        VigenereDecoder::new(&Config::default()).min_ciphertext_len(),
+        Config::default().vigenere_min_dec_len
+    );
+}
+
+fn scored(cipher_name: &str, key: &str, score: f64) -> DecryptionAttempt {
+    DecryptionAttempt {
+        cipher_name: cipher_name.to_string(),
+        key: key.to_string(),
+        plaintext: String::new(),
+        score,
+        note: None,
+        solver_stats: None,
+    }
+}
+
+#[test]
+fn test_sort_best_first_respects_decoder_direction() {
+    let config = Config::default();
+    let caesar_method = CaesarDecoder::new(&config).scoring_method();
+    let vigenere_method = VigenereDecoder::new(&config).scoring_method();
+    let caesar_best = scored("Caesar", "3", 0.05);
+    let vigenere_best = scored("Vigenere", "KEY", -120.0);
+    assert!(caesar_best.better_than(&scored("Caesar", "4", 1.8), caesar_method));
+    assert!(vigenere_best.better_than(&scored("Vigenere", "KEX", -400.0), vigenere_method));
+    assert!(!caesar_best.better_than(&vigenere_best, caesar_method));
+    assert!(!vigenere_best.better_than(&caesar_best, vigenere_method));
+
+    let mut caesar = vec![scored("Caesar", "4", 1.8), scored("Caesar", "9", 0.9), caesar_best.clone()];
+    let mut reordered = caesar.clone();
+    reordered.reverse();
+    sort_best_first(&mut caesar, caesar_method);
+    sort_best_first(&mut reordered, caesar_method);
+    let keys: Vec<&str> = caesar.iter().map(|attempt| attempt.key.as_str()).collect();
+    assert_eq!(keys, ["3", "9", "4"]);
+    assert_eq!(caesar, reordered);
+
+    let mut vigenere = vec![scored("Vigenere", "KEX", -400.0), vigenere_best.clone()];
+    sort_best_first(&mut vigenere, vigenere_method);
+    assert_eq!(vigenere[0], vigenere_best);
+}
+
+#[test]
+fn test_fallback_decoder_switches_on_poor_score() {
+    let config = Config { random_seed: Some(7), ..Default::default() };
+    let plaintext = "It was the best of times it was the worst of times it was the age of wisdom it was the age of foolishness it was the epoch of belief it was the epoch of incredulity";
+    let ciphertext = substitution_encrypt(plaintext, SUBSTITUTION_KEY);
+    let fallback = || FallbackDecoder::new(Box::new(CaesarDecoder::new(&config)), Box::new(SubstitutionDecoder::new(&config)), 1.0);
+
+    let caesar_best = CaesarDecoder::new(&config).decrypt(&ciphertext).remove(0);
+    assert!(caesar_best.score > 1.0);
+    let results = fallback().decrypt(&ciphertext);
+    assert_eq!(results[0].cipher_name, "Substitution");
+    assert_eq!(fallback().scoring_method_for(&results), ScoringMethod::Trigram);
+    assert!(peekaboo::analysis::score_trigram_avg(&results[0].plaintext) > peekaboo::analysis::score_trigram_avg(&caesar_best.plaintext));
+
+    let caesar_text = peekaboo::cipher_utils::shift_char_string(plaintext, 3);
+    let caesar_results = fallback().decrypt(&caesar_text);
+    assert_eq!(caesar_results[0].cipher_name, "Caesar");
+    assert_eq!(fallback().scoring_method_for(&caesar_results), ScoringMethod::ChiSquared);
+}
+
+// Scores each orientation with a different cipher name and method, like a fallback that switched decoders.
+struct SwitchingDecoder;
+
+impl Decoder for SwitchingDecoder {
+    fn decrypt(&self, ciphertext: &str) -> Vec<DecryptionAttempt> {
+        let forward = ciphertext.starts_with("the");
+        let mut attempt = scored(if forward { "Caesar" } else { "Substitution" }, "", if forward { 5.0 } else { -10.0 });
+        attempt.plaintext = ciphertext.to_string();
+        vec![attempt]
+    }
+
+    fn name(&self) -> &'static str {
+        "Switching"
+    }
+
+    fn description(&self) -> &'static str {
+        "Test decoder"
+    }
+
+    fn scoring_method(&self) -> ScoringMethod {
+        ScoringMethod::ChiSquared
+    }
+
+    fn scoring_method_for(&self, attempts: &[DecryptionAttempt]) -> ScoringMethod {
+        match attempts.first() {
+            Some(best) if best.cipher_name == "Substitution" => ScoringMethod::Trigram,
+            _ => ScoringMethod::ChiSquared,
+        }
+    }
+}
+
+#[test]
+fn test_reversal_does_not_merge_scores_from_different_decoders() {
+    let text = "the quick brown fox jumps over the lazy dog";
+    let results = decrypt_outcome_with_reversal(&SwitchingDecoder, text, true).into_attempts();
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].cipher_name, "Caesar");
+    assert_eq!(results[0].plaintext, text);
+
+    let reversed: String = text.chars().rev().collect();
+    let results = decrypt_outcome_with_reversal(&SwitchingDecoder, &reversed, true).into_attempts();
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].plaintext, text);
+    assert!(results[0].note.as_deref().is_some_and(|note| note.contains("reversed")));
+}
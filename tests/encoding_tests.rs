@@ -0,0 +1,36 @@
+use peekaboo::ciphers::encoding::{detect_and_decode, encode_base64, encode_hex};
+
+#[test]
+fn test_detect_base64() {
+    let (decoded, scheme) = detect_and_decode("V2toIHR4bGZuIGV1cnpxIGdyag==").expect("Base64 not detected");
+    assert_eq!(scheme, "Base64");
+    assert_eq!(decoded, "Wkh txlfn eurzq grj");
+
+    let (decoded, scheme) = detect_and_decode("QVRUQUNLQVREQVdO").unwrap();
+    assert_eq!(scheme, "Base64");
+    assert_eq!(decoded, "ATTACKATDAWN");
+}
+
+#[test]
+fn test_detect_hex() {
+    let (decoded, scheme) = detect_and_decode("4c58464f50564546524e4852").expect("Hex not detected");
+    assert_eq!(scheme, "Hex");
+    assert_eq!(decoded, "LXFOPVEFRNHR");
+}
+
+#[test]
+fn test_detect_rejects_plain_ciphertext() {
+    assert!(detect_and_decode("LXFOPVEFRNHR").is_none());
+    assert!(detect_and_decode("Wkh txlfn eurzq grj").is_none());
+    assert!(detect_and_decode("abc").is_none());
+    assert!(detect_and_decode("").is_none());
+}
+
+#[test]
+fn test_encode_base64_padding() {
+    assert_eq!(encode_base64(b"Man"), "TWFu");
+    assert_eq!(encode_base64(b"Ma"), "TWE=");
+    assert_eq!(encode_base64(b"M"), "TQ==");
+    assert_eq!(encode_base64(b""), "");
+    assert_eq!(encode_hex(&[0x00, 0xab, 0xff]), "00abff");
+}
@@ -0,0 +1,341 @@
+use peekaboo::cipher_utils;
+use peekaboo::ciphers::encoding::{encode_base64, encode_hex};
+use peekaboo::ciphers::caesar::CaesarDecoder;
+use peekaboo::ciphers::vigenere::VigenereDecoder;
+use peekaboo::decoder::{Decoder, FallbackDecoder};
+use peekaboo::config::Config;
+use peekaboo::identifier::{CipherParams, IdentificationResult, Identifier};
+use peekaboo::engine::{analyze, analyze_batch, available_decoders, available_identifiers, best_plaintext, cipher_names, available_preprocessors, analyze_with_cache, explain_attempt, identify_with_reversal, is_too_short, preprocess, AnalysisCache, PLAINTEXT_LABEL};
+use std::sync::Arc;
+
+mod common;
+use common::{substitution_encrypt, vigenere_encrypt, SUBSTITUTION_KEY};
+
+
+#[test]
+fn test_analyze_batch_reports_per_input() {
+    let caesar = cipher_utils::shift_char_string("Meet me by the old oak tree at midnight and bring the map", 3);
+    let vigenere = vigenere_encrypt(
+        "It is a truth universally acknowledged that a single man in possession of a good fortune must be in want of a wife. However little known the feelings or views of such a man may be on his first entering a neighbourhood, this truth is so well fixed in the minds of the surrounding families.",
+        "LEMON",
+    );
+    let plaintext = "This is a reasonably long sentence in English which should hopefully get a fairly low chi squared score when compared against standard letter frequencies";
+
+    // Capping the keywords per key length keeps the Vigenere and Gronsfeld passes quick.
+    let config = Config::builder().max_key_combinations(20000).build().unwrap();
+    let reports = analyze_batch(&[&caesar, &vigenere, plaintext], &config);
+
+    assert_eq!(reports.len(), 3);
+    assert_eq!(reports[0].input, caesar);
+    assert_eq!(reports[0].top_cipher(), Some("Caesar"));
+    assert_eq!(reports[0].best_guess.as_ref().unwrap().attempt.key, "3");
+    assert_eq!(reports[1].top_cipher(), Some("Vigenere"));
+    assert_eq!(reports[1].best_guess.as_ref().unwrap().attempt.key, "LEMON");
+    assert_eq!(reports[2].top_cipher(), Some(PLAINTEXT_LABEL));
+}
+
+#[test]
+fn test_analyze_rejects_tiny_input() {
+    let report = analyze("x", &Config::default());
+    assert!(report.too_short);
+    assert!(report.identifications.is_empty());
+    assert!(report.decoder_results.is_empty());
+    assert_eq!(report.top_cipher(), None);
+
+    // Letter-free cipher formats are not too short.
+    assert!(!analyze("8-5-12-12-15", &Config::default()).too_short);
+    assert!(is_too_short("  ?! "));
+}
+
+#[test]
+fn test_builtin_ciphers_have_descriptions() {
+    let config = Config::default();
+    for decoder in available_decoders(&config) {
+        assert!(!decoder.description().is_empty(), "{} has no description", decoder.name());
+    }
+    for identifier in available_identifiers(&config) {
+        assert!(!identifier.description().is_empty());
+    }
+}
+
+#[test]
+fn test_explain_caesar_attempt() {
+    let plaintext = "The quick brown fox jumps over the lazy dog while the farmer watches from the porch.";
+    let ciphertext = cipher_utils::shift_char_string(plaintext, 3);
+    let config = Config::default();
+    let decoder = CaesarDecoder::new(&config);
+    let best = &decoder.decrypt(&ciphertext)[0];
+
+    let explanation = explain_attempt(&ciphertext, best, decoder.scoring_method(), &config);
+    assert!(explanation[0].starts_with("Ciphertext IC is"));
+    let shift_line = explanation.iter().find(|line| line.contains("chi-squared")).unwrap();
+    assert!(shift_line.contains("shift 3"), "{}", shift_line);
+    assert!(shift_line.contains(&format!("{:.4}", best.score)));
+}
+
+#[test]
+fn test_analyze_promotes_crib_match() {
+    let plaintext = "It is a truth universally acknowledged that a single man in possession of a good fortune must be in want of a wife.";
+    let ciphertext = cipher_utils::shift_char_string(plaintext, 3);
+
+    let report = analyze(&ciphertext, &Config::builder().max_key_combinations(20000).build().unwrap());
+    assert_eq!(report.best_guess.as_ref().unwrap().attempt.plaintext, plaintext);
+
+    // Key 24 shifts the plaintext by 5, turning "truth" into "ywzym": a worse-scoring candidate.
+    let config = Config::builder().known_plaintext_crib("ywzym").max_key_combinations(20000).build().unwrap();
+    let guess = analyze(&ciphertext, &config).best_guess.unwrap();
+    assert_eq!(guess.cipher_name, "Caesar");
+    assert_eq!(guess.attempt.key, "24");
+    assert_eq!(guess.matched_crib.as_deref(), Some("YWZYM"));
+    let decoder = CaesarDecoder::new(&config);
+    let caesar = decoder.decrypt(&ciphertext);
+    assert!(caesar[0].better_than(&guess.attempt, decoder.scoring_method()));
+}
+
+#[test]
+fn test_analyze_picks_ciphers_without_an_identifier() {
+    let morse = analyze(".... . .-.. .-.. --- / .-- --- .-. .-.. -..", &Config::default()).best_guess.unwrap();
+    assert_eq!(morse.cipher_name, "Morse");
+    assert_eq!(morse.attempt.plaintext, "HELLO WORLD");
+
+    let a1z26 = analyze("8 5 12 12 15 / 23 15 18 12 4", &Config::default()).best_guess.unwrap();
+    assert_eq!(a1z26.cipher_name, "A1Z26");
+    assert_eq!(a1z26.attempt.plaintext, "HELLO WORLD");
+
+    // Caesar's chi-squared lands under the preference threshold here, but its plaintext is gibberish.
+    let plaintext = "It was the best of times it was the worst of times it was the age of wisdom it was the age of foolishness it was the epoch of belief it was the epoch of incredulity it was the season of light it was the season of darkness";
+    let ciphertext = substitution_encrypt(plaintext, SUBSTITUTION_KEY);
+    let report = analyze(&ciphertext, &Config::builder().random_seed(1).build().unwrap());
+    assert_eq!(report.best_guess.unwrap().cipher_name, "Substitution");
+}
+
+#[test]
+fn test_analyze_morse_skips_letter_analysis() {
+    let report = analyze(".... . .-.. .-.. --- / .-- --- .-. .-.. -..", &Config::default());
+    assert!(report.identifications.is_empty());
+    for (name, attempt) in &report.decoder_results {
+        assert_eq!(attempt.is_some(), name == "Morse", "decoder {}", name);
+    }
+    assert!(report.applied_methods.contains(&"Decoder Morse: applied".to_string()));
+    assert!(report.applied_methods.contains(&"Decoder Caesar: skipped (input looks like Morse)".to_string()));
+}
+
+#[test]
+fn test_analysis_cache_reuses_caesar_across_vigenere_passes() {
+    let ciphertext = vigenere_encrypt("THE QUICK BROWN FOX JUMPS OVER THE LAZY DOG AND KEEPS RUNNING", "KEY");
+    let defaults = Config::default();
+    let relaxed = Config::builder().vigenere_min_id_len(10).vigenere_min_dec_len(10).build().unwrap();
+    let mut cache = AnalysisCache::new();
+
+    let stats = cache.raw_stats(&ciphertext);
+    let caesar = cache.decode(&CaesarDecoder::new(&defaults), &ciphertext, &defaults);
+    let vigenere = cache.decode(&VigenereDecoder::new(&defaults), &ciphertext, &defaults);
+
+    assert!(Arc::ptr_eq(&stats, &cache.raw_stats(&ciphertext)));
+    assert!(Arc::ptr_eq(&caesar, &cache.decode(&CaesarDecoder::new(&relaxed), &ciphertext, &relaxed)));
+    assert!(!Arc::ptr_eq(&vigenere, &cache.decode(&VigenereDecoder::new(&relaxed), &ciphertext, &relaxed)));
+
+    let other = cache.decode(&CaesarDecoder::new(&relaxed), "KHOOR ZRUOG", &relaxed);
+    assert!(!Arc::ptr_eq(&caesar, &other));
+}
+
+#[test]
+fn test_analyze_with_cache_reports_decoder_runs() {
+    let config = Config::default();
+    let mut cache = AnalysisCache::new();
+    let report = analyze_with_cache(".... . .-.. .-.. --- / .-- --- .-. .-.. -..", &config, &mut cache);
+
+    let names: Vec<&str> = report.decoder_runs.iter().map(|run| run.name.as_str()).collect();
+    let result_names: Vec<&str> = report.decoder_results.iter().map(|(name, _)| name.as_str()).collect();
+    assert_eq!(names, result_names);
+    for run in &report.decoder_runs {
+        assert_eq!(run.outcome.is_some(), run.skip_reason.is_none(), "decoder {}", run.name);
+    }
+    let caesar = report.decoder_runs.iter().find(|run| run.name == "Caesar").unwrap();
+    assert_eq!(caesar.skip_reason.as_deref(), Some("input looks like Morse"));
+
+    let morse = report.decoder_runs.iter().find(|run| run.name == "Morse").unwrap();
+    let again = analyze_with_cache(".... . .-.. .-.. --- / .-- --- .-. .-.. -..", &config, &mut cache);
+    let morse_again = again.decoder_runs.iter().find(|run| run.name == "Morse").unwrap();
+    assert!(Arc::ptr_eq(morse.outcome.as_ref().unwrap(), morse_again.outcome.as_ref().unwrap()));
+}
+
+#[test]
+fn test_analysis_cache_keys_on_each_decoders_settings() {
+    let ciphertext = vigenere_encrypt("THE QUICK BROWN FOX JUMPS OVER THE LAZY DOG AND KEEPS RUNNING", "KEY");
+    let defaults = Config::default();
+    let relaxed = Config::builder().vigenere_min_dec_len(10).build().unwrap();
+    let bigrams = Config::builder().bigram_scoring_max_len(100).build().unwrap();
+    let wrapped = |config: &Config| {
+        FallbackDecoder::new(Box::new(CaesarDecoder::new(config)), Box::new(VigenereDecoder::new(config)), 1.0)
+    };
+    let mut cache = AnalysisCache::new();
+
+    let vigenere = cache.decode(&VigenereDecoder::new(&defaults), &ciphertext, &defaults);
+    assert!(Arc::ptr_eq(&vigenere, &cache.decode(&VigenereDecoder::new(&defaults), &ciphertext, &defaults)));
+
+    let caesar = cache.decode(&CaesarDecoder::new(&defaults), &ciphertext, &defaults);
+    assert!(!Arc::ptr_eq(&caesar, &cache.decode(&CaesarDecoder::new(&bigrams), &ciphertext, &bigrams)));
+
+    let fallback = cache.decode(&wrapped(&defaults), &ciphertext, &defaults);
+    assert!(Arc::ptr_eq(&fallback, &cache.decode(&wrapped(&defaults), &ciphertext, &defaults)));
+    assert!(!Arc::ptr_eq(&fallback, &cache.decode(&wrapped(&relaxed), &ciphertext, &relaxed)));
+}
+
+// Confident only when the text reads forwards from "THE".
+struct OrientationIdentifier;
+
+impl Identifier for OrientationIdentifier {
+    fn identify(&self, ciphertext: &str) -> Option<IdentificationResult> {
+        Some(IdentificationResult {
+            cipher_name: "Vigenere".to_string(),
+            confidence_score: if ciphertext.starts_with("THE") { 0.9 } else { 0.2 },
+            parameters: Some("Key length: 3".to_string()),
+            params: CipherParams::Other,
+        })
+    }
+
+    fn name(&self) -> &'static str {
+        "Orientation"
+    }
+}
+
+#[test]
+fn test_identify_with_reversal_keeps_the_better_orientation() {
+    let reversed: String = "THE END".chars().rev().collect();
+
+    let forward_only = identify_with_reversal(&OrientationIdentifier, &reversed, false).unwrap();
+    assert_eq!(forward_only.confidence_score, 0.2);
+
+    let both = identify_with_reversal(&OrientationIdentifier, &reversed, true).unwrap();
+    assert_eq!(both.confidence_score, 0.9);
+    assert_eq!(both.parameters.as_deref(), Some("Key length: 3; ciphertext reversed"));
+
+    let forward = identify_with_reversal(&OrientationIdentifier, "THE END", true).unwrap();
+    assert_eq!(forward.parameters.as_deref(), Some("Key length: 3"));
+}
+
+#[test]
+fn test_best_plaintext_caesar() {
+    let plaintext = "MEET ME AT THE OLD HOUSE BY THE RIVER WHEN THE SUN GOES DOWN";
+    let ciphertext = cipher_utils::shift_char_string(plaintext, 3);
+    assert_eq!(best_plaintext(&ciphertext, &Config::default()).as_deref(), Some(plaintext));
+}
+
+#[test]
+fn test_best_plaintext_symbol_and_xor_ciphers() {
+    let config = Config::default();
+    assert_eq!(best_plaintext(".... . .-.. .-.. --- / .-- --- .-. .-.. -..", &config).as_deref(), Some("HELLO WORLD"));
+    assert_eq!(best_plaintext("8 5 12 12 15 / 23 15 18 12 4", &config).as_deref(), Some("HELLO WORLD"));
+    assert_eq!(best_plaintext("2315313134 5234423114", &config).as_deref(), Some("HELLO WORLD"));
+
+    let plaintext = "Attack at dawn, the password is swordfish";
+    let xored: Vec<u8> = plaintext.bytes().map(|b| b ^ 0x2a).collect();
+    assert_eq!(best_plaintext(&encode_hex(&xored), &config).as_deref(), Some(plaintext));
+}
+
+#[test]
+fn test_applied_methods_on_short_input() {
+    let report = analyze("KHOOR ZRUOG", &Config::default());
+    let methods = &report.applied_methods;
+
+    assert!(methods.contains(&"Kasiski examination: skipped (needs 20 letters, got 10)".to_string()));
+    assert!(methods.contains(&"Identifier Vigenere: skipped (needs 30 letters, got 10)".to_string()));
+    assert!(methods.contains(&"Decoder Vigenere: skipped (needs 20 letters, got 10)".to_string()));
+    assert!(methods.contains(&"Identifier Caesar: applied".to_string()));
+    assert!(methods.contains(&"Decoder Caesar: applied".to_string()));
+    assert!(methods.contains(&"Index of Coincidence: applied".to_string()));
+    assert_eq!(methods.len(), 5 + available_identifiers(&Config::default()).len() + available_decoders(&Config::default()).len());
+}
+
+#[test]
+fn test_applied_methods_on_too_short_input() {
+    let report = analyze("x", &Config::default());
+    let methods = &report.applied_methods;
+
+    assert!(methods.contains(&"Index of Coincidence: skipped (fewer than 2 letters or symbols)".to_string()));
+    assert!(methods.contains(&"Chi-squared: skipped (fewer than 2 letters or symbols)".to_string()));
+    assert!(methods.contains(&"Identifier Caesar: skipped (fewer than 2 letters or symbols)".to_string()));
+    assert!(methods.contains(&"Decoder Caesar: skipped (fewer than 2 letters or symbols)".to_string()));
+    assert!(methods.contains(&"Decoder Morse: skipped (fewer than 2 letters or symbols)".to_string()));
+    assert!(!methods.iter().any(|method| method.starts_with("Decoder ") && method.ends_with(": applied")));
+}
+
+#[test]
+fn test_preprocessing_chain_base64_then_caesar() {
+    let plaintext = "MEET ME AT THE OLD HOUSE BY THE RIVER WHEN THE SUN GOES DOWN";
+    let wrapped = encode_base64(cipher_utils::shift_char_string(plaintext, 3).as_bytes());
+
+    let report = analyze(&wrapped, &Config::default());
+    assert_eq!(report.preprocessing_chain, vec!["Base64".to_string()]);
+    assert_eq!(report.encoding.as_deref(), Some("Base64"));
+    assert_eq!(report.analyzed_text, cipher_utils::shift_char_string(plaintext, 3));
+    let guess = report.best_guess.expect("no best guess");
+    assert_eq!(guess.cipher_name, "Caesar");
+    assert_eq!(guess.attempt.plaintext, plaintext);
+
+    let double = encode_hex(wrapped.as_bytes());
+    let (text, chain) = preprocess(&double, &available_preprocessors(&Config::default()));
+    assert_eq!(chain, vec!["Hex".to_string(), "Base64".to_string()]);
+    assert_eq!(text, cipher_utils::shift_char_string(plaintext, 3));
+}
+
+#[test]
+fn test_preprocessing_unwraps_morse_around_caesar() {
+    // "MEET ME AT THE OLD HOUSE ..." shifted by 3, then written in Morse.
+    let morse = ".--. .... .... .-- / .--. .... / -.. .-- / .-- -.- .... / .-. --- --. / -.- .-. -..- ...- .... / . -... / .-- -.- .... / ..- .-.. -.-- .... ..- / --.. -.- .... --.- / .-- -.- .... / ...- -..- --.- / .--- .-. .... ...- / --. .-. --.. --.-";
+    let report = analyze(morse, &Config::default());
+    assert_eq!(report.preprocessing_chain, vec!["Morse".to_string()]);
+    assert_eq!(report.analyzed_text, "PHHW PH DW WKH ROG KRXVH EB WKH ULYHU ZKHQ WKH VXQ JRHV GRZQ");
+    let guess = report.best_guess.expect("no best guess");
+    assert_eq!(guess.cipher_name, "Caesar");
+    assert_eq!(guess.attempt.plaintext, "MEET ME AT THE OLD HOUSE BY THE RIVER WHEN THE SUN GOES DOWN");
+
+    // Morse that already decodes to English is the answer, so it is left to the Morse decoder.
+    let plain = analyze(".... . .-.. .-.. --- / .-- --- .-. .-.. -..", &Config::default());
+    assert!(plain.preprocessing_chain.is_empty());
+    assert_eq!(plain.best_guess.unwrap().cipher_name, "Morse");
+}
+
+#[test]
+fn test_overall_confidence_clean_crack_vs_ambiguous() {
+    let plaintext = "We will meet at the old house by the river when the sun goes down, and then we can talk about what you have found there.";
+    let config = Config::builder().max_key_combinations(20000).build().unwrap();
+    let clean = analyze(&cipher_utils::shift_char_string(plaintext, 7), &config);
+    assert_eq!(clean.best_guess.as_ref().map(|guess| guess.attempt.plaintext.as_str()), Some(plaintext));
+    assert!(clean.overall_confidence > 0.8, "clean crack scored {}", clean.overall_confidence);
+
+    let ambiguous = analyze("XQZJK VWPB", &Config::default());
+    assert!(ambiguous.overall_confidence < 0.3, "ambiguous input scored {}", ambiguous.overall_confidence);
+    assert_eq!(analyze("?", &Config::default()).overall_confidence, 0.0);
+}
+
+#[test]
+fn test_enabled_ciphers_limits_registry() {
+    let config = Config { enabled_ciphers: Some(vec!["Caesar".to_string()]), ..Default::default() };
+    assert_eq!(available_decoders(&config).iter().map(|d| d.name()).collect::<Vec<_>>(), vec!["Caesar"]);
+    assert_eq!(available_identifiers(&config).iter().map(|i| i.name()).collect::<Vec<_>>(), vec!["Caesar"]);
+
+    let ciphertext = vigenere_encrypt("THE QUICK BROWN FOX JUMPS OVER THE LAZY DOG AND KEEPS RUNNING FAR AWAY", "KEY");
+    let report = analyze(&ciphertext, &config);
+    assert_eq!(report.decoder_results.len(), 1);
+    assert_eq!(report.decoder_results[0].0, "Caesar");
+    assert!(report.identifications.iter().all(|id| id.cipher_name == "Caesar"));
+
+    let built = Config::builder().enabled_cipher("caesar").enabled_cipher("Morse").build().unwrap();
+    assert!(built.cipher_enabled("Caesar") && built.cipher_enabled("Morse"));
+    assert!(!built.cipher_enabled("Vigenere"));
+    assert!(Config::default().cipher_enabled("Vigenere"));
+
+    let names = cipher_names();
+    let decoders = available_decoders(&Config::default());
+    assert!(decoders.iter().all(|decoder| names.contains(&decoder.name())));
+    assert_eq!(names.len(), decoders.len());
+}
+
+#[test]
+fn test_report_warns_about_non_ascii() {
+    let report = analyze("Wkh fdih vhuyhv fuèph euõoéh", &Config::default());
+    assert_eq!(report.non_ascii_warning.as_deref(), Some("3 non-ASCII characters ignored (è x1, é x1, õ x1)"));
+    assert_eq!(analyze("Wkh fdih", &Config::default()).non_ascii_warning, None);
+}
@@ -0,0 +1,61 @@
+use peekaboo::ciphers::gronsfeld::GronsfeldDecoder;
+use peekaboo::decoder::Decoder;
+use peekaboo::config::Config;
+use peekaboo::analysis;
+use peekaboo::cipher_utils;
+
+
+fn gronsfeld_encrypt(plaintext: &str, key: &str) -> String {
+    let key_digits: Vec<i8> = key.bytes().map(|b| (b - b'0') as i8).collect();
+    let mut key_index = 0;
+    let mut ciphertext = String::with_capacity(plaintext.len());
+
+    for p in plaintext.chars() {
+        if p.is_ascii_alphabetic() {
+            ciphertext.push(cipher_utils::shift_char(p, key_digits[key_index % key_digits.len()]));
+            key_index += 1;
+        } else {
+            ciphertext.push(p);
+        }
+    }
+    ciphertext
+}
+
+
+#[test]
+fn test_gronsfeld_full_cycle() {
+    let config = Config::default();
+    let decoder = GronsfeldDecoder::new(&config);
+
+    let plaintext = "Four score and seven years ago our fathers brought forth on this continent a new nation conceived in liberty and dedicated to the proposition that all men are created equal";
+    let key = "31415";
+    let ciphertext = gronsfeld_encrypt(plaintext, key);
+
+    let results = decoder.decrypt(&ciphertext);
+    assert!(!results.is_empty());
+    let best_result = &results[0];
+    assert_eq!(best_result.cipher_name, "Gronsfeld");
+    assert_eq!(best_result.key, key);
+    assert_eq!(best_result.plaintext, plaintext);
+    assert!(results.iter().all(|r| r.key.chars().all(|c| c.is_ascii_digit())));
+}
+
+#[test]
+fn test_gronsfeld_decrypt_with_key() {
+    let config = Config::default();
+    let decoder = GronsfeldDecoder::new(&config);
+    let ciphertext = gronsfeld_encrypt("ATTACK AT DAWN", "271");
+
+    let attempt = decoder.decrypt_with_key(&ciphertext, "271").unwrap();
+    assert_eq!(attempt.plaintext, "ATTACK AT DAWN");
+    assert_eq!(attempt.score, analysis::score_trigram_log_prob("ATTACK AT DAWN"));
+
+    assert!(decoder.decrypt_with_key(&ciphertext, "LEMON").is_none());
+}
+
+#[test]
+fn test_gronsfeld_short_text() {
+    let config = Config::default();
+    let decoder = GronsfeldDecoder::new(&config);
+    assert!(decoder.decrypt("SHORT").is_empty());
+}
@@ -0,0 +1,47 @@
+use peekaboo::ciphers::keyed_caesar::KeyedCaesarDecoder;
+use peekaboo::cipher_utils;
+use peekaboo::decoder::Decoder;
+use peekaboo::config::Config;
+
+
+fn keyed_caesar_encrypt(plaintext: &str, keyword: &str, shift: i8) -> String {
+    let alphabet = cipher_utils::keyed_alphabet(keyword);
+    plaintext.chars().map(|c| cipher_utils::shift_char_in_alphabet(c, shift, &alphabet)).collect()
+}
+
+
+#[test]
+fn test_keyed_alphabet() {
+    let alphabet: String = cipher_utils::keyed_alphabet("Mother").iter().collect();
+    assert_eq!(alphabet, "MOTHERABCDFGIJKLNPQSUVWXYZ");
+    let repeated: String = cipher_utils::keyed_alphabet("BALLOON").iter().collect();
+    assert_eq!(repeated, "BALONCDEFGHIJKMPQRSTUVWXYZ");
+}
+
+#[test]
+fn test_keyed_caesar_recovers_dictionary_keyword() {
+    let plaintext = "The quick brown fox jumps over the lazy dog while the farmer sleeps in the barn";
+    let ciphertext = keyed_caesar_encrypt(plaintext, "MOTHER", 5);
+
+    let decoder = KeyedCaesarDecoder::new(&Config::default());
+    let results = decoder.decrypt(&ciphertext);
+    assert_eq!(results[0].plaintext, plaintext);
+    assert_eq!(results[0].key, "MOTHER+5");
+}
+
+#[test]
+fn test_keyed_caesar_custom_keywords_and_key() {
+    let plaintext = "Meet at the bridge when the moon is high";
+    let ciphertext = keyed_caesar_encrypt(plaintext, "zebra", 11);
+
+    let decoder = KeyedCaesarDecoder::with_keywords(["ZEBRA", "tiger", "not a word!"]);
+    let results = decoder.decrypt(&ciphertext);
+    assert!(results.len() <= 10);
+    assert_eq!(results[0].key, "ZEBRA+11");
+    assert_eq!(results[0].plaintext, plaintext);
+
+    let attempt = decoder.decrypt_with_key(&ciphertext, "zebra+11").unwrap();
+    assert_eq!(attempt.plaintext, plaintext);
+    assert!(decoder.decrypt_with_key(&ciphertext, "ZEBRA").is_none());
+    assert!(decoder.decrypt_with_key(&ciphertext, "ZEBRA+26").is_none());
+}
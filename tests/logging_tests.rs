@@ -0,0 +1,50 @@
+use peekaboo::ciphers::vigenere::VigenereDecoder;
+use peekaboo::config::Config;
+use peekaboo::decoder::Decoder;
+use log::{Level, LevelFilter, Log, Metadata, Record};
+use std::sync::Mutex;
+
+
+struct CapturingLogger {
+    records: Mutex<Vec<(Level, String)>>,
+}
+
+impl Log for CapturingLogger {
+    fn enabled(&self, _metadata: &Metadata) -> bool {
+        true
+    }
+
+    fn log(&self, record: &Record) {
+        self.records.lock().unwrap().push((record.level(), record.args().to_string()));
+    }
+
+    fn flush(&self) {}
+}
+
+static LOGGER: CapturingLogger = CapturingLogger { records: Mutex::new(Vec::new()) };
+
+fn captured_logs() -> &'static CapturingLogger {
+    // Other tests in this binary may already have installed it.
+    let _ = log::set_logger(&LOGGER);
+    log::set_max_level(LevelFilter::Debug);
+    &LOGGER
+}
+
+
+#[test]
+fn test_vigenere_progress_goes_through_log_facade() {
+    let logger = captured_logs();
+    let ciphertext = "LXFOPVEFRNHR LXFOPVEFRNHR LXFOPVEFRNHR LXFOPVEFRNHR";
+
+    VigenereDecoder::new(&Config::default()).decrypt(ciphertext);
+
+    let records = logger.records.lock().unwrap();
+    assert!(
+        records.iter().any(|(level, msg)| *level == Level::Info && msg.starts_with("Final key lengths to attempt")),
+        "expected an info record listing key lengths, got {:?}", *records
+    );
+    assert!(
+        records.iter().all(|(_, msg)| !msg.starts_with("INFO:")),
+        "level prefix should come from the logger, not the message"
+    );
+}
@@ -0,0 +1,55 @@
+use peekaboo::ciphers::morse::MorseDecoder;
+use peekaboo::decoder::Decoder;
+use peekaboo::config::Config;
+use peekaboo::analysis;
+
+#[test]
+fn test_morse_sos() {
+    let config = Config::default();
+    let decoder = MorseDecoder::new(&config);
+
+    let results = decoder.decrypt("... --- ...");
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].cipher_name, "Morse");
+    assert_eq!(results[0].key, "morse");
+    assert_eq!(results[0].plaintext, "SOS");
+}
+
+#[test]
+fn test_morse_words_and_digits() {
+    let config = Config::default();
+    let decoder = MorseDecoder::new(&config);
+
+    let results = decoder.decrypt(".... . .-.. .-.. --- / .-- --- .-. .-.. -.. / ..--- ----- ..--- .....");
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].plaintext, "HELLO WORLD 2025");
+}
+
+#[test]
+fn test_morse_invalid_sequence_marked() {
+    let config = Config::default();
+    let decoder = MorseDecoder::new(&config);
+
+    let results = decoder.decrypt("... ........ ...");
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].plaintext, "S?S");
+}
+
+#[test]
+fn test_morse_rejects_non_morse() {
+    let config = Config::default();
+    let decoder = MorseDecoder::new(&config);
+
+    assert!(decoder.decrypt("This is a secret message.").is_empty());
+    assert!(decoder.decrypt("/ / /").is_empty());
+    assert!(decoder.decrypt("").is_empty());
+}
+
+#[test]
+fn test_looks_like_morse() {
+    assert!(analysis::looks_like_morse(".... . .-.. .-.. --- / .-- --- .-. .-.. -.."));
+    assert!(analysis::looks_like_morse("...---..."));
+    assert!(!analysis::looks_like_morse("WKH TXLFN EURZQ IRA MXPSV RYHU WKH ODCB GRJ."));
+    assert!(!analysis::looks_like_morse("well-known... and so on"));
+    assert!(!analysis::looks_like_morse(""));
+}
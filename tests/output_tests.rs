@@ -0,0 +1,125 @@
+use peekaboo::output::*;
+use peekaboo::decoder::{DecryptionAttempt, ScoringMethod};
+
+fn args(list: &[&str]) -> Vec<String> {
+    list.iter().map(|s| s.to_string()).collect()
+}
+
+#[test]
+fn test_parse_args_defaults() {
+    let options = parse_args(args(&[])).unwrap();
+    assert_eq!(options, OutputOptions::default());
+    assert_eq!(options.top_n, DEFAULT_TOP_N);
+    assert!(!options.full_plaintext);
+}
+
+#[test]
+fn test_parse_args_top_and_full() {
+    let options = parse_args(args(&["--top", "3", "--full"])).unwrap();
+    assert_eq!(options.top_n, 3);
+    assert!(options.full_plaintext);
+
+    assert!(parse_args(args(&["--top"])).is_err());
+    assert!(parse_args(args(&["--top", "many"])).is_err());
+    assert!(parse_args(args(&["--bogus"])).is_err());
+}
+
+#[test]
+fn test_plaintext_preview_vs_full() {
+    let long_text = "A".repeat(PLAINTEXT_PREVIEW_CHARS + 5);
+    let attempt = DecryptionAttempt {
+        cipher_name: "Caesar".to_string(),
+        key: "3".to_string(),
+        plaintext: long_text.clone(),
+        score: 1.0,
+        note: None,
+        solver_stats: None,
+    };
+    let key_width = OutputOptions::default().key_width;
+
+    let preview = format_attempt_preview(&attempt, OutputOptions::default().preview_width, key_width, ScoringMethod::ChiSquared);
+    assert!(preview.ends_with(&format!("\"{}...\"", "A".repeat(PLAINTEXT_PREVIEW_CHARS))));
+
+    let full = format_attempt_preview(&attempt, usize::MAX, key_width, ScoringMethod::ChiSquared);
+    assert!(full.ends_with(&format!("\"{}\"", long_text)));
+}
+
+#[test]
+fn test_parse_args_quiet() {
+    assert!(parse_args(args(&["--quiet"])).unwrap().quiet);
+    assert!(parse_args(args(&["-q", "--top", "2"])).unwrap().quiet);
+    assert!(!parse_args(args(&[])).unwrap().quiet);
+}
+
+#[test]
+fn test_parse_args_reversed() {
+    assert!(parse_args(args(&["--reversed"])).unwrap().try_reversed);
+    assert!(!parse_args(args(&["--full"])).unwrap().try_reversed);
+}
+
+#[test]
+fn test_parse_args_explain() {
+    assert!(parse_args(args(&["--explain"])).unwrap().explain);
+    assert!(!parse_args(args(&["--quiet"])).unwrap().explain);
+}
+
+#[test]
+fn test_setting_help_quiet_omits_explanation() {
+    let explanation = [" - What it is: a setting.", " - Why it matters: it changes things."];
+
+    let verbose = setting_help("Example Setting", &explanation, false);
+    assert!(verbose.contains("[Example Setting]"));
+    assert!(verbose.contains("What it is"));
+    assert!(verbose.contains("Why it matters"));
+
+    let quiet = setting_help("Example Setting", &explanation, true);
+    assert_eq!(quiet, "\n[Example Setting]");
+}
+
+#[test]
+fn test_truncate_with_ellipsis() {
+    assert_eq!(truncate_with_ellipsis("ABCDEFGHIJKL", 5), "ABCDE...");
+    assert_eq!(truncate_with_ellipsis("ABCDE", 5), "ABCDE");
+    assert_eq!(truncate_with_ellipsis("ABC", 5), "ABC");
+}
+
+#[test]
+fn test_format_attempt_preview_widths() {
+    let attempt = DecryptionAttempt {
+        cipher_name: "Vigenere".to_string(),
+        key: "LONGERKEYWORD".to_string(),
+        plaintext: "ATTACKATDAWN".to_string(),
+        score: -42.126,
+        note: None,
+        solver_stats: None,
+    };
+
+    let truncated = format_attempt_preview(&attempt, 6, 4, ScoringMethod::Trigram);
+    assert_eq!(truncated, "Key: LONG... | Score: -42.13   | Plaintext: \"ATTACK...\"");
+
+    let full = format_attempt_preview(&attempt, 20, 15, ScoringMethod::ChiSquared);
+    assert_eq!(full, "Key: LONGERKEYWORD   | Score: -42.1260 | Plaintext: \"ATTACKATDAWN\"");
+}
+
+#[test]
+fn test_parse_args_widths() {
+    let options = parse_args(args(&["--preview-width", "30", "--key-width", "4"])).unwrap();
+    assert_eq!(options.preview_width, 30);
+    assert_eq!(options.key_width, 4);
+    assert!(parse_args(args(&["--key-width", "wide"])).is_err());
+}
+
+#[test]
+fn test_parse_args_max_key_combinations() {
+    assert_eq!(parse_args(args(&["--max-key-combinations", "5000"])).unwrap().max_key_combinations, Some(5000));
+    assert_eq!(parse_args(args(&[])).unwrap().max_key_combinations, None);
+    assert!(parse_args(args(&["--max-key-combinations"])).is_err());
+}
+
+#[test]
+fn test_parse_args_cipher_repeatable() {
+    let options = parse_args(args(&["--cipher", "Caesar", "--top", "3", "--cipher", "Vigenere"])).unwrap();
+    assert_eq!(options.enabled_ciphers, Some(vec!["Caesar".to_string(), "Vigenere".to_string()]));
+    assert_eq!(parse_args(args(&[])).unwrap().enabled_ciphers, None);
+    assert!(parse_args(args(&["--cipher"])).is_err());
+}
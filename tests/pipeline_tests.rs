@@ -0,0 +1,45 @@
+use peekaboo::ciphers::caesar::CaesarDecoder;
+use peekaboo::ciphers::encoding::encode_base64;
+use peekaboo::ciphers::morse::MorseDecoder;
+use peekaboo::cipher_utils;
+use peekaboo::config::Config;
+use peekaboo::pipeline::{Pipeline, PipelineOutput};
+
+
+#[test]
+fn test_rot13_twice_is_identity() {
+    let config = Config::default();
+    let pipeline = Pipeline::new()
+        .then_with_key(Box::new(CaesarDecoder::new(&config)), "13")
+        .then_with_key(Box::new(CaesarDecoder::new(&config)), "13");
+    let input = "Why did the chicken cross the road?";
+
+    let output = pipeline.run(input).unwrap();
+    assert_eq!(output.stages.len(), 2);
+    assert_eq!(output.stages[0].plaintext, "Jul qvq gur puvpxra pebff gur ebnq?");
+    assert_eq!(output.final_attempt().unwrap().plaintext, input);
+}
+
+#[test]
+fn test_pipeline_searches_unkeyed_stages() {
+    let config = Config::default();
+    let plaintext = "Meet me by the old oak tree at midnight and bring the map";
+    let shifted = cipher_utils::shift_char_string(plaintext, 5);
+
+    let output = Pipeline::new()
+        .then(Box::new(CaesarDecoder::new(&config)))
+        .run(&shifted)
+        .unwrap();
+    assert_eq!(output.final_attempt().unwrap().key, "5");
+    assert_eq!(output.final_attempt().unwrap().plaintext, plaintext);
+
+    assert!(Pipeline::new().run(plaintext).is_none());
+    let morse_first = Pipeline::new().then(Box::new(MorseDecoder::new(&config)));
+    assert!(morse_first.run(&encode_base64(plaintext.as_bytes())).is_none());
+}
+
+#[test]
+fn test_pipeline_output_without_stages_has_no_final_attempt() {
+    let output = PipelineOutput { stages: Vec::new() };
+    assert!(output.final_attempt().is_none());
+}
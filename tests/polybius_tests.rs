@@ -0,0 +1,58 @@
+use peekaboo::ciphers::polybius::PolybiusDecoder;
+use peekaboo::decoder::Decoder;
+use peekaboo::config::Config;
+
+
+fn polybius_encode(plaintext: &str, square: &str) -> String {
+    plaintext
+        .split_whitespace()
+        .map(|word| {
+            word.chars()
+                .map(|c| {
+                    let c = if c == 'J' { 'I' } else { c };
+                    let index = square.find(c).unwrap();
+                    format!("{}{}", index / 5 + 1, index % 5 + 1)
+                })
+                .collect::<String>()
+        })
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+
+#[test]
+fn test_polybius_standard_square() {
+    let config = Config::default();
+    let decoder = PolybiusDecoder::new(&config);
+
+    let results = decoder.decrypt("2315313134 5234423114");
+    assert!(!results.is_empty());
+    assert_eq!(results[0].cipher_name, "Polybius");
+    assert_eq!(results[0].plaintext, "HELLO WORLD");
+    assert_eq!(results[0].key, "ABCDEFGHIKLMNOPQRSTUVWXYZ");
+
+    let letter_pairs = decoder.decrypt("23 15 31 31 34");
+    assert_eq!(letter_pairs[0].plaintext, "HELLO");
+}
+
+#[test]
+fn test_polybius_keyed_square() {
+    let config = Config::default();
+    let decoder = PolybiusDecoder::new(&config);
+    let ciphertext = polybius_encode("MEET AT THE BRIDGE", "ZEBRACDFGHIKLMNOPQSTUVWXY");
+
+    let attempt = decoder.decrypt_with_key(&ciphertext, "zebra").unwrap();
+    assert_eq!(attempt.plaintext, "MEET AT THE BRIDGE");
+    assert_eq!(attempt.key, "ZEBRACDFGHIKLMNOPQSTUVWXY");
+}
+
+#[test]
+fn test_polybius_rejects_non_polybius_input() {
+    let config = Config::default();
+    let decoder = PolybiusDecoder::new(&config);
+
+    assert!(decoder.decrypt("Hello world").is_empty());
+    assert!(decoder.decrypt("123").is_empty());
+    assert!(decoder.decrypt("77 88").is_empty());
+    assert!(decoder.decrypt("").is_empty());
+}
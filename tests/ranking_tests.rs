@@ -0,0 +1,187 @@
+use peekaboo::decoder::DecryptionAttempt;
+use peekaboo::identifier::{CipherParams, IdentificationResult};
+use peekaboo::ranking::*;
+
+fn id_result(cipher_name: &str, confidence_score: f64) -> IdentificationResult {
+    IdentificationResult {
+        cipher_name: cipher_name.to_string(),
+        confidence_score,
+        parameters: None,
+        params: CipherParams::Other,
+    }
+}
+
+fn attempt(cipher_name: &str, plaintext: &str) -> DecryptionAttempt {
+    DecryptionAttempt {
+        cipher_name: cipher_name.to_string(),
+        key: "K".to_string(),
+        plaintext: plaintext.to_string(),
+        score: 0.0,
+        note: None,
+        solver_stats: None,
+    }
+}
+
+#[test]
+fn test_best_overall_guess_near_tie() {
+    // Caesar chi^2 of 4.0 normalizes to 0.2, below Vigenere's 0.22 but outside the Caesar preference.
+    let ids = vec![id_result("Caesar", 4.0), id_result("Vigenere", 0.22)];
+    let decs = vec![
+        ("Caesar".to_string(), Some(attempt("Caesar", "caesar plaintext"))),
+        ("Vigenere".to_string(), Some(attempt("Vigenere", "vigenere plaintext"))),
+    ];
+    let guess = best_overall_guess(&ids, &decs).unwrap();
+
+    assert_eq!(guess.cipher_name, "Vigenere");
+    assert_eq!(guess.runner_up.as_ref().map(|(name, _)| name.as_str()), Some("Caesar"));
+    assert!((guess.confidence_gap - 0.02).abs() < 1e-9);
+    assert!(guess.is_ambiguous());
+}
+
+#[test]
+fn test_best_overall_guess_clear_winner() {
+    let ids = vec![id_result("Vigenere", 0.2), id_result("Caesar", 0.1)];
+    let decs = vec![
+        ("Caesar".to_string(), Some(attempt("Caesar", "caesar plaintext"))),
+        ("Vigenere".to_string(), Some(attempt("Vigenere", "vigenere plaintext"))),
+    ];
+    let guess = best_overall_guess(&ids, &decs).unwrap();
+
+    assert_eq!(guess.cipher_name, "Caesar");
+    assert!(guess.confidence_gap > 0.5);
+    assert!(!guess.is_ambiguous());
+}
+
+#[test]
+fn test_best_overall_guess_single_candidate() {
+    let ids = vec![id_result("Vigenere", 0.8)];
+    let decs = vec![("Vigenere".to_string(), Some(attempt("Vigenere", "vigenere plaintext")))];
+    let guess = best_overall_guess(&ids, &decs).unwrap();
+
+    assert!(guess.runner_up.is_none());
+    assert_eq!(guess.confidence_gap, 0.8);
+    assert!(!guess.is_ambiguous());
+}
+
+#[test]
+fn test_best_overall_guess_prefers_strong_caesar() {
+    // Vigenere normalizes higher (0.9 vs 1/3.5), but a chi-squared of 2.5 keeps Caesar.
+    let ids = vec![id_result("Caesar", 2.5), id_result("Vigenere", 0.9)];
+    let decs = vec![
+        ("Caesar".to_string(), Some(attempt("Caesar", "caesar plaintext"))),
+        ("Vigenere".to_string(), Some(attempt("Vigenere", "vigenere plaintext"))),
+    ];
+
+    let guess = best_overall_guess(&ids, &decs).unwrap();
+    assert_eq!(guess.cipher_name, "Caesar");
+    assert_eq!(guess.decoder_index, 0);
+    assert_eq!(guess.attempt.plaintext, "caesar plaintext");
+    assert!(guess.caesar_preferred);
+    assert!((guess.confidence - 1.0 / 3.5).abs() < 1e-9);
+    // The runner-up is measured against the preferred Caesar, not the better-identified Vigenere.
+    assert_eq!(guess.runner_up, Some(("Vigenere".to_string(), 0.9)));
+    assert!((guess.confidence_gap - (1.0 / 3.5 - 0.9)).abs() < 1e-9);
+    assert!(guess.is_ambiguous());
+}
+
+#[test]
+fn test_overall_confidence_gap_is_for_the_guessed_cipher() {
+    let ids = vec![id_result("Caesar", 2.5), id_result("Vigenere", 0.9)];
+    let decs = vec![
+        ("Caesar".to_string(), Some(attempt("Caesar", "xq zvk"))),
+        ("Vigenere".to_string(), Some(attempt("Vigenere", "vigenere plaintext"))),
+    ];
+
+    // Caesar wins on its chi-squared, so Vigenere's higher normalized confidence leaves it no margin.
+    let caesar = best_overall_guess(&ids, &decs).unwrap();
+    assert!((overall_confidence(Some(&caesar)) - 0.3 / 3.5).abs() < 1e-9);
+
+    let vigenere = best_overall_guess_with_threshold(&ids, &decs, 0.0).unwrap();
+    assert_eq!(vigenere.cipher_name, "Vigenere");
+    let expected = 0.3 * 0.9 + 0.2 * (0.9 - 1.0 / 3.5);
+    assert!((overall_confidence(Some(&vigenere)) - expected).abs() < 1e-9);
+}
+
+#[test]
+fn test_best_overall_guess_vigenere_wins() {
+    let ids = vec![id_result("Caesar", 8.0), id_result("Vigenere", 0.7)];
+    let decs = vec![
+        ("Caesar".to_string(), Some(attempt("Caesar", "caesar plaintext"))),
+        ("Gronsfeld".to_string(), None),
+        ("Vigenere".to_string(), Some(attempt("Vigenere", "vigenere plaintext"))),
+    ];
+
+    let guess = best_overall_guess(&ids, &decs).unwrap();
+    assert_eq!(guess.cipher_name, "Vigenere");
+    assert_eq!(guess.decoder_index, 2);
+    assert!(!guess.caesar_preferred);
+    assert!((guess.confidence - 0.7).abs() < 1e-9);
+}
+
+#[test]
+fn test_best_overall_guess_requires_a_decryption() {
+    let ids = vec![id_result("Caesar", 1.0), id_result("Vigenere", 0.7)];
+    let decs = vec![("Caesar".to_string(), None), ("Vigenere".to_string(), None)];
+    assert!(best_overall_guess(&ids, &decs).is_none());
+
+    let only_vigenere = vec![("Caesar".to_string(), None), ("Vigenere".to_string(), Some(attempt("Vigenere", "text")))];
+    assert_eq!(best_overall_guess(&ids, &only_vigenere).unwrap().cipher_name, "Vigenere");
+}
+
+#[test]
+fn test_promote_crib_match() {
+    let ids = vec![id_result("Caesar", 1.0), id_result("Vigenere", 0.9)];
+    let decs = vec![
+        ("Caesar".to_string(), vec![attempt("Caesar", "nothing useful here")]),
+        (
+            "Vigenere".to_string(),
+            vec![attempt("Vigenere", "the best scoring text"), attempt("Vigenere", "meet at the bridge at dawn")],
+        ),
+    ];
+
+    let guess = promote_crib_match(&ids, &decs, &["BRIDGE".to_string()]).unwrap();
+    assert_eq!(guess.cipher_name, "Vigenere");
+    assert_eq!(guess.decoder_index, 1);
+    assert_eq!(guess.attempt.plaintext, "meet at the bridge at dawn");
+    assert_eq!(guess.matched_crib.as_deref(), Some("BRIDGE"));
+    assert_eq!(guess.runner_up, Some(("Caesar".to_string(), 0.5)));
+    assert!((guess.confidence_gap - 0.5).abs() < 1e-9);
+
+    assert!(promote_crib_match(&ids, &decs, &[]).is_none());
+
+    let flagged = vec![("XOR".to_string(), vec![attempt("XOR", "noise"), attempt("XOR", "ctf{x0r_k3y}")])];
+    assert_eq!(promote_crib_match(&[], &flagged, &[]).unwrap().matched_crib.as_deref(), Some("ctf{x0r_k3y}"));
+}
+
+#[test]
+fn test_best_overall_guess_ranks_unidentified_ciphers_on_plaintext() {
+    // Nothing identifies Morse, so it wins on its readable plaintext alone.
+    let decs = vec![("Morse".to_string(), Some(attempt("Morse", "HELLO WORLD")))];
+    let guess = best_overall_guess(&[], &decs).unwrap();
+    assert_eq!(guess.cipher_name, "Morse");
+    assert_eq!(guess.confidence, 1.0);
+
+    // A preferred Caesar reading as gibberish loses to a readable Substitution attempt.
+    let ids = vec![id_result("Caesar", 2.9)];
+    let decs = vec![
+        ("Caesar".to_string(), Some(attempt("Caesar", "xq zvk pmo lrt"))),
+        ("Substitution".to_string(), Some(attempt("Substitution", "it was the best of times"))),
+    ];
+    let guess = best_overall_guess(&ids, &decs).unwrap();
+    assert_eq!(guess.cipher_name, "Substitution");
+    assert_eq!(guess.decoder_index, 1);
+    assert!(!guess.caesar_preferred);
+    assert_eq!(guess.runner_up.as_ref().map(|(name, _)| name.as_str()), Some("Caesar"));
+
+    // Reading only as well as the identified pick is not enough, and the same plaintext is no rival.
+    let decs = vec![
+        ("Caesar".to_string(), Some(attempt("Caesar", "it was the best of times"))),
+        ("Keyed Caesar".to_string(), Some(attempt("Keyed Caesar", "it was the best of times"))),
+    ];
+    let guess = best_overall_guess(&ids, &decs).unwrap();
+    assert_eq!(guess.cipher_name, "Caesar");
+    assert!(guess.runner_up.is_none());
+
+    let unreadable = vec![("Bacon".to_string(), Some(attempt("Bacon", "QXZVKPMO")))];
+    assert!(best_overall_guess(&[], &unreadable).is_none());
+}
@@ -0,0 +1,111 @@
+use peekaboo::ciphers::substitution::SubstitutionDecoder;
+use peekaboo::decoder::Decoder;
+use peekaboo::config::Config;
+use peekaboo::analysis;
+
+mod common;
+use common::{substitution_encrypt, SUBSTITUTION_KEY};
+
+
+fn sample_ciphertext() -> String {
+    substitution_encrypt(
+        "It was the best of times it was the worst of times it was the age of wisdom it was the age of foolishness it was the epoch of belief it was the epoch of incredulity it was the season of light it was the season of darkness",
+        SUBSTITUTION_KEY,
+    )
+}
+
+
+#[test]
+fn test_substitution_seeded_is_deterministic() {
+    let config = Config {
+        random_seed: Some(42),
+        ..Default::default()
+    };
+    let ciphertext = sample_ciphertext();
+
+    let first = SubstitutionDecoder::new(&config).decrypt(&ciphertext);
+    let second = SubstitutionDecoder::new(&config).decrypt(&ciphertext);
+    assert!(!first.is_empty());
+    assert_eq!(first[0], second[0]);
+    assert_eq!(first[0].plaintext.as_bytes(), second[0].plaintext.as_bytes());
+}
+
+#[test]
+fn test_substitution_improves_on_ciphertext() {
+    let config = Config {
+        random_seed: Some(7),
+        ..Default::default()
+    };
+    let ciphertext = sample_ciphertext();
+
+    let results = SubstitutionDecoder::new(&config).decrypt(&ciphertext);
+    assert!(!results.is_empty());
+    assert_eq!(results[0].cipher_name, "Substitution");
+    assert_eq!(results[0].key.len(), 26);
+    assert!(results[0].score > analysis::score_trigram_log_prob(&ciphertext));
+    println!("Substitution best: {} -> {}", results[0].key, results[0].plaintext);
+}
+
+#[test]
+fn test_substitution_skips_input_without_monoalphabetic_signature() {
+    let decoder = SubstitutionDecoder::new(&Config::default());
+    let plaintext = "It was the best of times it was the worst of times it was the age of wisdom it was the age of foolishness";
+    let vigenere = peekaboo::cipher_utils::apply_keystream(plaintext, "LEMONADE".bytes().map(|b| (b - b'A') as i8).cycle());
+
+    assert!(decoder.skip_reason(&sample_ciphertext()).is_none());
+    assert!(decoder.skip_reason(plaintext).is_some_and(|reason| reason.contains("chi-squared")));
+    assert!(decoder.skip_reason(&vigenere).is_some_and(|reason| reason.contains("IC")));
+    assert_eq!(decoder.min_ciphertext_len(), 40);
+}
+
+#[test]
+fn test_substitution_decrypt_with_key() {
+    let config = Config::default();
+    let decoder = SubstitutionDecoder::new(&config);
+    let ciphertext = substitution_encrypt("Attack at dawn", SUBSTITUTION_KEY);
+
+    // Invert the encryption key to get the ciphertext -> plaintext mapping.
+    let mut inverse = [b'A'; 26];
+    for (plain, &cipher) in SUBSTITUTION_KEY.as_bytes().iter().enumerate() {
+        inverse[(cipher - b'A') as usize] = b'A' + plain as u8;
+    }
+    let inverse_key = String::from_utf8(inverse.to_vec()).unwrap();
+
+    let attempt = decoder.decrypt_with_key(&ciphertext, &inverse_key).unwrap();
+    assert_eq!(attempt.plaintext, "Attack at dawn");
+
+    assert!(decoder.decrypt_with_key(&ciphertext, "ABC").is_none());
+    assert!(decoder.decrypt_with_key(&ciphertext, "AACDEFGHIJKLMNOPQRSTUVWXYZ").is_none());
+    assert!(decoder.decrypt("SHORT TEXT").is_empty());
+}
+
+#[test]
+fn test_substitution_restart_budget_is_honored() {
+    let ciphertext = sample_ciphertext();
+    let run = |restarts: usize| {
+        let config = Config::builder()
+            .random_seed(7)
+            .solver_restarts(restarts)
+            .solver_max_iterations(500)
+            .build()
+            .unwrap();
+        let attempts = SubstitutionDecoder::new(&config).decrypt(&ciphertext);
+        let stats = attempts[0].solver_stats.expect("substitution attempts carry solver stats");
+        (attempts, stats)
+    };
+
+    let (one_attempts, one) = run(1);
+    let (many_attempts, many) = run(20);
+
+    assert_eq!(one.restarts, 1);
+    assert_eq!(many.restarts, 20);
+    assert!(one.iterations <= 500);
+    assert!(many.iterations > one.iterations);
+    assert!(many.iterations <= 20 * 500);
+    assert_eq!(one_attempts.len(), 1);
+    assert!(!many_attempts.is_empty());
+
+    assert!(Config::builder().solver_restarts(0).build().is_err());
+    // By default a climb only ends once it stops improving.
+    assert_eq!(Config::default().solver_max_iterations, usize::MAX);
+}
@@ -1,34 +1,13 @@
-use peekaboo::ciphers::vigenere::{VigenereIdentifier, VigenereDecoder};
-use peekaboo::identifier::Identifier;
-use peekaboo::decoder::Decoder;
-use peekaboo::config::Config;
+use peekaboo::ciphers::vigenere::{VigenereIdentifier, VigenereDecoder, TOP_N_SHIFTS_PER_COLUMN};
+use peekaboo::identifier::{CipherParams, Identifier};
+use peekaboo::decoder::{Decoder, ScoringMethod};
+use peekaboo::config::{ColumnShiftMethod, Config};
 use peekaboo::analysis;
 use peekaboo::cipher_utils;
 
+mod common;
+use common::vigenere_encrypt;
 
-#[allow(dead_code)]
-fn vigenere_encrypt(plaintext: &str, keyword: &str) -> String {
-    if keyword.is_empty() || !keyword.chars().all(|c| c.is_ascii_alphabetic()) {
-        return plaintext.to_string();
-    }
-    let keyword_bytes = keyword.to_ascii_uppercase().into_bytes();
-    let key_len = keyword_bytes.len();
-    let mut key_index = 0;
-    let mut ciphertext = String::with_capacity(plaintext.len());
-
-    for p in plaintext.chars() {
-        if p.is_ascii_alphabetic() {
-            let key_byte = keyword_bytes[key_index % key_len];
-            let key_shift = (key_byte - b'A') as i8;
-            let encrypted_char = cipher_utils::shift_char(p, key_shift);
-            ciphertext.push(encrypted_char);
-            key_index += 1;
-        } else {
-            ciphertext.push(p);
-        }
-    }
-    ciphertext
-}
 
 #[allow(dead_code)]
 fn vigenere_decrypt(ciphertext: &str, keyword: &str) -> String {
@@ -71,6 +50,15 @@ fn test_vigenere_decrypt_helper_integration() {
     assert_eq!(vigenere_decrypt(&cipher_rt, key), plain);
 }
 
+#[test]
+fn test_vigenere_default_matches_default_config() {
+    let ciphertext = vigenere_encrypt("THE QUICK BROWN FOX JUMPS OVER THE LAZY DOG AND KEEPS RUNNING FAR AWAY", "LEMON");
+    let configured = VigenereIdentifier::new(&Config::default()).identify(&ciphertext);
+    assert!(configured.is_some());
+    assert_eq!(VigenereIdentifier::default().identify(&ciphertext), configured);
+    assert_eq!(VigenereDecoder::default().cache_key(), VigenereDecoder::new(&Config::default()).cache_key());
+}
+
 #[test]
 fn test_vigenere_long_text_cycle() {
     let config = Config::default();
@@ -184,6 +172,7 @@ fn test_vigenere_gettysburg_cycle() {
 }
 
 #[test]
+#[allow(clippy::field_reassign_with_default)]
 fn test_vigenere_short_text_behavior() {
     let config = Config::default();
     let identifier = VigenereIdentifier::new(&config);
@@ -203,7 +192,7 @@ fn test_vigenere_short_text_behavior() {
     let short_decoder = VigenereDecoder::new(&short_config);
     let short_results = short_decoder.decrypt(ciphertext);
 
-    // Corrected assertion: Expect results because columns length 6 >= MIN_CHARS_FOR_MIC=5
+    // Corrected assertion: Expect results because columns length 6 >= DEFAULT_MIN_CHARS_FOR_MIC=5
     assert!(!short_results.is_empty());
 }
 
@@ -224,6 +213,7 @@ fn test_vigenere_on_caesar() {
 
         assert!(results[0].key.chars().all(|c| c == 'F'));
         assert!((results[0].score - caesar_score).abs() < 1e-6);
+        assert!(results[0].note.as_deref().is_some_and(|note| note.contains("Caesar") && note.contains("shift 5")));
     } else {
         panic!("Vigenere attempt on Caesar produced no results when it should have found length 1 key F");
     }
@@ -250,3 +240,404 @@ fn test_vigenere_id_boundaries() {
     assert!(result_opt.is_some());
 
 }
+
+#[test]
+fn test_vigenere_decrypt_with_key() {
+    let config = Config::default();
+    let decoder = VigenereDecoder::new(&config);
+
+    let attempt = decoder.decrypt_with_key("LXFOPVEFRNHR", "LEMON").expect("Known-key Vigenere failed");
+    assert_eq!(attempt.cipher_name, "Vigenere");
+    assert_eq!(attempt.key, "LEMON");
+    assert_eq!(attempt.plaintext, "ATTACKATDAWN");
+    assert_eq!(attempt.score, analysis::score_trigram_log_prob("ATTACKATDAWN"));
+
+    assert!(decoder.decrypt_with_key("LXFOPVEFRNHR", "").is_none());
+    assert!(decoder.decrypt_with_key("LXFOPVEFRNHR", "L3MON").is_none());
+}
+
+#[test]
+fn test_vigenere_id_configurable_ic_threshold() {
+    let english_like = "ALICEWASBEGINNINGTOGETVERYTIREDOFSITTINGBYHERSISTERONTHEBANKANDOFHAVINGNOTHINGTODO";
+    assert!(analysis::calculate_ic(english_like).unwrap() > 0.060);
+
+    let default_identifier = VigenereIdentifier::new(&Config::default());
+    assert!(default_identifier.identify(english_like).is_none());
+
+    let relaxed_config = Config {
+        vigenere_ic_upper_threshold: 0.080,
+        ..Default::default()
+    };
+    let relaxed_identifier = VigenereIdentifier::new(&relaxed_config);
+    let result = relaxed_identifier.identify(english_like);
+    assert!(result.is_some());
+    assert_eq!(result.unwrap().cipher_name, "Vigenere");
+}
+
+#[test]
+fn test_vigenere_identify_all_default() {
+    let config = Config::default();
+    let identifier = VigenereIdentifier::new(&config);
+    let ciphertext = vigenere_encrypt("ALICEWASBEGINNINGTOGETVERYTIREDOFSITTINGBYHERSISTERONTHEBANK", "CRYPTO");
+
+    let all = identifier.identify_all(&ciphertext);
+    assert_eq!(all, identifier.identify(&ciphertext).into_iter().collect::<Vec<_>>());
+    assert!(identifier.identify_all("LXFOPVEFRNHR").is_empty());
+}
+
+#[test]
+fn test_vigenere_raised_max_key_length() {
+    let plaintext = "Four score and seven years ago our fathers brought forth on this continent a new nation conceived in liberty and dedicated to the proposition that all men are created equal Now we are engaged in a great civil war testing whether that nation or any nation so conceived and so dedicated can long endure We are met on a great battlefield of that war We have come to dedicate a portion of that field as a final resting place for those who here gave their lives that that nation might live It is altogether fitting and proper that we should do this";
+    let expected_key = "CRYPTOGRAPHIC";
+    assert_eq!(expected_key.len(), 13);
+    let ciphertext = vigenere_encrypt(plaintext, expected_key);
+
+    let default_decoder = VigenereDecoder::new(&Config::default());
+    let default_results = default_decoder.decrypt(&ciphertext);
+    assert!(default_results.iter().all(|r| r.key.len() <= 12));
+
+    let raised_config = Config {
+        kasiski_max_dec_key_len: 15,
+        max_key_combinations: Some(20000),
+        ..Default::default()
+    };
+    let raised_decoder = VigenereDecoder::new(&raised_config);
+    let raised_results = raised_decoder.decrypt(&ciphertext);
+    assert!(!raised_results.is_empty());
+    assert_eq!(raised_results[0].key, expected_key);
+    assert_eq!(raised_results[0].plaintext, plaintext);
+}
+
+#[test]
+fn test_vigenere_decrypt_with_key_length_hint() {
+    let config = Config::default();
+    let decoder = VigenereDecoder::new(&config);
+    let plaintext = "ALICEWASBEGINNINGTOGETVERYTIREDOFSITTINGBYHERSISTERONTHEBANKANDOFHAVINGNOTHINGTODOONCEORTWICESHEHADPEEPEDINTOTHEBOOKHERSISTERWASREADINGBUTITHADNOPICTURESORCONVERSATIONSINIT";
+    let ciphertext = vigenere_encrypt(plaintext, "CRYPTO");
+
+    let results = decoder.decrypt_with_key_length(&ciphertext, 6);
+    assert!(!results.is_empty());
+    assert!(results.iter().all(|r| r.key.len() == 6));
+    assert_eq!(results[0].key, "CRYPTO");
+    assert_eq!(results[0].plaintext, plaintext);
+
+    assert!(decoder.decrypt_with_key_length(&ciphertext, 0).is_empty());
+    assert!(decoder.decrypt_with_key_length("LXFOPVEFRNHR", 6).is_empty());
+}
+
+#[test]
+fn test_vigenere_decrypt_outcome() {
+    use peekaboo::decoder::DecodeOutcome;
+
+    let config = Config::default();
+    let decoder = VigenereDecoder::new(&config);
+
+    match decoder.decrypt_outcome("LXFOPVEFRNHR") {
+        DecodeOutcome::TooShort { needed, got } => {
+            assert_eq!(needed, config.vigenere_min_dec_len);
+            assert_eq!(got, 12);
+        }
+        other => panic!("Expected TooShort, got {:?}", other),
+    }
+
+    let ciphertext = vigenere_encrypt("THISISASAMPLETEXTOFMODERATELENGTHENCRYPTEDWITHTHEKEYTESTTOSEEANALYSIS", "TEST");
+    match decoder.decrypt_outcome(&ciphertext) {
+        DecodeOutcome::Results(attempts) => {
+            assert!(!attempts.is_empty());
+            assert_eq!(attempts, decoder.decrypt(&ciphertext));
+        }
+        other => panic!("Expected Results, got {:?}", other),
+    }
+
+    let strict_config = Config {
+        vigenere_min_dec_len: 10,
+        min_chars_for_mic: 50,
+        ..Default::default()
+    };
+    let strict_decoder = VigenereDecoder::new(&strict_config);
+    assert!(matches!(strict_decoder.decrypt_outcome("LXFOPVEFRNHRLXFOPVEFRNHR"), DecodeOutcome::ColumnsTooShort { .. }));
+}
+
+#[test]
+fn test_vigenere_leading_punctuation_alignment() {
+    let config = Config::default();
+    let decoder = VigenereDecoder::new(&config);
+    let plaintext = "ALICEWASBEGINNINGTOGETVERYTIREDOFSITTINGBYHERSISTERONTHEBANKANDOFHAVINGNOTHINGTODOONCEORTWICESHEHADPEEPEDINTOTHEBOOKHERSISTERWASREADINGBUTITHADNOPICTURESORCONVERSATIONSINIT";
+    let ciphertext = vigenere_encrypt(plaintext, "CRYPTO");
+    let prefixed_ciphertext = format!("!!! {}  ", ciphertext);
+
+    let plain_results = decoder.decrypt_with_key_length(&ciphertext, 6);
+    let prefixed_results = decoder.decrypt_with_key_length(&prefixed_ciphertext, 6);
+    assert!(!prefixed_results.is_empty());
+    assert_eq!(prefixed_results[0].key, plain_results[0].key);
+    assert!(prefixed_results[0].plaintext.starts_with("!!! "));
+    assert_eq!(
+        analysis::get_alphabetic_chars(&prefixed_results[0].plaintext),
+        analysis::get_alphabetic_chars(&plain_results[0].plaintext)
+    );
+
+    let full_plain = decoder.decrypt(&ciphertext);
+    let full_prefixed = decoder.decrypt(&prefixed_ciphertext);
+    assert!(!full_prefixed.is_empty());
+    assert_eq!(full_prefixed[0].key, full_plain[0].key);
+    assert_eq!(
+        analysis::get_alphabetic_chars(&full_prefixed[0].plaintext),
+        analysis::get_alphabetic_chars(&full_plain[0].plaintext)
+    );
+
+    let keyed = decoder.decrypt_with_key(&prefixed_ciphertext, "CRYPTO").unwrap();
+    assert_eq!(keyed.plaintext, format!("!!! {}  ", plaintext));
+}
+
+#[test]
+fn test_vigenere_note_absent_for_mixed_key() {
+    let config = Config::default();
+    let decoder = VigenereDecoder::new(&config);
+    let ciphertext = vigenere_encrypt("ATTACKATDAWN", "LEMON");
+
+    assert!(decoder.decrypt_with_key(&ciphertext, "LEMON").unwrap().note.is_none());
+    assert!(decoder.decrypt_with_key(&ciphertext, "kkk").unwrap().note.is_some());
+}
+
+#[test]
+fn test_vigenere_key_length_diagnostics() {
+    let config = Config::default();
+    let decoder = VigenereDecoder::new(&config);
+    let plaintext = "THEQUICKBROWNFOXJUMPSOVERTHELAZYDOGANDKEEPSRUNNINGTHROUGHTHEFIELDSUNTILNIGHTFALLS";
+    let ciphertext = vigenere_encrypt(plaintext, "LEMON");
+
+    let diagnostics = decoder.key_length_diagnostics(&ciphertext, 5).unwrap();
+    assert_eq!(diagnostics.key_len, 5);
+    assert_eq!(diagnostics.columns.len(), 5);
+    for (i, column) in diagnostics.columns.iter().enumerate() {
+        assert_eq!(column.column, i);
+        assert!(!column.top_shifts.is_empty());
+        assert!(column.top_shifts.len() <= TOP_N_SHIFTS_PER_COLUMN);
+        assert!(column.score_gap().unwrap() >= 0.0);
+    }
+
+    assert!(decoder.key_length_diagnostics(&ciphertext, 0).is_none());
+
+    let short = decoder.key_length_diagnostics("ABCDEFGH", 4).unwrap();
+    assert!(short.columns.iter().all(|c| c.letter_count == 2 && c.top_shifts.is_empty() && c.score_gap().is_none()));
+}
+
+#[test]
+fn test_vigenere_rank_by_trigram_avg() {
+    let plaintext = "DEFENDTHEEASTWALLOFTHECASTLEATDAWNANDHOLDUNTILREINFORCEMENTSARRIVE";
+    let ciphertext = vigenere_encrypt(plaintext, "KEY");
+    let config = Config {
+        vigenere_rank_by_trigram_avg: true,
+        ..Default::default()
+    };
+    let decoder = VigenereDecoder::new(&config);
+
+    let results = decoder.decrypt_with_key_length(&ciphertext, 3);
+    assert_eq!(results[0].key, "KEY");
+    assert_eq!(results[0].plaintext, plaintext);
+    assert!((results[0].score - analysis::score_trigram_avg(plaintext)).abs() < 1e-9);
+
+    let summed = VigenereDecoder::new(&Config::default()).decrypt_with_key(&ciphertext, "KEY").unwrap();
+    assert!((summed.score - analysis::score_trigram_log_prob(plaintext)).abs() < 1e-9);
+}
+
+#[test]
+fn test_vigenere_reports_bigram_method_for_short_text() {
+    let plaintext = "DEFENDTHEEASTWALLOFTHECASTLEATDAWNANDHOLDUNTILREINFORCEMENTSARRIVE";
+    let ciphertext = vigenere_encrypt(plaintext, "KEY");
+
+    let default_decoder = VigenereDecoder::new(&Config::default());
+    let default_results = default_decoder.decrypt_with_key_length(&ciphertext, 3);
+    assert_eq!(default_decoder.scoring_method_for(&default_results), ScoringMethod::Trigram);
+
+    let config = Config {
+        bigram_scoring_max_len: plaintext.len() + 1,
+        ..Default::default()
+    };
+    let decoder = VigenereDecoder::new(&config);
+    let results = decoder.decrypt_with_key_length(&ciphertext, 3);
+    assert_eq!(decoder.scoring_method_for(&results), ScoringMethod::Bigram);
+    assert_eq!(results[0].key, "KEY");
+    assert_eq!(results[0].score, analysis::score_bigram_log_prob(plaintext));
+    assert!(results.windows(2).all(|pair| pair[0].score >= pair[1].score));
+}
+
+#[test]
+fn test_vigenere_chi_squared_column_method() {
+    let plaintext = "DEFENDTHEEASTWALLOFTHECASTLEATDAWNANDHOLDUNTILREINFORCEMENTSARRIVE";
+    let ciphertext = vigenere_encrypt(plaintext, "KEY");
+    let config = Config {
+        vigenere_column_method: ColumnShiftMethod::ChiSquared,
+        ..Default::default()
+    };
+
+    let results = VigenereDecoder::new(&config).decrypt_with_key_length(&ciphertext, 3);
+    assert_eq!(results[0].key, "KEY");
+    assert_eq!(results[0].plaintext, plaintext);
+}
+
+#[test]
+fn test_vigenere_explain_key_follows_configured_methods() {
+    let plaintext = "DEFENDTHEEASTWALLOFTHECASTLEATDAWNANDHOLDUNTILREINFORCEMENTSARRIVE";
+    let ciphertext = vigenere_encrypt(plaintext, "KEY");
+
+    let mic = VigenereDecoder::new(&Config::default()).explain_key(&ciphertext, "KEY");
+    assert_eq!(mic.len(), 4);
+    assert!(mic[0].contains("IC periodicity"));
+    assert!(mic[1].starts_with("Column 1: shift 10 (K), MIC "));
+
+    let config = Config {
+        vigenere_column_method: ColumnShiftMethod::ChiSquared,
+        vigenere_rank_lengths_by_expected_ic: true,
+        ..Default::default()
+    };
+    let chi_squared = VigenereDecoder::new(&config).explain_key(&ciphertext, "KEY");
+    assert!(chi_squared[0].contains("expected IC"));
+    assert!(chi_squared[1..].iter().all(|line| line.contains("chi-squared") && !line.contains("MIC")));
+}
+
+#[test]
+fn test_vigenere_identify_reports_consensus_length() {
+    let plaintext = "It is a truth universally acknowledged that a single man in possession of a good fortune must be in want of a wife. However little known the feelings or views of such a man may be on his first entering a neighbourhood, this truth is so well fixed in the minds of the surrounding families that he is considered the rightful property of some one or other of their daughters.";
+    let ciphertext = vigenere_encrypt(plaintext, "CIPHER");
+
+    let result = VigenereIdentifier::new(&Config::default()).identify(&ciphertext).unwrap();
+    let params = result.parameters.unwrap();
+    assert!(params.contains("Consensus length: 6"), "params were: {}", params);
+    match result.params {
+        CipherParams::Vigenere { candidate_key_lengths, consensus_key_length, ic, beaufort } => {
+            assert_eq!(consensus_key_length, Some(6));
+            assert!(candidate_key_lengths.contains(&6));
+            assert!(params.contains(&format!("Low IC ({:.4})", ic)));
+            assert!(!beaufort);
+        }
+        other => panic!("expected Vigenere params, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_vigenere_results_deduplicated_across_key_lengths() {
+    let plaintext = "It is a truth universally acknowledged that a single man in possession of a good fortune must be in want of a wife. However little known the feelings or views of such a man may be on his first entering a neighbourhood, this truth is so well fixed in the minds of the surrounding families.";
+    let ciphertext = vigenere_encrypt(plaintext, "KEY");
+
+    // Key length 6 finds "KEYKEY", which decrypts to exactly the same text as "KEY".
+    let config = Config { max_key_combinations: Some(20000), ..Default::default() };
+    let six = VigenereDecoder::new(&config).decrypt_with_key_length(&ciphertext, 6);
+    assert_eq!(six[0].key, "KEYKEY");
+    assert_eq!(six[0].plaintext, plaintext);
+
+    let results = VigenereDecoder::new(&config).decrypt(&ciphertext);
+    assert_eq!(results[0].key, "KEY");
+    assert_eq!(results.iter().filter(|attempt| attempt.plaintext == plaintext).count(), 1);
+
+    let mut plaintexts: Vec<&str> = results.iter().map(|attempt| attempt.plaintext.as_str()).collect();
+    plaintexts.sort();
+    plaintexts.dedup();
+    assert_eq!(plaintexts.len(), results.len());
+}
+
+#[test]
+fn test_vigenere_key_rotation_post_pass() {
+    let plaintext = "It is a truth universally acknowledged that a single man in possession of a good fortune must be in want of a wife.";
+    let ciphertext = vigenere_encrypt(plaintext, "LEMON");
+    let decoder = VigenereDecoder::new(&Config::default());
+
+    // "LEMON" rotated by 4 leaves every plaintext letter shifted by the same amount.
+    let rotated = decoder.decrypt_with_key(&ciphertext, "PIQSR").unwrap();
+    assert_ne!(rotated.plaintext, plaintext);
+
+    let refined = decoder.refine_key_rotation(&ciphertext, &rotated).unwrap();
+    assert_eq!(refined.key, "LEMON");
+    assert_eq!(refined.plaintext, plaintext);
+    assert!(refined.score > rotated.score);
+
+    let correct = decoder.decrypt_with_key(&ciphertext, "LEMON").unwrap();
+    assert!(decoder.refine_key_rotation(&ciphertext, &correct).is_none());
+}
+
+#[test]
+fn test_vigenere_identify_distinguishes_beaufort() {
+    let plaintext = "It is a truth universally acknowledged that a single man in possession of a good fortune must be in want of a wife. However little known the feelings or views of such a man may be on his first entering a neighbourhood, this truth is so well fixed in the minds of the surrounding families that he is considered the rightful property of some one or other of their daughters.";
+    // Beaufort (C = K - P) is the Atbash of the Vigenere encryption under the Atbash of the key.
+    let beaufort = cipher_utils::atbash(&vigenere_encrypt(plaintext, &cipher_utils::atbash("CIPHER")));
+    let identifier = VigenereIdentifier::new(&Config::default());
+
+    let result = identifier.identify(&beaufort).unwrap();
+    assert_eq!(result.cipher_name, "Vigenere");
+    assert!(matches!(result.params, CipherParams::Vigenere { beaufort: true, .. }));
+    assert!(result.parameters.unwrap().contains("Variant: Beaufort"));
+
+    let result = identifier.identify(&vigenere_encrypt(plaintext, "CIPHER")).unwrap();
+    assert_eq!(result.cipher_name, "Vigenere");
+    assert!(matches!(result.params, CipherParams::Vigenere { beaufort: false, .. }));
+    assert!(!result.parameters.unwrap().contains("Beaufort"));
+}
+
+#[test]
+fn test_vigenere_rank_lengths_by_expected_ic() {
+    let plaintext = "ALICEWASBEGINNINGTOGETVERYTIREDOFSITTINGBYHERSISTERONTHEBANKANDOFHAVINGNOTHINGTODOONCEORTWICESHEHADPEEPEDINTOTHEBOOKHERSISTERWASREADINGBUTITHADNOPICTURESORCONVERSATIONSINIT";
+    let ciphertext = vigenere_encrypt(plaintext, "CRYPTO");
+    let config = Config {
+        vigenere_rank_lengths_by_expected_ic: true,
+        max_key_combinations: Some(20000),
+        ..Default::default()
+    };
+
+    let results = VigenereDecoder::new(&config).decrypt(&ciphertext);
+    assert_eq!(results[0].key, "CRYPTO");
+    assert_eq!(results[0].plaintext, plaintext);
+}
+
+#[test]
+fn test_vigenere_decrypt_multi_pools_columns() {
+    let first = "Meet me at the old mill after dark and bring the documents we discussed yesterday.";
+    let second = "The shipment arrives on Tuesday morning so keep the warehouse doors unlocked for them.";
+    let first_ct = vigenere_encrypt(first, "SECRET");
+    let second_ct = vigenere_encrypt(second, "SECRET");
+    let decoder = VigenereDecoder::new(&Config { max_key_combinations: Some(20000), ..Default::default() });
+
+    // Each message alone gives MIC too few letters per column to recover the key.
+    assert_ne!(decoder.decrypt(&first_ct)[0].plaintext, first);
+    assert_ne!(decoder.decrypt(&second_ct)[0].plaintext, second);
+
+    let results = decoder.decrypt_multi(&[&first_ct, &second_ct]);
+    assert_eq!(results.len(), 2);
+    assert_eq!(results[0].0, first_ct);
+    assert_eq!(results[0].1.key, "SECRET");
+    assert_eq!(results[0].1.plaintext, first);
+    assert_eq!(results[1].0, second_ct);
+    assert_eq!(results[1].1.plaintext, second);
+}
+
+#[test]
+fn test_vigenere_force_identify_skips_ic_gate() {
+    let plaintext = "ALICEWASBEGINNINGTOGETVERYTIREDOFSITTINGBYHERSISTERONTHEBANKANDOFHAVINGNOTHINGTODOONCEORTWICESHEHADPEEPEDINTOTHEBOOKHERSISTERWASREADINGBUTITHADNOPICTURESORCONVERSATIONSINIT";
+    let ciphertext = vigenere_encrypt(plaintext, "BE");
+    let ic = analysis::calculate_ic(&ciphertext).unwrap();
+    assert!(ic > 0.060 && ic < 0.062, "ic was {}", ic);
+
+    assert!(VigenereIdentifier::new(&Config::default()).identify(&ciphertext).is_none());
+
+    let forced = Config { vigenere_force_identify: true, ..Default::default() };
+    let result = VigenereIdentifier::new(&forced).identify(&ciphertext).unwrap();
+    assert!(result.parameters.unwrap().starts_with("High IC"));
+    // Identified, but with less confidence than a low-IC ciphertext would get.
+    let long_key = VigenereIdentifier::new(&forced).identify(&vigenere_encrypt(plaintext, "CRYPTO")).unwrap();
+    assert!(result.confidence_score < long_key.confidence_score);
+}
+
+#[test]
+fn test_vigenere_tries_key_length_one_on_high_ic() {
+    let decoder = VigenereDecoder::new(&Config::default());
+    let plaintext = "It was the best of times it was the worst of times it was the age of wisdom it was the age of foolishness it was the epoch of belief it was the epoch of incredulity";
+    let ciphertext = cipher_utils::shift_char_string(plaintext, 5);
+    assert!(analysis::calculate_ic(&ciphertext).unwrap() > analysis::ENGLISH_IC - 0.01);
+
+    let results = decoder.decrypt(&ciphertext);
+    assert_eq!(results[0].key, "F");
+    assert_eq!(results[0].plaintext, plaintext);
+    assert_eq!(
+        results[0].note.as_deref(),
+        Some("Key length 1: the polyalphabetic analysis reduces to a Caesar cipher with shift 5.")
+    );
+}
@@ -0,0 +1,83 @@
+use peekaboo::ciphers::xor::XorDecoder;
+use peekaboo::decoder::Decoder;
+use peekaboo::config::{Config, OutputEncoding};
+
+
+fn xor_hex(plaintext: &str, key: &[u8]) -> String {
+    plaintext
+        .bytes()
+        .zip(key.iter().cycle())
+        .map(|(b, k)| format!("{:02x}", b ^ k))
+        .collect()
+}
+
+
+#[test]
+fn test_xor_single_byte() {
+    let config = Config::default();
+    let decoder = XorDecoder::new(&config);
+    let ciphertext = "1b37373331363f78151b7f2b783431333d78397828372d363c78373e783a393b3736";
+
+    let results = decoder.decrypt(ciphertext);
+    assert!(!results.is_empty());
+    assert_eq!(results[0].cipher_name, "XOR");
+    assert_eq!(results[0].key, "58");
+    assert_eq!(results[0].plaintext, "Cooking MC's like a pound of bacon");
+}
+
+#[test]
+fn test_xor_repeating_key() {
+    let config = Config::default();
+    let decoder = XorDecoder::new(&config);
+    let plaintext = "Meet me at the old mill at midnight and bring the documents we discussed last week, do not be late again";
+    let ciphertext = xor_hex(plaintext, b"KEY");
+
+    let results = decoder.decrypt(&ciphertext);
+    assert!(!results.is_empty());
+    assert_eq!(results[0].key, "4b4559");
+    assert_eq!(results[0].plaintext, plaintext);
+}
+
+#[test]
+fn test_xor_skips_readable_text() {
+    let decoder = XorDecoder::new(&Config::default());
+
+    assert!(decoder.skip_reason(&xor_hex("Attack at dawn", b"KEY")).is_none());
+    assert!(decoder.skip_reason("Attack\x01at\x02dawn").is_none());
+    assert!(decoder.skip_reason("Khoor zruog, wklv lv d whvw").is_some());
+    assert!(decoder.skip_reason("... --- ...").is_some());
+}
+
+#[test]
+fn test_xor_decrypt_with_key() {
+    let config = Config::default();
+    let decoder = XorDecoder::new(&config);
+    let ciphertext = xor_hex("Attack at dawn", b"KEY");
+
+    assert_eq!(decoder.decrypt_with_key(&ciphertext, "4b4559").unwrap().plaintext, "Attack at dawn");
+    assert_eq!(decoder.decrypt_with_key(&xor_hex("Attack", &[0x58]), "58").unwrap().plaintext, "Attack");
+    assert!(decoder.decrypt_with_key(&ciphertext, "zz").is_none());
+    assert!(decoder.decrypt("").is_empty());
+}
+
+#[test]
+fn test_xor_output_encoding_for_binary_plaintext() {
+    // XOR with 0x01 turns these into 0xfe 0x01 0x80 0x7e 0x00 0xc2, which is not UTF-8.
+    let ciphertext = "ff00817f01c3";
+
+    let hex_config = Config::builder().output_encoding(OutputEncoding::Hex).build().unwrap();
+    let attempt = XorDecoder::new(&hex_config).decrypt_with_key(ciphertext, "01").unwrap();
+    assert_eq!(attempt.plaintext, "fe01807e00c2");
+    assert!(attempt.plaintext.len().is_multiple_of(2) && attempt.plaintext.chars().all(|c| c.is_ascii_hexdigit()));
+
+    let base64_config = Config::builder().output_encoding(OutputEncoding::Base64).build().unwrap();
+    let attempt = XorDecoder::new(&base64_config).decrypt_with_key(ciphertext, "01").unwrap();
+    assert_eq!(attempt.plaintext, "/gGAfgDC");
+
+    let lossy = XorDecoder::new(&Config::default()).decrypt_with_key(ciphertext, "01").unwrap();
+    assert!(lossy.plaintext.contains('\u{FFFD}'));
+
+    // Text results stay readable whatever the encoding.
+    let text = XorDecoder::new(&hex_config).decrypt_with_key(&xor_hex("Hello there", b"K"), "4b").unwrap();
+    assert_eq!(text.plaintext, "Hello there");
+}